@@ -0,0 +1,83 @@
+//! Compares reading an input through a matched [RawReader] enum against reading it through a
+//! boxed trait object. Run with `cargo bench`.
+
+use std::{
+    hint::black_box,
+    io::Read,
+    time::{Duration, Instant},
+};
+
+use grab::{Input, RawReader};
+
+const INPUT_LEN: usize = 1 << 20;
+const CHUNK: usize = 64;
+const ROUNDS: u32 = 50;
+
+fn main() {
+    let text = "abcdefghijklmnopqrstuvwxyz\n".repeat(INPUT_LEN / 27);
+    let input = Input::with_defaults(&text).expect("text input");
+
+    let enum_time = bench("enum match", || {
+        let reader = input.access_enum().expect("access");
+        read_enum(reader)
+    });
+
+    let dyn_time = bench("trait object", || {
+        let reader: Box<dyn Read> = Box::new(input.access_enum().expect("access"));
+        read_dyn(reader)
+    });
+
+    println!(
+        "enum match is {:.2}x the speed of trait object reading",
+        dyn_time.as_secs_f64() / enum_time.as_secs_f64()
+    );
+}
+
+fn bench<F>(name: &str, mut f: F) -> Duration
+where
+    F: FnMut() -> usize,
+{
+    // Warm up
+    black_box(f());
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        black_box(f());
+    }
+    let elapsed = start.elapsed() / ROUNDS;
+
+    println!("{:>12}: {:?} per {} byte read", name, elapsed, INPUT_LEN);
+
+    elapsed
+}
+
+fn read_enum(reader: RawReader) -> usize {
+    let mut buf = [0u8; CHUNK];
+    let mut total = 0;
+
+    match reader {
+        RawReader::Text(mut cursor) => {
+            while let Ok(n @ 1..) = cursor.read(&mut buf) {
+                total += n;
+            }
+        }
+        mut other => {
+            while let Ok(n @ 1..) = other.read(&mut buf) {
+                total += n;
+            }
+        }
+    }
+
+    total
+}
+
+fn read_dyn(mut reader: Box<dyn Read>) -> usize {
+    let mut buf = [0u8; CHUNK];
+    let mut total = 0;
+
+    while let Ok(n @ 1..) = reader.read(&mut buf) {
+        total += n;
+    }
+
+    total
+}
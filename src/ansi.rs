@@ -0,0 +1,76 @@
+//! Stripping of ANSI escape sequences, as used by [strip_ansi][crate::Builder::strip_ansi].
+//!
+//! This handles CSI sequences (e.g the SGR colour codes `ESC [ 31 m`), OSC sequences (e.g
+//! terminal titles or hyperlinks, terminated by `BEL` or `ESC \`) and other two character
+//! escapes. A lone `ESC` at the end of the text is removed as well.
+
+const ESC: char = '\u{1b}';
+const BEL: char = '\u{7}';
+
+/// Remove every ANSI escape sequence from the given text, leaving the visible text in place
+pub(crate) fn strip(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != ESC {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            // CSI: parameter and intermediate bytes, ended by a final byte in '@'..='~'
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC: anything, ended by BEL or ST (ESC \)
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == BEL {
+                        break;
+                    }
+
+                    if c == ESC && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            // Any other escape is a single character
+            Some(_) | None => {}
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_sgr() {
+        let text = "\u{1b}[1;31mred\u{1b}[0m and \u{1b}[38;5;82mgreen\u{1b}[m";
+
+        assert_eq!(strip(text), "red and green")
+    }
+
+    #[test]
+    fn strip_osc() {
+        let text =
+            "\u{1b}]0;title\u{7}a \u{1b}]8;;https://example.com\u{1b}\\link\u{1b}]8;;\u{1b}\\";
+
+        assert_eq!(strip(text), "a link")
+    }
+
+    #[test]
+    fn strip_other() {
+        assert_eq!(strip("\u{1b}7saved\u{1b}8"), "saved");
+        assert_eq!(strip("plain text, ünïcödé"), "plain text, ünïcödé");
+        assert_eq!(strip("trailing\u{1b}"), "trailing")
+    }
+}
@@ -0,0 +1,138 @@
+//! Expansion of argfiles, files which hold one argument per line, into the arguments they
+//! contain. See [Config::expand_argfiles][crate::Config::expand_argfiles].
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::error::access::AccessError;
+
+/// Expand every argument starting with `marker` into the lines of the file it names, following
+/// argfiles named inside of argfiles at most `max_depth` deep.
+pub(crate) fn expand<I, S>(
+    args: I,
+    marker: &str,
+    max_depth: usize,
+) -> Result<Vec<String>, AccessError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut expander = Expander {
+        marker,
+        max_depth,
+        visiting: Vec::new(),
+        out: Vec::new(),
+    };
+
+    for arg in args {
+        expander.arg(arg.as_ref())?;
+    }
+
+    Ok(expander.out)
+}
+
+struct Expander<'a> {
+    marker: &'a str,
+    max_depth: usize,
+    /// Canonical paths of the argfiles currently being expanded, outermost first
+    visiting: Vec<PathBuf>,
+    out: Vec<String>,
+}
+
+impl Expander<'_> {
+    fn arg(&mut self, arg: &str) -> Result<(), AccessError> {
+        match arg.strip_prefix(self.marker) {
+            Some(path) if !path.is_empty() => self.argfile(Path::new(path)),
+            _ => {
+                self.out.push(arg.to_string());
+
+                Ok(())
+            }
+        }
+    }
+
+    fn argfile(&mut self, path: &Path) -> Result<(), AccessError> {
+        let canonical =
+            fs::canonicalize(path).map_err(|e| AccessError::file_with_context(e, path))?;
+
+        // A cycle would otherwise only be caught by the depth limit, with a less helpful error
+        if self.visiting.contains(&canonical) {
+            return Err(AccessError::argfile_cycle(path));
+        }
+
+        if self.visiting.len() >= self.max_depth {
+            return Err(AccessError::argfile_depth(path, self.max_depth));
+        }
+
+        let contents =
+            fs::read_to_string(&canonical).map_err(|e| AccessError::file_with_context(e, path))?;
+
+        self.visiting.push(canonical);
+
+        for line in contents.lines().filter(|line| !line.is_empty()) {
+            self.arg(line)?;
+        }
+
+        self.visiting.pop();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{error::access::Kind, test_support::TempDir};
+
+    #[test]
+    fn expand_nested() {
+        let dir = TempDir::new("argfile-nested");
+        let inner = dir.write("inner", "@b.txt\r\n\n-\n");
+        let outer = dir.write("outer", format!("--flag\n@@{}\nlast\n", inner.display()));
+
+        let args = expand(["first", &format!("@@{}", outer.display()), "@@"], "@@", 10).unwrap();
+
+        assert_eq!(args, ["first", "--flag", "@b.txt", "-", "last", "@@"])
+    }
+
+    #[test]
+    fn expand_depth_limit() {
+        let dir = TempDir::new("argfile-depth");
+
+        let mut next = dir.write("0", "end");
+        for i in 1..=5 {
+            next = dir.write(&i.to_string(), format!("@@{}", next.display()));
+        }
+
+        let root = format!("@@{}", next.display());
+
+        assert_eq!(expand([&root], "@@", 6).unwrap(), ["end"]);
+
+        let err = expand([&root], "@@", 5).unwrap_err();
+
+        assert_eq!(err.kind(), Kind::ArgfileDepth)
+    }
+
+    #[test]
+    fn expand_cycle() {
+        let dir = TempDir::new("argfile-cycle");
+
+        // Refers back to itself by a different, but equivalent path
+        let alias = dir.join(".").join("self");
+        let path = dir.write("self", format!("arg\n@@{}\n", alias.display()));
+
+        let err = expand([format!("@@{}", path.display())], "@@", 100).unwrap_err();
+
+        assert_eq!(err.kind(), Kind::ArgfileCycle)
+    }
+
+    #[test]
+    fn expand_missing() {
+        let err = expand(["@@/grab/does/not/exist"], "@@", 10).unwrap_err();
+
+        assert_eq!(err.kind(), Kind::File)
+    }
+}
@@ -5,12 +5,24 @@
 //! [Stdin](std::io::Stdin).
 
 use crate::{
-    error::input::InputError,
-    input::Input,
-    parsers::{File, InputType, Parser, Stdin, Text, WeightedParser as WP},
+    error::{
+        build::{BuildError, WeightConflict},
+        input::{EKind, InputError},
+    },
+    input::{Decoder, Input, InputKind, ReadOptions},
+    parsers::{
+        Base64, Env, File, InputType, Named, Parser, ParserName, Stdin, Text, Url,
+        WeightedParser as WP,
+    },
 };
 
-use std::{ffi::OsStr, fmt};
+use std::{
+    borrow::Cow,
+    ffi::OsStr,
+    fmt,
+    io::Read,
+    sync::{Arc, OnceLock},
+};
 
 /// Represents a set of parsers that will be called in ascending order according to their weight
 /// until the list is exhausted or a parser returns successfully.
@@ -25,13 +37,319 @@ pub struct Config {
 impl Config {
     /// Attempt to parse the input into a concrete handle which can be [accessed](Input::access)
     pub fn parse(&self, input: &str) -> Result<Input, InputError> {
-        self.parse_str(input).map(Input::from_input_type)
+        self.parse_str(input).map(|kind| self.finish(kind, input))
     }
 
     /// Attempt to parse the given [OsStr] into a concrete handle which can be
     /// [accessed](Input::access).
     pub fn parse_os(&self, input: &OsStr) -> Result<Input, InputError> {
-        self.parse_os_str(input).map(Input::from_input_type)
+        self.parse_os_str(input)
+            .map(|kind| self.finish(kind, &input.to_string_lossy()))
+    }
+
+    /// Like [parse][Config::parse], but additionally returns the name of every parser that was
+    /// attempted, in the order they were tried. The last name is always the parser which
+    /// matched, unless the input named a [slot][Builder::slot], in which case no parsers were
+    /// tried.
+    pub fn parse_trace(&self, input: &str) -> Result<(Input, Vec<ParserName>), InputError> {
+        let mut trace = Vec::new();
+        let kind = self.parse_traced(input, Some(&mut trace))?;
+
+        Ok((self.finish(kind, input), trace))
+    }
+
+    /// Like [parse][Config::parse], but inputs which refer to several sources, e.g a `Glob`
+    /// pattern, are split into an [Input] per source. Other inputs produce a single [Input].
+    pub fn parse_many(&self, input: &str) -> Result<Vec<Input>, InputError> {
+        let inputs = match self.parse(input)?.into_kind() {
+            InputType::Files(files) => files
+                .into_iter()
+                .map(|f| self.new_input(InputType::File(f), input))
+                .collect(),
+            kind => vec![self.new_input(kind, input)],
+        };
+
+        Ok(inputs)
+    }
+
+    /// Expand every argument starting with the [argfile marker][Builder::argfile_marker], e.g
+    /// `@@args.txt`, into the lines of the file it names, leaving other arguments as they are.
+    /// Each non empty line is an argument, and may itself name another argfile, up to
+    /// [max_argfile_depth][Builder::max_argfile_depth] deep. Expanded arguments can then be
+    /// [parsed](Config::parse) as usual.
+    ///
+    /// ```no_run
+    /// use grab::Config;
+    ///
+    /// let cfg = Config::default();
+    ///
+    /// for arg in cfg.expand_argfiles(std::env::args().skip(1))? {
+    ///     let input = cfg.parse(&arg)?;
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(feature = "argfile")]
+    pub fn expand_argfiles<I, S>(
+        &self,
+        args: I,
+    ) -> Result<Vec<String>, crate::error::access::AccessError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        crate::argfile::expand(
+            args,
+            self.inner.get_argfile_marker(),
+            self.inner.get_max_argfile_depth(),
+        )
+    }
+
+    /// Split the input on the given delimiter, [parsing](Config::parse) each item into its own
+    /// [Input], e.g `@a.txt,@b.txt,-` split on `,`. Parsing stops at the first item which fails,
+    /// and empty items fail with [EKind::MISSING_VALUE].
+    pub fn parse_list(&self, input: &str, delimiter: char) -> Result<Vec<Input>, InputError> {
+        input
+            .split(delimiter)
+            .enumerate()
+            .map(|(i, item)| {
+                let context =
+                    |e: InputError| e.with(|this| this.add_context(format!("list item {}", i + 1)));
+
+                match item {
+                    "" => Err(context(EKind::MISSING_VALUE.into())),
+                    item => self.parse(item).map_err(context),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the [name](ParserName::as_str) of every enabled parser, in the order they are
+    /// tried
+    pub fn parser_names(&self) -> Vec<&'static str> {
+        self.with_parsers(|parsers| {
+            parsers
+                .iter()
+                .filter_map(|o| *o)
+                .map(|p| p.name().as_str())
+                .collect()
+        })
+    }
+
+    /// Returns the [name](ParserName::as_str) of every enabled parser, in the order they are
+    /// tried, paired with the marker that triggers it. Parsers which accept several markers, like
+    /// [File::markers] or a [File::append_marker], appear once per marker.
+    ///
+    /// This is useful for describing the accepted input in help text.
+    pub fn markers(&self) -> Vec<(&'static str, String)> {
+        self.with_parsers(|parsers| {
+            parsers
+                .iter()
+                .filter_map(|o| *o)
+                .flat_map(|p| {
+                    p.markers()
+                        .into_iter()
+                        .map(move |marker| (p.name().as_str(), marker.to_string()))
+                })
+                .collect()
+        })
+    }
+
+    /// Returns the prefixes a user might start an input with, for generating shell completions
+    /// that hint at the accepted input. This is every non-empty marker of the enabled parsers,
+    /// including `file://` if [File::file_uri] is set, the marker of every
+    /// [marker_chain][Builder::marker_chain], and every [slot][Builder::slot] along with its
+    /// marker. Each hint appears once, roughly in the order parsing considers them.
+    ///
+    /// ```
+    /// use grab::Config;
+    ///
+    /// assert_eq!(Config::default().completion_hints(), ["@", "-"]);
+    /// ```
+    pub fn completion_hints(&self) -> Vec<String> {
+        let chains = self.inner.chains.iter().map(|(marker, _)| marker.clone());
+        let slots = self
+            .inner
+            .slots
+            .iter()
+            .map(|(name, _)| format!("{}{}", self.inner.get_slot_marker(), name));
+        let file_uri = self
+            .inner
+            .file
+            .as_ref()
+            .filter(|file| file.accepts_file_uri())
+            .map(|_| String::from("file://"));
+
+        let mut hints: Vec<String> = Vec::new();
+        for hint in slots
+            .chain(chains)
+            .chain(self.markers().into_iter().map(|(_, marker)| marker))
+            .chain(file_uri)
+        {
+            if !hint.is_empty() && !hints.contains(&hint) {
+                hints.push(hint);
+            }
+        }
+
+        hints
+    }
+
+    /// A config using the conventions of the current platform, which otherwise behaves like
+    /// [Config::default]:
+    ///
+    /// - On unix, this is the same as the default config, files are marked with `@` and stdin
+    ///   with `-`.
+    /// - On Windows, absolute paths starting with a drive letter, like `C:\notes.txt` or
+    ///   `C:/notes.txt`, are additionally treated as files without needing a marker.
+    pub fn platform_default() -> Self {
+        #[cfg(windows)]
+        let file = File::new().with(|this| this.parser(crate::parsers::drive_path_parser));
+        #[cfg(not(windows))]
+        let file = File::new();
+
+        let mut builder = Builder::new();
+        builder.text().stdin().with_file(file);
+
+        builder.build()
+    }
+
+    /// The config [Input]'s [Display][fmt::Display] form is written for, which its FromStr
+    /// parses with. This is the [default][Config::default] config, additionally using `\` as its
+    /// [escape][Builder::escape] and accepting `file:` URIs, so every input the default config
+    /// produces can be displayed in a form which reads back as the same input.
+    pub(crate) fn canonical() -> &'static Config {
+        static CANONICAL: OnceLock<Config> = OnceLock::new();
+
+        CANONICAL.get_or_init(|| {
+            let file = File::new().with(|this| this.file_uri(true));
+
+            Builder::new()
+                .with(|this| this.text().stdin().with_file(file.clone()).escape("\\"))
+                .build()
+        })
+    }
+
+    /// Convert this config back into the [Builder] it was built from, allowing it to be tweaked
+    /// and rebuilt.
+    ///
+    /// ```
+    /// use grab::Config;
+    ///
+    /// // The default config, without stdin
+    /// let cfg = Config::default()
+    ///     .into_builder()
+    ///     .with(|this| this.without_stdin())
+    ///     .build();
+    /// ```
+    pub fn into_builder(self) -> Builder {
+        self.inner
+    }
+
+    /// If the input starts with a [literal_prefix][Builder::literal_prefix], or is
+    /// [escaped][Builder::escape], returns the input as text with the prefix removed
+    fn literal(&self, input: Option<&str>) -> Option<InputType> {
+        let input = input?;
+
+        let literal = self
+            .inner
+            .literal_prefix
+            .as_deref()
+            .and_then(|prefix| input.strip_prefix(prefix));
+
+        if let Some(text) = literal {
+            return Some(InputType::UTF8(text.to_string()));
+        }
+
+        let escape = self.inner.escape.as_deref().filter(|e| !e.is_empty())?;
+        let rest = input.strip_prefix(escape)?;
+
+        match self.is_marked(strip_all(rest, escape)) {
+            true => Some(InputType::UTF8(rest.to_string())),
+            false => None,
+        }
+    }
+
+    /// Escape the given text, if needed, such that parsing the result produces the same text.
+    /// Text only needs escaping if it (ignoring any leading escapes) would be parsed as something
+    /// else, so returns None if no [escape][Builder::escape] is configured but escaping is needed.
+    pub(crate) fn escape_text<'a>(&self, text: &'a str) -> Option<Cow<'a, str>> {
+        let escape = self.inner.escape.as_deref().filter(|e| !e.is_empty());
+
+        let core = match escape {
+            Some(escape) => strip_all(text, escape),
+            None => text,
+        };
+
+        match (self.is_marked(core), escape) {
+            (false, _) => Some(Cow::Borrowed(text)),
+            (true, Some(escape)) => Some(Cow::Owned(format!("{}{}", escape, text))),
+            (true, None) => None,
+        }
+    }
+
+    /// Checks whether the enabled parsers would parse input as anything but the input itself, as
+    /// text
+    fn is_marked(&self, input: &str) -> bool {
+        !matches!(self.parse_plain(input, None), Ok(InputType::UTF8(ref text)) if text == input)
+    }
+
+    /// If the input names a [slot][Builder::slot], returns the slot's input
+    fn slot(&self, input: Option<&str>) -> Option<InputType> {
+        let name = input?.strip_prefix(self.inner.get_slot_marker())?;
+
+        self.inner
+            .slots
+            .iter()
+            .find(|(slot, _)| slot == name)
+            .map(|(_, kind)| kind.clone())
+    }
+
+    /// If the input starts with the marker of a [marker_chain][Builder::marker_chain], tries
+    /// each of the chain's interpretations in turn, returning the result along with the name of
+    /// every interpretation tried
+    fn chain(
+        &self,
+        input: Option<&str>,
+    ) -> Option<(Result<InputType, InputError>, Vec<ParserName>)> {
+        let input = input?;
+        let (marker, value, chain) = self.inner.chains.iter().find_map(|(marker, chain)| {
+            input
+                .strip_prefix(marker.as_str())
+                .map(|value| (marker, value, chain))
+        })?;
+
+        let mut trace = Vec::new();
+
+        for &interpretation in chain {
+            trace.push(interpretation.name());
+
+            if let Some(kind) = interpretation.interpret(value, &self.inner) {
+                return Some((Ok(kind), trace));
+            }
+        }
+
+        let mut error = InputError::new(EKind::empty());
+        for interpretation in chain {
+            error.insert(interpretation.kind());
+        }
+        error.add_context(format!("no interpretation of {:?} applies", marker));
+
+        Some((Err(error), trace))
+    }
+
+    fn new_input(&self, kind: InputType, spec: &str) -> Input {
+        Input::with_options(kind, self.inner.read.clone()).with_spec(spec)
+    }
+
+    /// Turn a successfully parsed input into an [Input], calling the
+    /// [on_parse][Builder::on_parse] hook
+    fn finish(&self, kind: InputType, spec: &str) -> Input {
+        let parsed = self.new_input(kind, spec);
+
+        if let Some(ref hook) = self.inner.on_parse {
+            hook.call(spec, parsed.kind());
+        }
+
+        parsed
     }
 
     /// Generates a list of parsers from the available, sorts them by weight,
@@ -40,37 +358,69 @@ impl Config {
     where
         F: FnMut(&[Option<&dyn WP>]) -> R,
     {
-        let b = &self.inner;
-        let mut callback = f;
+        self.inner.with_parsers(f)
+    }
 
-        let mut list = [
-            b.file.as_ref().map(|p| p as &dyn WP),
-            b.stdin.as_ref().map(|p| p as &dyn WP),
-            b.text.as_ref().map(|p| p as &dyn WP),
-        ];
+    /// Checks whether the given parser should be skipped for this input, before it is run.
+    ///
+    /// Currently, this only prevents the text parser from accepting input that starts with the
+    /// marker of another parser, if [text_rejects_markers][Builder::text_rejects_markers] is set.
+    fn guard(
+        &self,
+        parser: &dyn WP,
+        parsers: &[Option<&dyn WP>],
+        input: Option<&str>,
+    ) -> Result<(), InputError> {
+        if !self.inner.text_rejects_markers || parser.name() != ParserName::Text {
+            return Ok(());
+        }
 
-        // Sort parsers by weight, with lower numbers taking
-        // priority.
-        list.sort_by_key(|opt| opt.map(|p| p.weight()));
+        let marked = |input: &str| {
+            parsers
+                .iter()
+                .filter_map(|o| *o)
+                .filter(|p| p.name() != ParserName::Text)
+                .flat_map(|p| p.markers())
+                .any(|marker| !marker.is_empty() && input.starts_with(marker))
+        };
+
+        match input {
+            Some(input) if marked(input) => Err(EKind::TEXT.into()),
+            _ => Ok(()),
+        }
+    }
 
-        callback(&list)
+    /// Checks that the given input type doesn't expand into more inputs than allowed
+    fn check_expanded(&self, kind: InputType) -> Result<InputType, InputError> {
+        match (&kind, self.inner.max_expanded) {
+            (InputType::Files(files), Some(max)) if files.len() > max => {
+                Err(InputError::new(EKind::TOO_MANY_INPUTS).with(|this| {
+                    this.add_context(format!("input expands past the limit of {} inputs", max))
+                }))
+            }
+            _ => Ok(kind),
+        }
     }
 
     /// Iterates over the given list of parsers, trying the given closure on each
     /// and returning the first success.
     ///
     /// Notably, this function _does not_ provide the input on which a parser
-    /// operates, this should be pulled in by the closure.
-    fn apply<'a, F, I>(&self, parsers: I, mut f: F) -> Result<InputType, InputError>
+    /// operates, this should be pulled in by the closure. The given input is only used for
+    /// describing the error if every parser fails.
+    fn apply<'a, F, I>(&self, input: &str, parsers: I, mut f: F) -> Result<InputType, InputError>
     where
         F: FnMut(&dyn WP) -> Result<InputType, InputError>,
         I: IntoIterator<Item = &'a dyn WP>,
     {
         let mut error: Option<InputError> = None;
+        let mut tried = Vec::new();
 
         for parser in parsers {
+            tried.push(parser.name().as_str());
+
             match f(parser) {
-                Ok(success) => return Ok(success),
+                Ok(success) => return self.check_expanded(success),
                 Err(e) => match error {
                     Some(ref mut prev) => {
                         prev.extend(e);
@@ -80,29 +430,113 @@ impl Config {
             }
         }
 
-        Err(error.expect("Config should never have less than one parser, this is a bug"))
+        let mut error =
+            error.expect("Config should never have less than one parser, this is a bug");
+        error.set_unmatched(input, tried);
+
+        Err(error)
     }
 }
 
-impl Parser for Config {
-    fn parse_str(&self, input: &str) -> Result<InputType, InputError> {
+impl Config {
+    /// Parse the input, trying literal text, then slots, then marker chains and finally the
+    /// enabled parsers. If given, the name of every parser attempted is pushed onto trace.
+    fn parse_traced(
+        &self,
+        input: &str,
+        mut trace: Option<&mut Vec<ParserName>>,
+    ) -> Result<InputType, InputError> {
+        if let Some(text) = self.literal(Some(input)) {
+            trace
+                .iter_mut()
+                .for_each(|trace| trace.push(ParserName::Text));
+            return Ok(text);
+        }
+
+        if let Some(kind) = self.slot(Some(input)) {
+            return Ok(kind);
+        }
+
+        if let Some((kind, tried)) = self.chain(Some(input)) {
+            trace.iter_mut().for_each(|trace| trace.extend(&tried));
+            return kind;
+        }
+
+        self.parse_plain(input, trace)
+    }
+
+    /// Parse the input with the enabled parsers, ignoring any literal or escape prefixes
+    fn parse_plain(
+        &self,
+        input: &str,
+        mut trace: Option<&mut Vec<ParserName>>,
+    ) -> Result<InputType, InputError> {
         self.with_parsers(|parsers| {
             let iter = parsers.iter().filter_map(|o| *o);
-            self.apply(iter, |p| p.parse_str(input))
+            self.apply(input, iter, |p| {
+                trace.iter_mut().for_each(|trace| trace.push(p.name()));
+                self.guard(p, parsers, Some(input))?;
+                p.parse_str(input)
+            })
         })
     }
+}
+
+/// Strip every repetition of prefix from the start of s
+fn strip_all<'a>(mut s: &'a str, prefix: &str) -> &'a str {
+    while let Some(rest) = s.strip_prefix(prefix) {
+        s = rest;
+    }
+
+    s
+}
+
+impl Parser for Config {
+    fn parse_str(&self, input: &str) -> Result<InputType, InputError> {
+        self.parse_traced(input, None)
+    }
 
     fn parse_os_str(&self, input: &OsStr) -> Result<InputType, InputError> {
+        if let Some(text) = self.literal(input.to_str()) {
+            return Ok(text);
+        }
+
+        if let Some(kind) = self.slot(input.to_str()) {
+            return Ok(kind);
+        }
+
+        if let Some((kind, _)) = self.chain(input.to_str()) {
+            return kind;
+        }
+
         self.with_parsers(|parsers| {
             let iter = parsers.iter().filter_map(|o| *o);
-            self.apply(iter, |p| p.parse_os_str(input))
+            self.apply(&input.to_string_lossy(), iter, |p| {
+                self.guard(p, parsers, input.to_str())?;
+                p.parse_os_str(input)
+            })
         })
     }
 
     fn parse_bytes(&self, input: &[u8]) -> Result<InputType, InputError> {
+        if let Some(text) = self.literal(std::str::from_utf8(input).ok()) {
+            return Ok(text);
+        }
+
+        if let Some(kind) = self.slot(std::str::from_utf8(input).ok()) {
+            return Ok(kind);
+        }
+
+        if let Some((kind, _)) = self.chain(std::str::from_utf8(input).ok()) {
+            return kind;
+        }
+
         self.with_parsers(|parsers| {
             let iter = parsers.iter().filter_map(|o| *o);
-            self.apply(iter, |p| p.parse_bytes(input))
+            self.apply(&String::from_utf8_lossy(input), iter, |p| {
+                self.guard(p, parsers, std::str::from_utf8(input).ok())?;
+                p.parse_bytes(input)
+            })
         })
     }
 }
@@ -123,6 +557,33 @@ impl fmt::Debug for Config {
             dbg.field("file", &file);
         }
 
+        if let Some(url) = &self.inner.url {
+            dbg.field("url", &url);
+        }
+
+        if let Some(env) = &self.inner.env {
+            dbg.field("env", &env);
+        }
+
+        if let Some(base64) = &self.inner.base64 {
+            dbg.field("base64", &base64);
+        }
+
+        #[cfg(feature = "glob")]
+        if let Some(glob) = &self.inner.glob {
+            dbg.field("glob", &glob);
+        }
+
+        #[cfg(feature = "tcp")]
+        if let Some(tcp) = &self.inner.tcp {
+            dbg.field("tcp", &tcp);
+        }
+
+        #[cfg(feature = "clipboard")]
+        if let Some(clipboard) = &self.inner.clipboard {
+            dbg.field("clipboard", &clipboard);
+        }
+
         dbg.finish()
     }
 }
@@ -137,6 +598,89 @@ impl Default for Config {
     }
 }
 
+/// The order in which a [Config] tries its parsers, see [Builder::order]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Order {
+    /// Try parsers in ascending order of their weight. This is the default
+    #[default]
+    Weight,
+    /// Try parsers in the order they were added to the [Builder], ignoring their weights
+    Registration,
+}
+
+/// One way of interpreting the value following the marker of a
+/// [marker_chain][Builder::marker_chain]. Each applies only to some values, with the
+/// interpretations of a chain tried in turn until one does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Interpretation {
+    /// Read from stdin, if the value is empty, i.e the marker was given on its own
+    Stdin,
+    /// Read the file at the value, if the value is the path of an existing file
+    File,
+    /// Read the environment variable named by the value, if it is set
+    Env,
+    /// Use the value itself as text, which always applies unless the [text](Text) parser
+    /// rejects it, e.g for being longer than its [max_len][Text::max_len]
+    Text,
+}
+
+impl Interpretation {
+    /// Returns the input this interpretation produces for the value, if it applies. Values are
+    /// read with the builder's parser of the same kind, or one with default settings if that
+    /// parser isn't enabled.
+    fn interpret(self, value: &str, builder: &Builder) -> Option<InputType> {
+        match self {
+            Self::Stdin if value.is_empty() => Some(InputType::Stdin(
+                builder
+                    .stdin
+                    .as_ref()
+                    .map(Stdin::options)
+                    .unwrap_or_default(),
+            )),
+            Self::File if !value.is_empty() => {
+                let parsed = match builder.file {
+                    Some(ref file) => file.parse_path(value),
+                    None => File::new().parse_path(value),
+                };
+
+                match parsed {
+                    Ok(InputType::File(file)) if file.path.is_file() => Some(InputType::File(file)),
+                    _ => None,
+                }
+            }
+            Self::Env if !value.is_empty() && std::env::var_os(value).is_some() => {
+                Some(InputType::Env(value.to_string()))
+            }
+            Self::Text => match builder.text {
+                Some(ref text) => text
+                    .parse_str(&format!("{}{}", Named::marker(text), value))
+                    .ok(),
+                None => Some(InputType::UTF8(value.to_string())),
+            },
+            _ => None,
+        }
+    }
+
+    fn kind(self) -> EKind {
+        match self {
+            Self::Stdin => EKind::STDIN,
+            Self::File => EKind::FILE,
+            Self::Env => EKind::ENV,
+            Self::Text => EKind::TEXT,
+        }
+    }
+
+    fn name(self) -> ParserName {
+        match self {
+            Self::Stdin => ParserName::Stdin,
+            Self::File => ParserName::File,
+            Self::Env => ParserName::Env,
+            Self::Text => ParserName::Text,
+        }
+    }
+}
+
 /// A [Config] builder, you can use this struct to customize which parsers are available to be called
 /// when attempting to parse input.
 ///
@@ -147,6 +691,30 @@ pub struct Builder {
     stdin: Option<Stdin>,
     file: Option<File>,
     text: Option<Text>,
+    url: Option<Url>,
+    env: Option<Env>,
+    base64: Option<Base64>,
+    #[cfg(feature = "glob")]
+    glob: Option<crate::parsers::Glob>,
+    #[cfg(feature = "tcp")]
+    tcp: Option<crate::parsers::Tcp>,
+    #[cfg(feature = "clipboard")]
+    clipboard: Option<crate::parsers::Clipboard>,
+    registered: Vec<ParserName>,
+    order: Order,
+    max_expanded: Option<usize>,
+    text_rejects_markers: bool,
+    literal_prefix: Option<String>,
+    chains: Vec<(String, Vec<Interpretation>)>,
+    slots: Vec<(String, InputType)>,
+    slot_marker: Option<String>,
+    #[cfg(feature = "argfile")]
+    argfile_marker: Option<String>,
+    #[cfg(feature = "argfile")]
+    max_argfile_depth: Option<usize>,
+    escape: Option<String>,
+    on_parse: Option<ParseHook>,
+    read: ReadOptions,
 }
 
 impl Builder {
@@ -155,6 +723,13 @@ impl Builder {
         Self::default()
     }
 
+    /// The smallest useful [Config], which only reads from stdin and text, for contexts where
+    /// accessing the filesystem is undesirable. Input which would be a file in the default
+    /// config, e.g `@file`, is text.
+    pub fn minimal() -> Config {
+        Self::new().with(|this| this.text().stdin()).build()
+    }
+
     /// Convenience function for applying configuration options
     pub fn with<F>(self, f: F) -> Self
     where
@@ -180,22 +755,110 @@ impl Builder {
             "A grab::Builder must contain at least one parser"
         );
 
-        Config { inner: self }
+        self.assemble()
     }
 
     /// Attempt to create a [Config] from the given parser,
     /// if the current configuration is valid, returning the
     /// builder otherwise.
     ///
-    /// This is the safe variant of [build][Builder::build]
+    /// This is the safe variant of [build][Builder::build], and additionally rejects parsers
+    /// which share a weight, see [check_weights][Builder::check_weights].
+    #[allow(clippy::result_large_err)]
     pub fn try_build(self) -> Result<Config, Self> {
-        if self.is_valid() {
-            return Ok(Config { inner: self });
+        if self.is_valid() && self.check_weights().is_ok() {
+            return Ok(self.assemble());
         }
 
         Err(self)
     }
 
+    /// Create a [Config] from the given parser, returning a [BuildError] describing the problem
+    /// if the configuration is invalid. Unlike [try_build][Builder::try_build], the builder
+    /// itself is not returned on error, which makes this convenient to use with `?`.
+    ///
+    /// In addition to requiring at least one parser, this checks that no two parsers share a
+    /// weight when [ordered by weight][Order::Weight], as the order they would be tried in is
+    /// ambiguous.
+    pub fn finish(self) -> Result<Config, BuildError> {
+        if !self.is_valid() {
+            return Err(BuildError::no_parsers());
+        }
+
+        self.check_weights()?;
+
+        Ok(self.assemble())
+    }
+
+    /// Checks that no two of the enabled parsers share a weight, returning the first pair that
+    /// does. As parsers with the same weight are tried in an unspecified order, one may silently
+    /// shadow the other.
+    ///
+    /// Weights are irrelevant when parsers are [ordered by registration][Order::Registration],
+    /// so this always succeeds in that case.
+    pub fn check_weights(&self) -> Result<(), WeightConflict> {
+        if self.order != Order::Weight {
+            return Ok(());
+        }
+
+        self.with_parsers(|parsers| {
+            let enabled: Vec<_> = parsers.iter().filter_map(|o| *o).collect();
+
+            // Parsers are sorted by weight, so any collision is between neighbours
+            match enabled.windows(2).find(|w| w[0].weight() == w[1].weight()) {
+                Some(w) => Err(WeightConflict::new(w[0].name(), w[1].name(), w[0].weight())),
+                None => Ok(()),
+            }
+        })
+    }
+
+    /// Call the given closure with every parser, sorted in the order they should be tried
+    fn with_parsers<F, R>(&self, f: F) -> R
+    where
+        F: FnMut(&[Option<&dyn WP>]) -> R,
+    {
+        let b = self;
+        let mut callback = f;
+
+        let mut list = [
+            b.file.as_ref().map(|p| p as &dyn WP),
+            b.stdin.as_ref().map(|p| p as &dyn WP),
+            b.text.as_ref().map(|p| p as &dyn WP),
+            b.url.as_ref().map(|p| p as &dyn WP),
+            b.env.as_ref().map(|p| p as &dyn WP),
+            b.base64.as_ref().map(|p| p as &dyn WP),
+            #[cfg(feature = "glob")]
+            b.glob.as_ref().map(|p| p as &dyn WP),
+            #[cfg(feature = "tcp")]
+            b.tcp.as_ref().map(|p| p as &dyn WP),
+            #[cfg(feature = "clipboard")]
+            b.clipboard.as_ref().map(|p| p as &dyn WP),
+        ];
+
+        match b.order {
+            // Sort parsers by weight, with lower numbers taking
+            // priority.
+            Order::Weight => list.sort_by_key(|opt| opt.map(|p| p.weight())),
+            // Sort parsers by the order they were added to the builder
+            Order::Registration => list.sort_by_key(|opt| {
+                opt.map(|p| b.registered.iter().position(|&name| name == p.name()))
+            }),
+        }
+
+        callback(&list)
+    }
+
+    /// Propagate any builder wide settings to the parsers that need them
+    #[cfg_attr(not(feature = "glob"), allow(unused_mut))]
+    fn assemble(mut self) -> Config {
+        #[cfg(feature = "glob")]
+        if let Some(ref mut glob) = self.glob {
+            glob.set_limit(self.max_expanded);
+        }
+
+        Config { inner: self }
+    }
+
     /// Enable [text](Text) parsing, with the default parser
     pub fn text(&mut self) -> &mut Self {
         self.with_text(Text::new())
@@ -204,6 +867,7 @@ impl Builder {
     /// Enable [text](Text) parsing, with the given parser
     pub fn with_text(&mut self, t: Text) -> &mut Self {
         self.text = Some(t);
+        self.register(ParserName::Text);
 
         self
     }
@@ -216,6 +880,7 @@ impl Builder {
     /// Enable [stdin](Stdin) parsing, using the given parser
     pub fn with_stdin(&mut self, s: Stdin) -> &mut Self {
         self.stdin = Some(s);
+        self.register(ParserName::Stdin);
 
         self
     }
@@ -228,84 +893,1150 @@ impl Builder {
     /// Enable [file path](File) parsing, using the given parser
     pub fn with_file(&mut self, f: File) -> &mut Self {
         self.file = Some(f);
+        self.register(ParserName::File);
 
         self
     }
 
-    /// Checks if you can successfully convert into a [Config]
-    pub fn is_valid(&self) -> bool {
-        let b = self;
+    /// Enable [URL](Url) parsing with the default parser. Note that actually fetching URLs
+    /// requires the `http` feature.
+    pub fn url(&mut self) -> &mut Self {
+        self.with_url(Url::new())
+    }
 
-        b.text.is_some() || b.stdin.is_some() || b.file.is_some()
+    /// Enable [URL](Url) parsing, using the given parser
+    pub fn with_url(&mut self, u: Url) -> &mut Self {
+        self.url = Some(u);
+        self.register(ParserName::Url);
+
+        self
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Enable [environment variable](Env) parsing with the default parser
+    pub fn env(&mut self) -> &mut Self {
+        self.with_env(Env::new())
+    }
 
-    #[test]
-    fn config_default_is_valid() {
-        let _cfg = Config::default();
+    /// Enable [environment variable](Env) parsing, using the given parser
+    pub fn with_env(&mut self, e: Env) -> &mut Self {
+        self.env = Some(e);
+        self.register(ParserName::Env);
+
+        self
     }
 
-    #[test]
-    fn builder_set_text() {
-        let b = Builder::new().with(|this| this.text());
+    /// Enable [base64](Base64) parsing with the default parser
+    pub fn base64(&mut self) -> &mut Self {
+        self.with_base64(Base64::new())
+    }
 
-        assert!(b.text.is_some())
+    /// Enable [base64](Base64) parsing, using the given parser
+    pub fn with_base64(&mut self, b: Base64) -> &mut Self {
+        self.base64 = Some(b);
+        self.register(ParserName::Base64);
+
+        self
     }
 
-    #[test]
-    fn builder_set_file() {
-        let b = Builder::new().with(|this| this.file());
+    /// Enable [glob](crate::parsers::Glob) parsing with the default parser
+    #[cfg(feature = "glob")]
+    pub fn glob(&mut self) -> &mut Self {
+        self.with_glob(crate::parsers::Glob::new())
+    }
 
-        assert!(b.file.is_some())
+    /// Enable [glob](crate::parsers::Glob) parsing, using the given parser
+    #[cfg(feature = "glob")]
+    pub fn with_glob(&mut self, g: crate::parsers::Glob) -> &mut Self {
+        self.glob = Some(g);
+        self.register(ParserName::Glob);
+
+        self
     }
 
-    #[test]
-    fn builder_set_stdin() {
-        let b = Builder::new().with(|this| this.stdin());
+    /// Disable [text](Text) parsing
+    pub fn without_text(&mut self) -> &mut Self {
+        self.text = None;
+        self.unregister(ParserName::Text);
 
-        assert!(b.stdin.is_some())
+        self
     }
 
-    #[test]
-    fn sorted_by_weight_ascending() {
-        let cfg = Config::default();
+    /// Disable [stdin](Stdin) parsing
+    pub fn without_stdin(&mut self) -> &mut Self {
+        self.stdin = None;
+        self.unregister(ParserName::Stdin);
 
-        let mut last = 0;
-        cfg.with_parsers(|list| {
-            // TODO: replace with list.is_sorted_by(|wp| wp.weight()) when method is stabilized
-            for wp in list.iter().filter_map(|o| *o) {
-                let weight = wp.weight();
+        self
+    }
 
-                assert!(weight >= last);
+    /// Disable [file](File) parsing
+    pub fn without_file(&mut self) -> &mut Self {
+        self.file = None;
+        self.unregister(ParserName::File);
 
-                last = weight;
-            }
-        })
+        self
     }
 
-    #[test]
-    fn config_default_parse_stdin() {
-        let input = "-";
-        let cfg = Config::default();
+    /// Enable [TCP](crate::parsers::Tcp) parsing with the default parser
+    #[cfg(feature = "tcp")]
+    pub fn tcp(&mut self) -> &mut Self {
+        self.with_tcp(crate::parsers::Tcp::new())
+    }
 
-        let t = cfg.parse_str(input).expect("a successful parse");
+    /// Enable [TCP](crate::parsers::Tcp) parsing, using the given parser
+    #[cfg(feature = "tcp")]
+    pub fn with_tcp(&mut self, t: crate::parsers::Tcp) -> &mut Self {
+        self.tcp = Some(t);
+        self.register(ParserName::Tcp);
 
-        match t {
-            InputType::Stdin => {}
-            bad => panic!("expected Stdin, got: {:?}", bad),
-        }
+        self
     }
 
-    #[test]
-    fn config_default_parse_file() {
-        let input = "@some/relative/path";
-        let cfg = Config::default();
+    /// Enable [clipboard](crate::parsers::Clipboard) parsing with the default parser
+    #[cfg(feature = "clipboard")]
+    pub fn clipboard(&mut self) -> &mut Self {
+        self.with_clipboard(crate::parsers::Clipboard::new())
+    }
 
-        let t = cfg.parse_str(input).expect("a successful parse");
+    /// Enable [clipboard](crate::parsers::Clipboard) parsing, using the given parser
+    #[cfg(feature = "clipboard")]
+    pub fn with_clipboard(&mut self, c: crate::parsers::Clipboard) -> &mut Self {
+        self.clipboard = Some(c);
+        self.register(ParserName::Clipboard);
+
+        self
+    }
+
+    /// Disable [url](Url) parsing
+    pub fn without_url(&mut self) -> &mut Self {
+        self.url = None;
+        self.unregister(ParserName::Url);
+
+        self
+    }
+
+    /// Disable [environment variable](Env) parsing
+    pub fn without_env(&mut self) -> &mut Self {
+        self.env = None;
+        self.unregister(ParserName::Env);
+
+        self
+    }
+
+    /// Disable [base64](Base64) parsing
+    pub fn without_base64(&mut self) -> &mut Self {
+        self.base64 = None;
+        self.unregister(ParserName::Base64);
+
+        self
+    }
+
+    /// Disable [glob](crate::parsers::Glob) parsing
+    #[cfg(feature = "glob")]
+    pub fn without_glob(&mut self) -> &mut Self {
+        self.glob = None;
+        self.unregister(ParserName::Glob);
+
+        self
+    }
+
+    /// Disable [TCP](crate::parsers::Tcp) parsing
+    #[cfg(feature = "tcp")]
+    pub fn without_tcp(&mut self) -> &mut Self {
+        self.tcp = None;
+        self.unregister(ParserName::Tcp);
+
+        self
+    }
+
+    /// Disable [clipboard](crate::parsers::Clipboard) parsing
+    #[cfg(feature = "clipboard")]
+    pub fn without_clipboard(&mut self) -> &mut Self {
+        self.clipboard = None;
+        self.unregister(ParserName::Clipboard);
+
+        self
+    }
+
+    /// Disable every parser, leaving any other settings untouched. The builder is then no
+    /// longer [valid][Builder::is_valid] until a parser is enabled again.
+    pub fn clear(&mut self) -> &mut Self {
+        self.without_text()
+            .without_stdin()
+            .without_file()
+            .without_url()
+            .without_env()
+            .without_base64();
+
+        #[cfg(feature = "glob")]
+        self.without_glob();
+
+        #[cfg(feature = "tcp")]
+        self.without_tcp();
+
+        #[cfg(feature = "clipboard")]
+        self.without_clipboard();
+
+        self
+    }
+
+    /// Set the order in which parsers are tried, by default this is by [weight](Order::Weight).
+    ///
+    /// With [Order::Registration], parsers are tried in the order they were first enabled on
+    /// this builder. Replacing an already enabled parser keeps its original position.
+    pub fn order(&mut self, order: Order) -> &mut Self {
+        self.order = order;
+
+        self
+    }
+
+    /// Limit the number of inputs that a single input may expand into, e.g the files matched by a
+    /// `Glob` or given with [split_on_marker][File::split_on_marker].
+    /// Exceeding the limit fails with an [InputError] of [EKind::TOO_MANY_INPUTS], and globs stop
+    /// expanding early once they pass it.
+    pub fn max_expanded(&mut self, max: usize) -> &mut Self {
+        self.max_expanded = Some(max);
+
+        self
+    }
+
+    /// Prevent the [text](Text) parser from accepting input that starts with the marker of one
+    /// of the other enabled parsers, for example a mistyped file path like `@typo` will then
+    /// cause an error rather than silently being treated as text.
+    pub fn text_rejects_markers(&mut self, reject: bool) -> &mut Self {
+        self.text_rejects_markers = reject;
+
+        self
+    }
+
+    /// Treat any input starting with the given prefix as literal text, with the prefix removed.
+    /// For example, with a prefix of `=` the input `=@file` is the text `@file` and `=-` is the
+    /// text `-`, rather than a file or stdin.
+    ///
+    /// Inputs with the prefix skip every parser, including the [text](Text) parser, so this
+    /// applies even if text parsing isn't otherwise enabled.
+    pub fn literal_prefix(&mut self, prefix: impl AsRef<str>) -> &mut Self {
+        self.literal_prefix = Some(prefix.as_ref().to_string());
+
+        self
+    }
+
+    /// Associate the given marker with a chain of [interpretations](Interpretation), which are
+    /// tried in order on the rest of the input. For example, with a chain of
+    /// `[Stdin, File, Text]` for the marker `?`, a bare `?` reads stdin, `?notes.txt` reads
+    /// the file if it exists, and is otherwise the text `notes.txt`.
+    ///
+    /// Input starting with the marker skips every parser, failing if none of the chain's
+    /// interpretations apply. Chains are checked in the order they were added, and setting a
+    /// chain for a marker again replaces it, or removes it if the chain is empty.
+    ///
+    /// Values are read with the settings of the enabled [file](File), [stdin](Stdin) and
+    /// [text](Text) parsers, e.g a file's [direct_io][File::direct_io] or
+    /// [append_marker][File::append_marker], though their markers aren't needed. If one of these
+    /// parsers isn't enabled, its interpretation uses the parser's default settings.
+    pub fn marker_chain<I>(&mut self, marker: impl AsRef<str>, chain: I) -> &mut Self
+    where
+        I: IntoIterator<Item = Interpretation>,
+    {
+        let marker = marker.as_ref();
+        let chain: Vec<_> = chain.into_iter().collect();
+
+        match self.chains.iter().position(|(m, _)| m == marker) {
+            Some(idx) if chain.is_empty() => {
+                self.chains.remove(idx);
+            }
+            Some(idx) => self.chains[idx].1 = chain,
+            None if chain.is_empty() => {}
+            None => self.chains.push((marker.to_string(), chain)),
+        }
+
+        self
+    }
+
+    /// Register the given input under a name, so that the [slot marker][Builder::slot_marker]
+    /// followed by the name resolves to the same source. For example, a tool running several
+    /// steps could register a step's output file as `prev`, letting the next step take `@prev`
+    /// as an argument.
+    ///
+    /// Only the input's source is kept, it is read with this builder's settings (e.g
+    /// [max_bytes][Builder::max_bytes]) like any other input. Slots are checked before any
+    /// parser, so `@prev` never refers to a file named `prev`. Registering a name again
+    /// replaces its input.
+    pub fn slot(&mut self, name: impl AsRef<str>, input: Input) -> &mut Self {
+        let name = name.as_ref();
+        let kind = input.into_kind();
+
+        match self.slots.iter_mut().find(|(slot, _)| slot == name) {
+            Some(slot) => slot.1 = kind,
+            None => self.slots.push((name.to_string(), kind)),
+        }
+
+        self
+    }
+
+    /// Set the marker which precedes the name of a [slot][Builder::slot], by default `@`
+    pub fn slot_marker(&mut self, marker: impl AsRef<str>) -> &mut Self {
+        self.slot_marker = Some(marker.as_ref().to_string());
+
+        self
+    }
+
+    fn get_slot_marker(&self) -> &str {
+        self.slot_marker.as_deref().unwrap_or("@")
+    }
+
+    /// Set the marker which precedes the path of an argfile given to
+    /// [expand_argfiles][Config::expand_argfiles], by default `@@`
+    #[cfg(feature = "argfile")]
+    pub fn argfile_marker(&mut self, marker: impl AsRef<str>) -> &mut Self {
+        self.argfile_marker = Some(marker.as_ref().to_string());
+
+        self
+    }
+
+    #[cfg(feature = "argfile")]
+    fn get_argfile_marker(&self) -> &str {
+        self.argfile_marker.as_deref().unwrap_or("@@")
+    }
+
+    /// Limit how deeply argfiles may name other argfiles when
+    /// [expanding](Config::expand_argfiles) them, by default 10. An argfile given directly as
+    /// an argument is at a depth of 1. Exceeding the limit fails with an
+    /// [AccessError][crate::error::access::AccessError] of
+    /// [Kind::ArgfileDepth][crate::error::access::Kind::ArgfileDepth].
+    ///
+    /// Regardless of the limit, an argfile which refers back to itself fails with
+    /// [Kind::ArgfileCycle][crate::error::access::Kind::ArgfileCycle].
+    #[cfg(feature = "argfile")]
+    pub fn max_argfile_depth(&mut self, depth: usize) -> &mut Self {
+        self.max_argfile_depth = Some(depth);
+
+        self
+    }
+
+    #[cfg(feature = "argfile")]
+    fn get_max_argfile_depth(&self) -> usize {
+        self.max_argfile_depth.unwrap_or(10)
+    }
+
+    /// Call the given closure every time [Config::parse], [Config::parse_os] or one of their
+    /// variants like [Config::parse_many] succeeds, with the input and the [kind](InputKind) it
+    /// was resolved to. This is intended for logging or
+    /// metrics, input which isn't valid UTF-8 is passed to the closure lossily converted.
+    pub fn on_parse<F>(&mut self, hook: F) -> &mut Self
+    where
+        F: Fn(&str, InputKind) + Send + Sync + 'static,
+    {
+        self.on_parse = Some(ParseHook(Arc::new(hook)));
+
+        self
+    }
+
+    /// Treat a [BrokenPipe][std::io::ErrorKind::BrokenPipe] error while reading an input as
+    /// the end of the input, so that pipelines whose other end closes early terminate cleanly
+    /// rather than failing.
+    pub fn eof_on_broken_pipe(&mut self, eof: bool) -> &mut Self {
+        self.read.eof_on_broken_pipe = eof;
+
+        self
+    }
+
+    /// Allow escaping input which would otherwise be parsed as something other than text, by
+    /// starting it with the given prefix. For example, with an escape of `\`, `\@notafile` is the
+    /// text `@notafile` and `\-` is the text `-`.
+    ///
+    /// Unlike a [literal_prefix][Builder::literal_prefix], the escape is only removed when the
+    /// rest of the input, ignoring any further escapes, wouldn't be parsed as text. Other input
+    /// starting with the escape, like `\n`, is parsed as normal. To write text which starts with
+    /// the escape followed by e.g a marker, like `\@x`, escape it once more, as in `\\@x`.
+    ///
+    /// No escape is set by default. [Input]'s Display escapes text with `\`, which its FromStr
+    /// reads back.
+    pub fn escape(&mut self, escape: impl AsRef<str>) -> &mut Self {
+        self.escape = Some(escape.as_ref().to_string());
+
+        self
+    }
+
+    /// Buffer the entirety of stdin into memory the first time a stdin input is accessed, so
+    /// that accessing the same [Input] again replays the same bytes rather than finding stdin
+    /// already consumed. This trades memory for repeatability, and means nothing can be read
+    /// until stdin is closed.
+    pub fn buffer_stdin(&mut self, buffer: bool) -> &mut Self {
+        self.read.buffer_stdin = buffer;
+
+        self
+    }
+
+    /// Fail with [Kind::StdinAlreadyConsumed][crate::error::access::Kind::StdinAlreadyConsumed]
+    /// when accessing stdin after any input in the program already has, rather than silently
+    /// reading whatever the first access left behind, usually nothing. Inputs which
+    /// [buffer_stdin][Builder::buffer_stdin] may still be accessed any number of times, as they
+    /// only read stdin once.
+    pub fn guard_stdin(&mut self, guard: bool) -> &mut Self {
+        self.read.guard_stdin = guard;
+
+        self
+    }
+
+    /// Limit the amount of data that may be read from an input, failing reads past the limit.
+    /// This guards against unbounded inputs like `@/dev/zero` exhausting memory in
+    /// [Input::read_to_string] and [Input::read_to_bytes], which then fail with
+    /// [Kind::LimitExceeded][crate::error::access::Kind::LimitExceeded].
+    ///
+    /// The limit applies to the input's source, not including any
+    /// [wrap_content][Builder::wrap_content], and isn't applied by [Input::access_enum].
+    pub fn max_bytes(&mut self, max: usize) -> &mut Self {
+        self.read.max_bytes = Some(max as u64);
+
+        self
+    }
+
+    /// Remove ANSI escape sequences, like the colour codes in captured terminal output, from the
+    /// text returned by [Input::read_to_string] and [InputReader::read_to_string], whatever
+    /// the input's source. Raw reads via [io::Read] are left untouched.
+    ///
+    /// [InputReader::read_to_string]: crate::InputReader::read_to_string
+    /// [io::Read]: std::io::Read
+    pub fn strip_ansi(&mut self, strip: bool) -> &mut Self {
+        self.read.strip_ansi = strip;
+
+        self
+    }
+
+    /// Surround the content of every input with the given prefix and suffix when it is read,
+    /// regardless of the input's source.
+    pub fn wrap_content(&mut self, prefix: impl AsRef<str>, suffix: impl AsRef<str>) -> &mut Self {
+        self.read.wrap = Some((prefix.as_ref().to_string(), suffix.as_ref().to_string()));
+
+        self
+    }
+
+    /// Pass the source of every input through the given decoder when it is
+    /// [accessed][Input::access], for custom decoding like decompression or decryption. The
+    /// decoder is given the opened source, and the reader it returns is read from instead.
+    ///
+    /// Other settings which change the content as it is read, like
+    /// [max_bytes][Builder::max_bytes] and [wrap_content][Builder::wrap_content], apply to the
+    /// decoded content. Like them, the decoder isn't applied by [Input::access_enum] or
+    /// [Input::access_seek].
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use grab::Builder;
+    /// use std::{io::Read, sync::Arc};
+    ///
+    /// // Upper case every input as it is read
+    /// let cfg = Builder::new()
+    ///     .with(|this| {
+    ///         this.text().with_decoder(Arc::new(|mut source| {
+    ///             let mut buf = Vec::new();
+    ///             let _ = source.read_to_end(&mut buf);
+    ///             buf.make_ascii_uppercase();
+    ///
+    ///             Box::new(std::io::Cursor::new(buf))
+    ///         }))
+    ///     })
+    ///     .build();
+    ///
+    /// let input = cfg.parse("shout").unwrap();
+    /// assert_eq!(input.read_to_string().unwrap(), "SHOUT");
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn with_decoder(
+        &mut self,
+        decoder: Arc<dyn Fn(Box<dyn Read + Send>) -> Box<dyn Read + Send> + Send + Sync>,
+    ) -> &mut Self {
+        self.read.decoder = Some(Decoder::new(decoder));
+
+        self
+    }
+
+    fn register(&mut self, name: ParserName) {
+        if !self.registered.contains(&name) {
+            self.registered.push(name);
+        }
+    }
+
+    /// Forget a disabled parser's position, so it is tried last if enabled again
+    fn unregister(&mut self, name: ParserName) {
+        self.registered.retain(|&n| n != name);
+    }
+
+    /// Checks if you can successfully convert into a [Config]
+    pub fn is_valid(&self) -> bool {
+        let b = self;
+
+        #[cfg(feature = "glob")]
+        if b.glob.is_some() {
+            return true;
+        }
+
+        #[cfg(feature = "tcp")]
+        if b.tcp.is_some() {
+            return true;
+        }
+
+        #[cfg(feature = "clipboard")]
+        if b.clipboard.is_some() {
+            return true;
+        }
+
+        b.text.is_some()
+            || b.stdin.is_some()
+            || b.file.is_some()
+            || b.url.is_some()
+            || b.env.is_some()
+            || b.base64.is_some()
+    }
+}
+
+/// A closure called after every successful parse, see [Builder::on_parse]
+#[derive(Clone)]
+struct ParseHook(Arc<HookFn>);
+
+type HookFn = dyn Fn(&str, InputKind) + Send + Sync;
+
+impl ParseHook {
+    fn call(&self, input: &str, kind: InputKind) {
+        (self.0)(input, kind)
+    }
+}
+
+impl fmt::Debug for ParseHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ParseHook")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::build::Kind as BuildKind;
+    use crate::test_support::fixture;
+
+    #[test]
+    fn config_default_is_valid() {
+        let _cfg = Config::default();
+    }
+
+    #[test]
+    fn builder_minimal() {
+        let cfg = Builder::minimal();
+
+        assert_eq!(cfg.parser_names(), vec!["stdin", "text"]);
+        assert_eq!(cfg.parse("-").unwrap().kind(), InputKind::Stdin);
+        assert_eq!(cfg.parse("some text").unwrap().kind(), InputKind::Text);
+
+        let input = cfg.parse("@some/file").unwrap();
+        assert_eq!(input.kind(), InputKind::Text);
+        assert_eq!(input.read_to_string().unwrap(), "@some/file")
+    }
+
+    #[test]
+    fn builder_set_text() {
+        let b = Builder::new().with(|this| this.text());
+
+        assert!(b.text.is_some())
+    }
+
+    #[test]
+    fn builder_set_file() {
+        let b = Builder::new().with(|this| this.file());
+
+        assert!(b.file.is_some())
+    }
+
+    #[test]
+    fn builder_set_stdin() {
+        let b = Builder::new().with(|this| this.stdin());
+
+        assert!(b.stdin.is_some())
+    }
+
+    #[test]
+    fn builder_set_url() {
+        let b = Builder::new().with(|this| this.url());
+
+        assert!(b.url.is_some())
+    }
+
+    #[test]
+    fn builder_finish_success() {
+        let cfg = Builder::new()
+            .with(|this| this.text().stdin().file())
+            .finish()
+            .unwrap();
+
+        assert!(cfg.parse("-").is_ok())
+    }
+
+    #[test]
+    fn builder_finish_no_parsers() {
+        let err = Builder::new().finish().unwrap_err();
+
+        assert_eq!(err.kind(), BuildKind::NoParsers)
+    }
+
+    #[test]
+    fn builder_finish_weight_collision() {
+        let file = File::new().with(|this| this.weight(Stdin::DEFAULT_WEIGHT));
+
+        let err = Builder::new()
+            .with(|this| this.stdin().with_file(file.clone()))
+            .finish()
+            .unwrap_err();
+        assert_eq!(err.kind(), BuildKind::WeightCollision);
+
+        // Weights are irrelevant when parsers are tried in registration order
+        let result = Builder::new()
+            .with(|this| {
+                this.stdin()
+                    .with_file(file.clone())
+                    .order(Order::Registration)
+            })
+            .finish();
+        assert!(result.is_ok())
+    }
+
+    #[test]
+    fn builder_check_weights() {
+        assert_eq!(Config::default().inner.check_weights(), Ok(()));
+
+        let b = Builder::new().with(|this| {
+            this.with_file(File::new().with(|f| f.weight(100)))
+                .with_stdin(Stdin::new().with(|s| s.weight(100)))
+        });
+
+        let conflict = b.check_weights().unwrap_err();
+        assert_eq!(
+            (conflict.first(), conflict.second(), conflict.weight()),
+            (ParserName::File, ParserName::Stdin, 100)
+        );
+
+        assert!(b.try_build().is_err())
+    }
+
+    #[test]
+    fn builder_without_parsers() {
+        let mut b = Builder::new().with(|this| this.text().stdin().file());
+
+        b.without_text();
+        assert!(b.text.is_none() && b.is_valid());
+
+        b.without_stdin();
+        assert!(b.stdin.is_none() && b.is_valid());
+
+        b.without_file();
+        assert!(b.file.is_none() && !b.is_valid());
+        assert!(b.registered.is_empty())
+    }
+
+    #[test]
+    fn builder_clear() {
+        let mut b = Builder::new().with(|this| this.text().stdin().file().url().env().base64());
+
+        assert!(!b.clear().is_valid());
+
+        b.text();
+
+        let cfg = b.try_build().expect("a valid builder");
+        assert_eq!(cfg.parse("@foo").unwrap().kind(), InputKind::Text)
+    }
+
+    #[test]
+    fn builder_without_reregisters_last() {
+        let mut b = Builder::new().with(|this| this.stdin().file().order(Order::Registration));
+
+        b.without_stdin().stdin();
+
+        assert_eq!(b.registered, [ParserName::File, ParserName::Stdin])
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn config_platform_default() {
+        let cfg = Config::platform_default();
+
+        for input in &["C:\\notes.txt", "c:/notes.txt", "@notes.txt"] {
+            assert_eq!(
+                cfg.parse(input).unwrap().kind(),
+                InputKind::File,
+                "{}",
+                input
+            )
+        }
+
+        assert_eq!(cfg.parse("C:notes.txt").unwrap().kind(), InputKind::Text);
+        assert_eq!(
+            Config::default().parse("C:\\notes.txt").unwrap().kind(),
+            InputKind::Text
+        )
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn config_platform_default() {
+        let cfg = Config::platform_default();
+
+        assert_eq!(cfg.parse("@notes.txt").unwrap().kind(), InputKind::File);
+        assert_eq!(cfg.parse("-").unwrap().kind(), InputKind::Stdin);
+        assert_eq!(cfg.parse("C:\\notes.txt").unwrap().kind(), InputKind::Text)
+    }
+
+    #[test]
+    fn config_into_builder() {
+        let cfg = Config::default()
+            .into_builder()
+            .with(|this| this.without_file())
+            .build();
+
+        assert_eq!(cfg.parse("@x").unwrap().kind(), InputKind::Text);
+        assert_eq!(cfg.parse("-").unwrap().kind(), InputKind::Stdin)
+    }
+
+    #[test]
+    fn config_marker_chain() {
+        use Interpretation::*;
+
+        let cfg = Builder::new()
+            .with(|this| this.file().marker_chain("?", [Stdin, File, Text]))
+            .build();
+        let empty = format!("?{}", fixture("empty.txt").display());
+
+        assert_eq!(cfg.parse("?").unwrap().kind(), InputKind::Stdin);
+        assert_eq!(cfg.parse(&empty).unwrap().kind(), InputKind::File);
+
+        let (input, trace) = cfg.parse_trace("?no/such/file").unwrap();
+        assert_eq!(input.read_to_string().unwrap(), "no/such/file");
+        assert_eq!(
+            trace,
+            [ParserName::Stdin, ParserName::File, ParserName::Text]
+        );
+
+        // Input without the marker goes to the parsers as usual
+        assert_eq!(cfg.parse("@x").unwrap().kind(), InputKind::File)
+    }
+
+    #[test]
+    fn config_marker_chain_parser_options() {
+        let cfg = Builder::new()
+            .with(|this| {
+                this.with_file(File::new().with(|f| f.direct_io(true).append_marker("+")))
+                    .with_stdin(Stdin::new().with(|s| s.require_piped(true)))
+                    .with_text(Text::new().with(|t| t.trim(true).max_len(8)))
+                    .marker_chain(
+                        "?",
+                        [
+                            Interpretation::Stdin,
+                            Interpretation::File,
+                            Interpretation::Text,
+                        ],
+                    )
+            })
+            .build();
+        let empty = fixture("empty.txt");
+
+        match cfg.parse_str("?") {
+            Ok(InputType::Stdin(options)) => assert!(options.require_piped),
+            bad => panic!("expected Stdin, got: {:?}", bad),
+        }
+
+        match cfg.parse_str(&format!("?{}", empty.display())) {
+            Ok(InputType::File(f)) => assert!(f.options.direct_io && f.options.append.is_none()),
+            bad => panic!("expected File, got: {:?}", bad),
+        }
+
+        match cfg.parse_str(&format!("?+{}", empty.display())) {
+            Ok(InputType::File(f)) => assert_eq!(f.options.append.as_deref(), Some("+")),
+            bad => panic!("expected File, got: {:?}", bad),
+        }
+
+        assert_eq!(cfg.parse_str("? some "), Ok(InputType::UTF8("some".into())));
+        let err = cfg.parse_str("?far too long").unwrap_err();
+        assert!(err.contains(EKind::TEXT))
+    }
+
+    #[test]
+    fn config_marker_chain_failure() {
+        use Interpretation::*;
+
+        let cfg = Builder::new()
+            .with(|this| this.text().marker_chain("?", [File, Env]))
+            .build();
+
+        let err = cfg.parse("?no/such/file").unwrap_err();
+
+        assert!(err.contains(EKind::FILE) && err.contains(EKind::ENV));
+        assert!(!err.contains(EKind::TEXT))
+    }
+
+    #[test]
+    fn config_unmatched_lists_tried() {
+        let cfg = Builder::new()
+            .with(|this| {
+                this.with_text(Text::new().with(|t| t.marker("txt:")))
+                    .stdin()
+                    .file()
+            })
+            .build();
+
+        let err = cfg.parse("foo").unwrap_err();
+
+        assert_eq!(err.tried().collect::<Vec<_>>(), cfg.parser_names());
+        assert!(err
+            .to_string()
+            .starts_with("no parser matched 'foo' (tried: file, stdin, text)"))
+    }
+
+    #[test]
+    fn builder_marker_chain_replace() {
+        use Interpretation::*;
+
+        let mut b = Builder::new().with(|this| this.marker_chain("?", [Stdin, Text]));
+
+        b.marker_chain("?", [Text]);
+        assert_eq!(b.chains, [(String::from("?"), vec![Text])]);
+
+        b.marker_chain("?", None);
+        assert!(b.chains.is_empty())
+    }
+
+    #[test]
+    fn config_slot() {
+        let prev = Input::with_defaults("output of the previous step").unwrap();
+
+        let mut builder = Builder::new()
+            .with(|this| this.file().text().slot("prev", Input::from_bytes("old")))
+            .build()
+            .into_builder();
+        builder.slot("prev", prev);
+
+        let cfg = builder.build();
+
+        let input = cfg.parse("@prev").unwrap();
+        assert_eq!(
+            input.read_to_string().unwrap(),
+            "output of the previous step"
+        );
+
+        // Other names are still files, and the name must match exactly
+        assert_eq!(cfg.parse("@other").unwrap().kind(), InputKind::File);
+        assert_eq!(cfg.parse("@prevx").unwrap().kind(), InputKind::File);
+
+        let (_, trace) = cfg.parse_trace("@prev").unwrap();
+        assert!(trace.is_empty())
+    }
+
+    #[test]
+    fn config_slot_marker() {
+        let cfg = Builder::new()
+            .with(|this| {
+                this.file()
+                    .slot("prev", Input::from_bytes("slot"))
+                    .slot_marker("$")
+            })
+            .build();
+
+        assert_eq!(cfg.parse("$prev").unwrap().kind(), InputKind::Bytes);
+        assert_eq!(cfg.parse("@prev").unwrap().kind(), InputKind::File)
+    }
+
+    #[test]
+    fn config_default_parsers() {
+        let cfg = Config::default();
+
+        assert_eq!(cfg.parser_names(), ["file", "stdin", "text"]);
+        assert_eq!(
+            cfg.markers(),
+            [
+                ("file", String::from("@")),
+                ("stdin", String::from("-")),
+                ("text", String::new()),
+            ]
+        )
+    }
+
+    #[test]
+    fn config_markers_several() {
+        let cfg = Builder::new()
+            .with(|this| {
+                this.with_file(File::new().with(|f| f.markers(["@", "file:"])))
+                    .env()
+            })
+            .build();
+
+        assert_eq!(
+            cfg.markers(),
+            [
+                ("file", String::from("@")),
+                ("file", String::from("file:")),
+                ("env", String::from("env:")),
+            ]
+        )
+    }
+
+    #[test]
+    fn config_completion_hints() {
+        let hints = Config::default().completion_hints();
+        assert!(hints.contains(&String::from("@")) && hints.contains(&String::from("-")));
+
+        let cfg = Builder::new()
+            .with(|this| {
+                this.text()
+                    .with_file(File::new().with(|f| f.file_uri(true).append_marker("+")))
+                    .env()
+                    .marker_chain("?", [Interpretation::File])
+                    .slot("config", Input::from_text("x"))
+            })
+            .build();
+
+        assert_eq!(
+            cfg.completion_hints(),
+            ["@config", "?", "@", "+", "env:", "file://"]
+        )
+    }
+
+    #[test]
+    fn builder_set_env() {
+        let b = Builder::new().with(|this| this.env());
+
+        assert!(b.env.is_some())
+    }
+
+    #[test]
+    fn config_url_before_text() {
+        let cfg = Builder::new().with(|this| this.text().url()).build();
+
+        let (t, trace) = (
+            cfg.parse_str("http://example.com")
+                .expect("a successful parse"),
+            cfg.parse_trace("plain").expect("a successful parse").1,
+        );
+
+        assert_eq!(t, InputType::Url("http://example.com".into()));
+        assert_eq!(trace, vec![ParserName::Url, ParserName::Text])
+    }
+
+    #[test]
+    fn sorted_by_weight_ascending() {
+        let cfg = Config::default();
+
+        let mut last = 0;
+        cfg.with_parsers(|list| {
+            // TODO: replace with list.is_sorted_by(|wp| wp.weight()) when method is stabilized
+            for wp in list.iter().filter_map(|o| *o) {
+                let weight = wp.weight();
+
+                assert!(weight >= last);
+
+                last = weight;
+            }
+        })
+    }
+
+    #[test]
+    fn config_registration_order() {
+        let cfg = Builder::new()
+            .with(|this| this.file().stdin().text().order(Order::Registration))
+            .build();
+
+        let (_, trace) = cfg.parse_trace("foo").expect("a successful parse");
+
+        assert_eq!(
+            trace,
+            vec![ParserName::File, ParserName::Stdin, ParserName::Text]
+        );
+
+        let cfg = Builder::new()
+            .with(|this| {
+                this.text()
+                    .with_stdin(Stdin::new().with(|s| s.weight(1)))
+                    .file()
+                    .order(Order::Registration)
+            })
+            .build();
+
+        let (_, trace) = cfg.parse_trace("@foo").expect("a successful parse");
+
+        assert_eq!(trace, vec![ParserName::Text]);
+
+        let err = Builder::new()
+            .with(|this| this.file().stdin().order(Order::Registration))
+            .build()
+            .parse_trace("foo")
+            .unwrap_err();
+
+        assert!(err.contains(EKind::FILE | EKind::STDIN))
+    }
+
+    #[test]
+    fn config_registration_order_replace_keeps_position() {
+        let cfg = Builder::new()
+            .with(|this| {
+                this.file()
+                    .stdin()
+                    .with_file(File::new().with(|f| f.weight(200)))
+                    .order(Order::Registration)
+            })
+            .build();
+
+        let (_, trace) = cfg.parse_trace("@foo").expect("a successful parse");
+
+        assert_eq!(trace, vec![ParserName::File])
+    }
+
+    #[test]
+    fn config_parse_many_single() {
+        let cfg = Config::default();
+
+        let inputs = cfg.parse_many("@some/file").expect("a successful parse");
+
+        assert_eq!(inputs.len(), 1)
+    }
+
+    #[test]
+    fn config_parse_many_split_on_marker() {
+        let cfg = Builder::new()
+            .with(|this| this.with_file(File::new().with(|f| f.split_on_marker(true))))
+            .build();
+
+        let inputs = cfg.parse_many("@a@b").expect("a successful parse");
+        assert_eq!(inputs.len(), 2);
+
+        assert!(cfg.parse_many("@a@").is_err())
+    }
+
+    #[test]
+    fn config_input_spec() {
+        let cfg = Config::default();
+
+        for &(spec, kind) in &[
+            ("@./a.txt", InputKind::File),
+            ("-", InputKind::Stdin),
+            ("some text", InputKind::Text),
+            ("\\-", InputKind::Text),
+            ("", InputKind::Text),
+        ] {
+            let input = cfg.parse(spec).unwrap();
+            assert_eq!(input.kind(), kind);
+            assert_eq!(input.spec(), Some(spec));
+
+            let input = cfg.parse_os(OsStr::new(spec)).unwrap();
+            assert_eq!(input.spec(), Some(spec));
+        }
+
+        let inputs = cfg.parse_list("@a.txt,-", ',').unwrap();
+        let specs: Vec<_> = inputs.iter().map(Input::spec).collect();
+        assert_eq!(specs, vec![Some("@a.txt"), Some("-")]);
+
+        assert_eq!(Input::from_text("some text").spec(), None)
+    }
+
+    #[test]
+    fn config_parse_list_mixed() {
+        let cfg = Config::default();
+
+        let inputs = cfg
+            .parse_list("@a.txt,@b.txt,-", ',')
+            .expect("a successful parse");
+        let kinds: Vec<_> = inputs.iter().map(Input::kind).collect();
+
+        assert_eq!(
+            kinds,
+            vec![InputKind::File, InputKind::File, InputKind::Stdin]
+        )
+    }
+
+    #[test]
+    fn config_parse_list_bad_item() {
+        let cfg = Builder::new().with(|this| this.file().stdin()).build();
+
+        let err = cfg.parse_list("@a.txt;neither;-", ';').unwrap_err();
+        assert!(err.contains(EKind::FILE | EKind::STDIN));
+        assert!(err.to_string().contains("list item 2"));
+
+        let err = cfg.parse_list("@a.txt;;-", ';').unwrap_err();
+        assert!(err.contains(EKind::MISSING_VALUE))
+    }
+
+    #[test]
+    fn config_max_expanded() {
+        let cfg = Builder::new()
+            .with(|this| {
+                this.with_file(File::new().with(|f| f.split_on_marker(true)))
+                    .max_expanded(2)
+            })
+            .build();
+
+        assert_eq!(cfg.parse_many("@a@b").map(|i| i.len()), Ok(2));
+
+        let err = cfg.parse_many("@a@b@c").unwrap_err();
+        assert!(err.contains(EKind::TOO_MANY_INPUTS));
+        assert!(err.to_string().contains("limit of 2"))
+    }
+
+    #[cfg(feature = "glob")]
+    #[test]
+    fn config_max_expanded_glob() {
+        let cfg = Builder::new()
+            .with(|this| this.glob().file().max_expanded(3))
+            .build();
+
+        let err = cfg
+            .parse_many(&format!("@{}", fixture("*").display()))
+            .unwrap_err();
+        assert!(err.contains(EKind::TOO_MANY_INPUTS));
+
+        let inputs = cfg.parse_many(&format!("@{}", fixture("utf16*").display()));
+        assert_eq!(inputs.map(|i| i.len()), Ok(2))
+    }
+
+    #[cfg(feature = "glob")]
+    #[test]
+    fn config_parse_many_glob() {
+        let cfg = Builder::new().with(|this| this.glob().file()).build();
+
+        let inputs = cfg
+            .parse_many(&format!("@{}", fixture("utf16*.txt").display()))
+            .expect("a successful parse");
+        let contents: Vec<_> = inputs.iter().map(|i| i.read_to_bytes().unwrap()).collect();
+
+        assert_eq!(contents.len(), 2);
+        assert!(contents.iter().all(|c| !c.is_empty()));
+
+        // Patterns matching nothing are left to the file parser
+        let inputs = cfg
+            .parse_many(&format!("@{}", fixture("*.nothing").display()))
+            .expect("a successful parse");
+
+        assert_eq!(inputs.len(), 1);
+        assert_eq!(
+            inputs[0].access().unwrap_err().kind(),
+            crate::error::access::Kind::File
+        )
+    }
+
+    #[test]
+    fn config_default_parse_stdin() {
+        let input = "-";
+        let cfg = Config::default();
+
+        let t = cfg.parse_str(input).expect("a successful parse");
+
+        match t {
+            InputType::Stdin(_) => {}
+            bad => panic!("expected Stdin, got: {:?}", bad),
+        }
+    }
+
+    #[test]
+    fn config_default_parse_file() {
+        let input = "@some/relative/path";
+        let cfg = Config::default();
+
+        let t = cfg.parse_str(input).expect("a successful parse");
 
         match t {
             InputType::File(_) => {}
@@ -325,4 +2056,231 @@ mod tests {
             bad => panic!("expected Text, got: {:?}", bad),
         }
     }
+
+    #[test]
+    fn config_parser_labels() {
+        let cfg = Builder::new()
+            .with(|this| {
+                this.with_file(File::new().with(|f| f.label("config file")))
+                    .with_stdin(Stdin::new().with(|s| s.label("piped config")))
+            })
+            .build();
+
+        let err = cfg.parse("neither").unwrap_err();
+
+        assert_eq!(
+            err.labels().collect::<Vec<_>>(),
+            ["config file", "piped config"]
+        );
+        assert!(err.to_string().contains("config file, piped config"))
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn config_default_parse_os_non_utf8() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let input = OsStr::from_bytes(b"@caf\xe9");
+        let cfg = Config::default();
+
+        let t = cfg.parse_os_str(input).expect("a successful parse");
+
+        match t {
+            InputType::File(f) => assert_eq!(f.path.as_os_str().as_bytes(), b"caf\xe9"),
+            bad => panic!("expected File, got: {:?}", bad),
+        }
+    }
+
+    #[test]
+    fn config_text_rejects_markers() {
+        let file = File::new().with(|f| f.require_path_prefix(true).append_marker("+"));
+
+        let cfg = Builder::new()
+            .with(|this| this.text().stdin().with_file(file.clone()))
+            .build();
+        assert!(matches!(cfg.parse_str("@x"), Ok(InputType::UTF8(_))));
+        assert!(matches!(cfg.parse_str("+x"), Ok(InputType::UTF8(_))));
+
+        let cfg = Builder::new()
+            .with(|this| {
+                this.text()
+                    .stdin()
+                    .with_file(file.clone())
+                    .text_rejects_markers(true)
+            })
+            .build();
+        let err = cfg.parse_str("@x").unwrap_err();
+        assert!(err.contains(EKind::FILE | EKind::TEXT));
+        let err = cfg.parse_str("+x").unwrap_err();
+        assert!(err.contains(EKind::FILE | EKind::TEXT));
+
+        assert!(matches!(cfg.parse_str("./x"), Ok(InputType::UTF8(_))));
+        assert!(matches!(cfg.parse_str("@./x"), Ok(InputType::File(_))));
+    }
+
+    #[test]
+    fn config_literal_prefix() {
+        let cfg = Builder::new()
+            .with(|this| this.text().stdin().file().literal_prefix("="))
+            .build();
+
+        let cases = [("=@x", "@x"), ("=-", "-"), ("==y", "=y"), ("=", "")];
+
+        for &(input, text) in cases.iter() {
+            assert_eq!(
+                cfg.parse_str(input),
+                Ok(InputType::UTF8(text.to_string())),
+                "{}",
+                input
+            );
+        }
+
+        assert!(matches!(cfg.parse_str("@x"), Ok(InputType::File(_))));
+        assert!(matches!(cfg.parse_str("-"), Ok(InputType::Stdin(_))));
+    }
+
+    fn escape_config() -> Config {
+        Builder::new()
+            .with(|this| this.text().stdin().file().escape("\\"))
+            .build()
+    }
+
+    #[test]
+    fn config_escape() {
+        let cfg = escape_config();
+
+        let cases = [
+            ("\\@x", "@x"),
+            ("\\-", "-"),
+            ("\\\\@x", "\\@x"),
+            ("\\n", "\\n"),
+            ("\\\\server\\share", "\\\\server\\share"),
+            ("\\", "\\"),
+        ];
+
+        for &(input, text) in cases.iter() {
+            assert_eq!(
+                cfg.parse_str(input),
+                Ok(InputType::UTF8(text.to_string())),
+                "{}",
+                input
+            );
+        }
+
+        assert!(matches!(cfg.parse_str("@x"), Ok(InputType::File(_))));
+
+        // Escapes are opt in
+        for cfg in [Config::default(), Builder::minimal()].iter() {
+            assert_eq!(
+                cfg.parse_str("\\-"),
+                Ok(InputType::UTF8(String::from("\\-")))
+            )
+        }
+    }
+
+    #[test]
+    fn config_escape_text() {
+        let cfg = escape_config();
+
+        for text in ["@x", "-", "\\@x", "plain", "\\n", "-x", ""].iter() {
+            let escaped = cfg.escape_text(text).unwrap();
+
+            assert_eq!(
+                cfg.parse_str(&escaped),
+                Ok(InputType::UTF8(text.to_string())),
+                "{}",
+                escaped
+            );
+        }
+
+        let cfg = Builder::new().with(|this| this.text().file()).build();
+        assert_eq!(cfg.escape_text("@x"), None)
+    }
+
+    #[test]
+    fn config_literal_prefix_trace() {
+        let cfg = Builder::new()
+            .with(|this| this.stdin().file().literal_prefix("="))
+            .build();
+
+        let (i, trace) = cfg.parse_trace("=@x").unwrap();
+
+        assert_eq!(trace, [ParserName::Text]);
+        assert_eq!(i.read_to_string().unwrap(), "@x")
+    }
+
+    #[test]
+    fn config_parse_trace_file() {
+        let cfg = Config::default();
+
+        let (_, trace) = cfg.parse_trace("@foo").expect("a successful parse");
+
+        assert_eq!(trace, vec![ParserName::File])
+    }
+
+    #[test]
+    fn config_parse_trace_text() {
+        let cfg = Config::default();
+
+        let (_, trace) = cfg.parse_trace("foo").expect("a successful parse");
+
+        assert_eq!(
+            trace,
+            vec![ParserName::File, ParserName::Stdin, ParserName::Text]
+        )
+    }
+
+    #[test]
+    fn config_parse_trace_custom_weight() {
+        let cfg = Builder::new()
+            .with(|this| this.file().with_stdin(Stdin::new().with(|s| s.weight(1))))
+            .build();
+
+        let (_, trace) = cfg.parse_trace("@foo").expect("a successful parse");
+
+        assert_eq!(trace, vec![ParserName::Stdin, ParserName::File])
+    }
+
+    #[test]
+    fn config_on_parse_hook() {
+        use std::sync::Mutex;
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let hook = Arc::clone(&seen);
+
+        let mut builder =
+            Builder::new().with(|this| this.text().stdin().file().url().env().base64());
+        builder.on_parse(move |input, kind| hook.lock().unwrap().push((input.to_string(), kind)));
+
+        let cfg = builder.build();
+
+        let cases = [
+            ("-", InputKind::Stdin),
+            ("@some/file", InputKind::File),
+            ("https://example.com", InputKind::Url),
+            ("env:HOME", InputKind::Env),
+            ("base64:aGk=", InputKind::Base64),
+            ("some text", InputKind::Text),
+        ];
+
+        for &(input, _) in cases.iter() {
+            cfg.parse(input).expect("a successful parse");
+        }
+        cfg.parse_os(OsStr::new("@other"))
+            .expect("a successful parse");
+        cfg.parse_many("@many").expect("a successful parse");
+        cfg.parse_trace("-").expect("a successful parse");
+
+        let expected: Vec<_> = cases
+            .iter()
+            .chain(&[
+                ("@other", InputKind::File),
+                ("@many", InputKind::File),
+                ("-", InputKind::Stdin),
+            ])
+            .map(|&(input, kind)| (input.to_string(), kind))
+            .collect();
+
+        assert_eq!(*seen.lock().unwrap(), expected);
+    }
 }
@@ -0,0 +1,103 @@
+//! Integration with [clap] v4, for parsing arguments into [Input]s with a custom [Config] while
+//! clap matches them.
+
+use std::ffi::OsStr;
+
+use ::clap::{builder::TypedValueParser, error::ErrorKind, Arg, Command, Error};
+
+use crate::{Config, Input};
+
+/// A clap value parser which parses each argument into an [Input] with the given config. Each
+/// raw argument is parsed with [parse_os][Config::parse_os], so arguments which aren't valid
+/// UTF-8 can still name a file. Any [InputError][crate::error::input::InputError] is reported
+/// as an invalid value for the argument.
+///
+/// ```
+/// use clap::{Arg, Command};
+/// use grab::{parsers::Stdin, Builder, Input};
+///
+/// let cfg = Builder::new()
+///     .with(|this| this.text().file().with_stdin(Stdin::new().with(|s| s.marker("<-"))))
+///     .build();
+///
+/// let matches = Command::new("cli")
+///     .arg(Arg::new("input").value_parser(grab::clap::input_value_parser(cfg)))
+///     .get_matches_from(["cli", "<-"]);
+///
+/// let input = matches.get_one::<Input>("input").unwrap();
+///
+/// assert!(input.is_stdin());
+/// ```
+pub fn input_value_parser(config: Config) -> impl TypedValueParser<Value = Input> {
+    InputValueParser { config }
+}
+
+#[derive(Clone)]
+struct InputValueParser {
+    config: Config,
+}
+
+impl TypedValueParser for InputValueParser {
+    type Value = Input;
+
+    fn parse_ref(&self, cmd: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<Input, Error> {
+        self.config.parse_os(value).map_err(|e| {
+            let arg = arg.map_or_else(|| String::from("..."), |arg| arg.get_id().to_string());
+            let msg = format!(
+                "invalid value '{}' for '{}': {}\n",
+                value.to_string_lossy(),
+                arg,
+                e
+            );
+
+            Error::raw(ErrorKind::InvalidValue, msg).with_cmd(cmd)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::Builder;
+
+    #[test]
+    fn value_parser_error() {
+        let cmd = Command::new("cli").arg(Arg::new("input").value_parser(input_value_parser(
+            Builder::new().with(|this| this.file()).build(),
+        )));
+
+        let err = cmd.try_get_matches_from(["cli", "some text"]).unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::InvalidValue);
+        assert!(err.to_string().contains("'input'"), "{}", err)
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn value_parser_non_utf8() {
+        use std::{
+            ffi::OsString,
+            os::unix::ffi::{OsStrExt, OsStringExt},
+        };
+
+        let cmd = Command::new("cli")
+            .arg(Arg::new("input").value_parser(input_value_parser(Config::default())));
+
+        let m = cmd
+            .try_get_matches_from([
+                OsString::from("cli"),
+                OsString::from_vec(b"@caf\xe9".to_vec()),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            m.get_one::<Input>("input")
+                .unwrap()
+                .path()
+                .unwrap()
+                .as_os_str(),
+            OsStr::from_bytes(b"caf\xe9")
+        )
+    }
+}
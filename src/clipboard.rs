@@ -0,0 +1,23 @@
+//! Reading the system clipboard for [Clipboard][crate::parsers::Clipboard] inputs, backed by
+//! [arboard].
+
+use std::io;
+
+/// Read the text on the clipboard, failing if the clipboard can't be opened, or holds no text
+pub(crate) fn read() -> io::Result<String> {
+    let text = arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .map_err(|e| match e {
+            arboard::Error::ContentNotAvailable => empty(),
+            e => io::Error::other(e),
+        })?;
+
+    match text.is_empty() {
+        true => Err(empty()),
+        false => Ok(text),
+    }
+}
+
+fn empty() -> io::Error {
+    io::Error::other("the clipboard is empty")
+}
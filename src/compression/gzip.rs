@@ -0,0 +1,205 @@
+//! Decompression of gzip files and streams as they are read, via [flate2]. Concatenated members
+//! are decoded one after another, as `gzip -d` does.
+
+use std::{
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+/// The two bytes every gzip member starts with
+pub(crate) const MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Decompresses a gzip stream as it is read, verifying the checksum and length of each member
+/// once it ends
+pub(crate) type Decoder<R> = flate2::bufread::MultiGzDecoder<R>;
+
+/// Checks whether the given path has a `.gz` extension
+pub(crate) fn is_gzip_path(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "gz")
+}
+
+/// Open the gzip file at path, which is decompressed as it is read
+pub(crate) fn read_file(path: &Path) -> io::Result<Decoder<io::BufReader<fs::File>>> {
+    fs::File::open(path).map(|file| Decoder::new(io::BufReader::new(file)))
+}
+
+/// Read the uncompressed size recorded in the trailer of the gzip file at path. This is the size
+/// of the last member only, modulo 2^32.
+pub(crate) fn size_hint(path: &Path) -> io::Result<u32> {
+    use io::{Seek, SeekFrom};
+
+    let mut file = fs::File::open(path)?;
+    let mut isize = [0u8; 4];
+
+    file.seek(SeekFrom::End(-4))?;
+    file.read_exact(&mut isize)?;
+
+    Ok(u32::from_le_bytes(isize))
+}
+
+/// Gzip data which is far larger decompressed than compressed
+#[cfg(test)]
+pub(crate) mod test_data {
+    /// A gzip member holding len zero bytes, compressed with fixed huffman codes. Computing the
+    /// checksum of large outputs is slow, so unless check is set the trailer is left zeroed.
+    pub(crate) fn zeros(len: usize, check: bool) -> Vec<u8> {
+        let mut bits = Bits::default();
+
+        // A final block of fixed huffman codes
+        bits.push(0b1, 1);
+        bits.push(0b01, 2);
+
+        let mut left = len;
+        if left > 0 {
+            // The literal 0, then copies of the maximum length from one byte back
+            bits.code(0b0011_0000, 8);
+            left -= 1;
+
+            while left >= 258 {
+                bits.code(0b1100_0101, 8);
+                bits.code(0b00000, 5);
+                left -= 258;
+            }
+
+            for _ in 0..left {
+                bits.code(0b0011_0000, 8);
+            }
+        }
+
+        bits.code(0b000_0000, 7);
+
+        let mut member = vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff];
+        member.extend(bits.finish());
+
+        let crc = match check {
+            true => {
+                let mut crc = flate2::Crc::new();
+                crc.update(&vec![0; len]);
+                crc.sum()
+            }
+            false => 0,
+        };
+        member.extend(crc.to_le_bytes());
+        member.extend((len as u32).to_le_bytes());
+
+        member
+    }
+
+    /// Writes bits least significant first, as DEFLATE packs them
+    #[derive(Default)]
+    struct Bits {
+        out: Vec<u8>,
+        acc: u32,
+        count: u32,
+    }
+
+    impl Bits {
+        fn push(&mut self, value: u32, count: u32) {
+            self.acc |= value << self.count;
+            self.count += count;
+
+            while self.count >= 8 {
+                self.out.push(self.acc as u8);
+                self.acc >>= 8;
+                self.count -= 8;
+            }
+        }
+
+        /// Push a huffman code, which is packed most significant bit first
+        fn code(&mut self, code: u32, len: u32) {
+            self.push(code.reverse_bits() >> (32 - len), len)
+        }
+
+        fn finish(mut self) -> Vec<u8> {
+            if self.count > 0 {
+                self.out.push(self.acc as u8);
+            }
+
+            self.out
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::fixture;
+
+    fn decode(data: &[u8]) -> io::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        Decoder::new(data).read_to_end(&mut out)?;
+
+        Ok(out)
+    }
+
+    #[test]
+    fn read_gzip_file() {
+        let expected = fs::read(fixture("lorem.txt")).unwrap();
+
+        let mut data = Vec::new();
+        read_file(&fixture("compressed.txt.gz"))
+            .unwrap()
+            .read_to_end(&mut data)
+            .unwrap();
+
+        assert_eq!(data, expected)
+    }
+
+    #[test]
+    fn size_hint_fixture() {
+        let expected = fs::metadata(fixture("lorem.txt")).unwrap().len();
+
+        let hint = size_hint(&fixture("compressed.txt.gz")).unwrap();
+
+        assert_eq!(u64::from(hint), expected)
+    }
+
+    #[test]
+    fn concatenated_members() {
+        let member = fs::read(fixture("compressed.txt.gz")).unwrap();
+        let expected = fs::read(fixture("lorem.txt")).unwrap().repeat(2);
+
+        let data = decode(&member.repeat(2)).unwrap();
+
+        assert_eq!(data, expected)
+    }
+
+    #[test]
+    fn corrupt_checksum() {
+        let mut member = fs::read(fixture("compressed.txt.gz")).unwrap();
+        let at = member.len() - 8;
+        member[at] ^= 0xff;
+
+        let err = decode(&member).unwrap_err();
+
+        // flate2 reports a bad checksum as invalid input
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput)
+    }
+
+    #[test]
+    fn zeros_member() {
+        for len in [0, 1, 258, 259, 100_000] {
+            assert_eq!(decode(&test_data::zeros(len, true)).unwrap(), vec![0; len])
+        }
+    }
+
+    #[test]
+    fn truncated_member() {
+        let member = fs::read(fixture("compressed.txt.gz")).unwrap();
+
+        for len in [0, 5, 12, member.len() / 2, member.len() - 3] {
+            let err = decode(&member[..len]).unwrap_err();
+
+            assert!(
+                matches!(
+                    err.kind(),
+                    io::ErrorKind::InvalidData | io::ErrorKind::UnexpectedEof
+                ),
+                "{}",
+                len
+            )
+        }
+    }
+}
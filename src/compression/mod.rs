@@ -0,0 +1,10 @@
+//! Decoders for the compressed formats that inputs may be stored in.
+
+#[cfg(feature = "gzip")]
+pub(crate) mod gzip;
+
+#[cfg(feature = "zip")]
+pub(crate) mod zip;
+
+#[cfg(feature = "zstd")]
+pub(crate) mod zstd;
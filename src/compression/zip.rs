@@ -0,0 +1,180 @@
+//! Extracting a single named member from a zip archive. The archive's directory is read with
+//! the [zip] crate, and the member is then decompressed with [flate2] as it is read, rather than
+//! held in memory. Only stored and deflated members are supported.
+
+use std::{
+    fs,
+    io::{self, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use flate2::{bufread::DeflateDecoder, CrcReader};
+use zip::{CompressionMethod, ZipArchive};
+
+type Data = io::Take<io::BufReader<fs::File>>;
+
+/// A member of an archive, which is decompressed as it is read. Its checksum is verified once
+/// all of it has been read.
+pub(crate) struct Member {
+    data: CrcReader<Body>,
+    crc: u32,
+}
+
+enum Body {
+    Stored(Data),
+    Deflated(DeflateDecoder<Data>),
+}
+
+impl Read for Body {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Stored(ref mut data) => data.read(buf),
+            Self::Deflated(ref mut data) => data.read(buf),
+        }
+    }
+}
+
+impl Read for Member {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.data.read(buf)?;
+
+        if n == 0 && !buf.is_empty() && self.data.crc().sum() != self.crc {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "zip member checksum mismatch",
+            ));
+        }
+
+        Ok(n)
+    }
+}
+
+/// Open the member with the given name from the archive at path. A missing member is reported
+/// as an error of [NotFound][io::ErrorKind::NotFound].
+pub(crate) fn read_member(path: &Path, member: &str) -> io::Result<Member> {
+    let mut archive = ZipArchive::new(io::BufReader::new(fs::File::open(path)?))?;
+
+    let (method, start, len, crc) = {
+        let entry = archive.by_name(member)?;
+
+        (
+            entry.compression(),
+            entry.data_start(),
+            entry.compressed_size(),
+            entry.crc32(),
+        )
+    };
+
+    let mut file = archive.into_inner();
+    file.seek(SeekFrom::Start(start))?;
+    let data = file.take(len);
+
+    let body = match method {
+        CompressionMethod::Stored => Body::Stored(data),
+        CompressionMethod::Deflated => Body::Deflated(DeflateDecoder::new(data)),
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "unsupported zip compression method",
+            ))
+        }
+    };
+
+    Ok(Member {
+        data: CrcReader::new(body),
+        crc,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::PathBuf;
+
+    use crate::test_support::{fixture, TempDir};
+
+    fn archive() -> PathBuf {
+        fixture("archive.zip")
+    }
+
+    fn read_to_vec(path: &Path, member: &str) -> io::Result<Vec<u8>> {
+        let mut data = Vec::new();
+        read_member(path, member)?.read_to_end(&mut data)?;
+
+        Ok(data)
+    }
+
+    #[test]
+    fn read_deflated_member() {
+        let expected = std::fs::read(fixture("lorem.txt")).unwrap();
+
+        let data = read_to_vec(&archive(), "lorem.txt").unwrap();
+
+        assert_eq!(data, expected)
+    }
+
+    #[test]
+    fn read_stored_member() {
+        let data = read_to_vec(&archive(), "dir/stored.txt").unwrap();
+
+        assert_eq!(data, b"stored, not compressed\n")
+    }
+
+    #[test]
+    fn corrupt_member() {
+        let mut archive = std::fs::read(archive()).unwrap();
+        let dir = TempDir::new("corrupt-zip");
+
+        let stored = b"stored, not compressed";
+        let at = archive
+            .windows(stored.len())
+            .position(|w| w == stored)
+            .unwrap();
+        archive[at] ^= 0xff;
+        let path = dir.write("corrupt.zip", archive);
+
+        let err = read_to_vec(&path, "dir/stored.txt").unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData)
+    }
+
+    #[test]
+    fn missing_member() {
+        let err = read_member(&archive(), "nope.txt").err().unwrap();
+
+        assert_eq!(err.kind(), io::ErrorKind::NotFound)
+    }
+
+    #[test]
+    fn truncated_archive() {
+        let archive = std::fs::read(archive()).unwrap();
+        let dir = TempDir::new("truncated-zip");
+
+        // Cutting the end off loses the end of central directory record, while keeping only the
+        // 22 byte record leaves it pointing at data which is no longer there
+        let cuts = [0, 2, 4, 21, archive.len() / 2];
+        let truncated = cuts
+            .iter()
+            .map(|&len| &archive[..len])
+            .chain(std::iter::once(&archive[archive.len() - 22..]));
+
+        for data in truncated {
+            let path = dir.write("truncated.zip", data);
+
+            let err = read_to_vec(&path, "lorem.txt").unwrap_err();
+
+            // The zip crate reports seeking to a directory before the start of the file as
+            // invalid input
+            assert!(
+                matches!(
+                    err.kind(),
+                    io::ErrorKind::InvalidData | io::ErrorKind::InvalidInput
+                ),
+                "{}: {}",
+                data.len(),
+                err
+            )
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! Decompression of zstd files as they are read, via [zstd]. Concatenated frames are decoded
+//! one after another, as `zstd -d` does.
+
+use std::{
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+/// Decompresses a zstd stream as it is read
+pub(crate) type Decoder<R> = zstd::stream::read::Decoder<'static, R>;
+
+/// Checks whether the given path has a `.zst` extension
+pub(crate) fn is_zstd_path(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "zst")
+}
+
+/// Open the zstd file at path, which is decompressed as it is read
+pub(crate) fn read_file(path: &Path) -> io::Result<Decoder<io::BufReader<fs::File>>> {
+    fs::File::open(path).and_then(Decoder::new)
+}
+
+/// The longest a frame header can be, ZSTD_FRAMEHEADERSIZE_MAX
+const FRAME_HEADER_MAX: usize = 18;
+
+/// Read the uncompressed size recorded in the header of the first frame of the zstd file at
+/// path, returning None if the frame doesn't record it
+pub(crate) fn size_hint(path: &Path) -> io::Result<Option<u64>> {
+    let mut header = Vec::with_capacity(FRAME_HEADER_MAX);
+    fs::File::open(path)?
+        .take(FRAME_HEADER_MAX as u64)
+        .read_to_end(&mut header)?;
+
+    content_size(&header)
+}
+
+/// Read the uncompressed size recorded in the given frame header, if any
+fn content_size(header: &[u8]) -> io::Result<Option<u64>> {
+    zstd::zstd_safe::get_frame_content_size(header)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "invalid zstd frame header"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::fixture;
+
+    #[test]
+    fn read_zstd_file() {
+        let expected = fs::read(fixture("lorem.txt")).unwrap();
+
+        let mut data = Vec::new();
+        read_file(&fixture("compressed.txt.zst"))
+            .unwrap()
+            .read_to_end(&mut data)
+            .unwrap();
+
+        assert_eq!(data, expected)
+    }
+
+    #[test]
+    fn size_hint_fixture() {
+        let expected = fs::metadata(fixture("lorem.txt")).unwrap().len();
+
+        let hint = size_hint(&fixture("compressed.txt.zst")).unwrap();
+
+        assert_eq!(hint, Some(expected))
+    }
+
+    #[test]
+    fn size_hint_unknown() {
+        // Streamed frames don't record their size
+        let mut frame = Vec::new();
+        let mut encoder = zstd::stream::write::Encoder::new(&mut frame, 0).unwrap();
+        io::Write::write_all(&mut encoder, b"streamed").unwrap();
+        encoder.finish().unwrap();
+
+        assert_eq!(content_size(&frame).unwrap(), None)
+    }
+
+    #[test]
+    fn size_hint_not_zstd() {
+        let err = size_hint(&fixture("lorem.txt")).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData)
+    }
+}
@@ -26,6 +26,150 @@ impl AccessError {
             inner: Inner::file_cxt(err, context.as_ref().to_owned()),
         }
     }
+
+    /// Create a new error that originates from reading an already accessed input. Errors caused
+    /// by a [max_bytes][crate::Builder::max_bytes] limit are reported as
+    /// [Kind::LimitExceeded].
+    pub fn read(err: io::Error) -> Self {
+        let limit = err
+            .get_ref()
+            .and_then(|e| e.downcast_ref::<LimitExceeded>())
+            .map(|e| e.max);
+
+        let inner = match limit {
+            Some(max) => Inner::LimitExceeded { max },
+            None => Inner::Read { err },
+        };
+
+        Self { inner }
+    }
+
+    /// Create a new error for a member of an archive which couldn't be read
+    pub fn archive_member(
+        err: io::Error,
+        archive: impl AsRef<Path>,
+        member: impl AsRef<str>,
+    ) -> Self {
+        Self {
+            inner: Inner::ArchiveMember {
+                archive: archive.as_ref().to_owned(),
+                member: member.as_ref().to_string(),
+                err,
+            },
+        }
+    }
+
+    /// Create a new error that originates from an attempt to fetch a URL
+    pub fn url(err: io::Error, url: impl AsRef<str>) -> Self {
+        Self {
+            inner: Inner::Url {
+                url: url.as_ref().to_string(),
+                err,
+            },
+        }
+    }
+
+    /// Create a new error that originates from an attempt to connect to a TCP server
+    pub fn tcp(err: io::Error, addr: impl AsRef<str>) -> Self {
+        Self {
+            inner: Inner::Tcp {
+                addr: addr.as_ref().to_string(),
+                err,
+            },
+        }
+    }
+
+    /// Create a new error for a clipboard which couldn't be read, or was empty
+    pub fn clipboard(err: io::Error) -> Self {
+        Self {
+            inner: Inner::Clipboard { err },
+        }
+    }
+
+    /// Create a new error for an environment variable which couldn't be read
+    pub fn env(err: std::env::VarError, name: impl AsRef<str>) -> Self {
+        Self {
+            inner: Inner::Env {
+                name: name.as_ref().to_string(),
+                err,
+            },
+        }
+    }
+
+    /// Create a new error for stdin being an interactive terminal, when piped input was required
+    pub fn stdin_is_terminal() -> Self {
+        Self {
+            inner: Inner::StdinTerminal,
+        }
+    }
+
+    /// Create a new error for stdin which was required to contain data, but didn't
+    pub fn empty_stdin() -> Self {
+        Self {
+            inner: Inner::EmptyStdin,
+        }
+    }
+
+    /// Create a new error for accessing stdin after it was already accessed, when
+    /// [guard_stdin][crate::Builder::guard_stdin] is set
+    pub fn stdin_already_consumed() -> Self {
+        Self {
+            inner: Inner::StdinConsumed,
+        }
+    }
+
+    /// Create a new error for attempting to read stdin in reverse, which requires seeking
+    pub fn stdin_not_seekable() -> Self {
+        Self {
+            inner: Inner::StdinNotSeekable,
+        }
+    }
+
+    /// Create a new error for attempting to seek within an input of the given kind, which can
+    /// only be read from start to end
+    pub fn not_seekable(kind: crate::InputKind) -> Self {
+        Self {
+            inner: Inner::NotSeekable { kind },
+        }
+    }
+
+    /// Create a new error for an argfile nested more than max argfiles deep, see
+    /// `Builder::max_argfile_depth`
+    pub fn argfile_depth(path: impl AsRef<Path>, max: usize) -> Self {
+        Self {
+            inner: Inner::ArgfileDepth {
+                path: path.as_ref().to_owned(),
+                max,
+            },
+        }
+    }
+
+    /// Create a new error for an argfile which refers back to itself, directly or through other
+    /// argfiles
+    pub fn argfile_cycle(path: impl AsRef<Path>) -> Self {
+        Self {
+            inner: Inner::ArgfileCycle {
+                path: path.as_ref().to_owned(),
+            },
+        }
+    }
+
+    /// Create a new error for a file that exists but contains no data
+    pub fn empty_file(path: impl AsRef<Path>) -> Self {
+        Self {
+            inner: Inner::EmptyFile {
+                path: Some(path.as_ref().to_owned()),
+            },
+        }
+    }
+
+    /// Create a new error for a file [opened by the caller][crate::Input::from_open_file],
+    /// which has no path, that contains no data
+    pub fn empty_open_file() -> Self {
+        Self {
+            inner: Inner::EmptyFile { path: None },
+        }
+    }
 }
 
 impl fmt::Display for AccessError {
@@ -44,15 +188,60 @@ impl std::error::Error for AccessError {}
 
 /// A cheap descriptor of the kind of access error encountered
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum Kind {
     /// The underlying error originates from attempting to access a file
     File,
+    /// The underlying file exists, but is empty
+    EmptyFile,
+    /// The input was accessed, but reading from it failed
+    Read,
+    /// A member of an archive was missing or couldn't be read
+    ArchiveMember,
+    /// The underlying error originates from attempting to fetch a URL
+    Url,
+    /// The underlying error originates from attempting to connect to a TCP server
+    Tcp,
+    /// An environment variable was unset, or not valid unicode
+    Env,
+    /// The clipboard couldn't be read, or was empty
+    Clipboard,
+    /// Stdin couldn't be used, e.g it was an interactive terminal rather than piped input
+    Stdin,
+    /// Stdin contained no data, when [require_nonempty][crate::parsers::Stdin::require_nonempty]
+    /// was set
+    EmptyStdin,
+    /// Stdin was already accessed by an earlier input, when
+    /// [guard_stdin][crate::Builder::guard_stdin] was set
+    StdinAlreadyConsumed,
+    /// The input can only be read from start to end, see [access_seek][crate::Input::access_seek]
+    NotSeekable,
+    /// The input contained more data than allowed by [max_bytes][crate::Builder::max_bytes]
+    LimitExceeded,
+    /// Argfiles were nested deeper than allowed by `Builder::max_argfile_depth`
+    ArgfileDepth,
+    /// An argfile referred back to itself, directly or through other argfiles
+    ArgfileCycle,
 }
 
 impl fmt::Display for Kind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let kind = match self {
             Self::File => "file",
+            Self::EmptyFile => "empty file",
+            Self::Read => "read",
+            Self::ArchiveMember => "archive member",
+            Self::Url => "url",
+            Self::Tcp => "tcp",
+            Self::Env => "env",
+            Self::Clipboard => "clipboard",
+            Self::Stdin => "stdin",
+            Self::EmptyStdin => "empty stdin",
+            Self::StdinAlreadyConsumed => "stdin already consumed",
+            Self::NotSeekable => "not seekable",
+            Self::LimitExceeded => "limit exceeded",
+            Self::ArgfileDepth => "argfile depth",
+            Self::ArgfileCycle => "argfile cycle",
         };
 
         write!(f, "{}", kind)
@@ -66,12 +255,69 @@ enum Inner {
         context: Option<PathBuf>,
         err: io::Error,
     },
+    EmptyFile {
+        path: Option<PathBuf>,
+    },
+    Read {
+        err: io::Error,
+    },
+    ArchiveMember {
+        archive: PathBuf,
+        member: String,
+        err: io::Error,
+    },
+    Url {
+        url: String,
+        err: io::Error,
+    },
+    Tcp {
+        addr: String,
+        err: io::Error,
+    },
+    Env {
+        name: String,
+        err: std::env::VarError,
+    },
+    Clipboard {
+        err: io::Error,
+    },
+    StdinTerminal,
+    StdinNotSeekable,
+    EmptyStdin,
+    StdinConsumed,
+    NotSeekable {
+        kind: crate::InputKind,
+    },
+    LimitExceeded {
+        max: u64,
+    },
+    ArgfileDepth {
+        path: PathBuf,
+        max: usize,
+    },
+    ArgfileCycle {
+        path: PathBuf,
+    },
 }
 
 impl Inner {
     fn kind(&self) -> Kind {
         match self {
             Self::File { .. } => Kind::File,
+            Self::EmptyFile { .. } => Kind::EmptyFile,
+            Self::Read { .. } => Kind::Read,
+            Self::ArchiveMember { .. } => Kind::ArchiveMember,
+            Self::Url { .. } => Kind::Url,
+            Self::Tcp { .. } => Kind::Tcp,
+            Self::Env { .. } => Kind::Env,
+            Self::Clipboard { .. } => Kind::Clipboard,
+            Self::StdinTerminal | Self::StdinNotSeekable => Kind::Stdin,
+            Self::EmptyStdin => Kind::EmptyStdin,
+            Self::StdinConsumed => Kind::StdinAlreadyConsumed,
+            Self::NotSeekable { .. } => Kind::NotSeekable,
+            Self::LimitExceeded { .. } => Kind::LimitExceeded,
+            Self::ArgfileDepth { .. } => Kind::ArgfileDepth,
+            Self::ArgfileCycle { .. } => Kind::ArgfileCycle,
         }
     }
 }
@@ -98,6 +344,97 @@ impl fmt::Display for Inner {
                 Some(path) => write!(f, "unable to open {}: {}", path.display(), err),
                 None => write!(f, "unable to open file: {}", err),
             },
+            EmptyFile { path } => match path {
+                Some(path) => write!(f, "{} contains no data", path.display()),
+                None => f.write_str("file contains no data"),
+            },
+            Read { err } => write!(f, "unable to read input: {}", err),
+            ArchiveMember {
+                archive,
+                member,
+                err,
+            } => write!(
+                f,
+                "unable to read {} from {}: {}",
+                member,
+                archive.display(),
+                err
+            ),
+            Url { url, err } => write!(f, "unable to fetch {}: {}", url, err),
+            Tcp { addr, err } => write!(f, "unable to connect to {}: {}", addr, err),
+            Env { name, err } => write!(f, "unable to read ${}: {}", name, err),
+            Clipboard { err } => write!(f, "unable to read the clipboard: {}", err),
+            StdinTerminal => write!(f, "expected piped input, but stdin is a terminal"),
+            StdinNotSeekable => write!(f, "stdin can't be read in reverse"),
+            EmptyStdin => write!(f, "expected piped input, but stdin contains no data"),
+            StdinConsumed => write!(f, "stdin was already read by another input"),
+            NotSeekable { kind } => write!(f, "{:?} inputs can't be seeked", kind),
+            LimitExceeded { max } => write!(f, "input is larger than {} bytes", max),
+            ArgfileDepth { path, max } => write!(
+                f,
+                "{} is nested more than {} argfiles deep",
+                path.display(),
+                max
+            ),
+            ArgfileCycle { path } => write!(f, "{} refers back to itself", path.display()),
         }
     }
 }
+
+/// The error carried by an [io::Error] when a read limit is exceeded
+#[derive(Debug)]
+pub(crate) struct LimitExceeded {
+    max: u64,
+}
+
+impl LimitExceeded {
+    pub fn new(max: u64) -> Self {
+        Self { max }
+    }
+}
+
+impl From<LimitExceeded> for io::Error {
+    fn from(err: LimitExceeded) -> Self {
+        io::Error::other(err)
+    }
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "input is larger than {} bytes", self.max)
+    }
+}
+
+impl std::error::Error for LimitExceeded {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_kind() {
+        let err = AccessError::read(io::Error::new(io::ErrorKind::BrokenPipe, "pipe closed"));
+
+        assert_eq!(err.kind(), Kind::Read);
+        assert_eq!(
+            err.to_string(),
+            "read access failed: unable to read input: pipe closed"
+        )
+    }
+
+    #[test]
+    fn read_limit_exceeded() {
+        let err = AccessError::read(LimitExceeded::new(8).into());
+
+        assert_eq!(err.kind(), Kind::LimitExceeded)
+    }
+
+    #[test]
+    fn eq_by_kind() {
+        let a = AccessError::read(io::Error::new(io::ErrorKind::BrokenPipe, "a"));
+        let b = AccessError::read(io::Error::new(io::ErrorKind::Interrupted, "b"));
+
+        assert_eq!(a, b);
+        assert_ne!(a, AccessError::stdin_is_terminal())
+    }
+}
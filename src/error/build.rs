@@ -0,0 +1,144 @@
+//! Contains the error returned attempting to [finish][crate::Builder::finish] a
+//! [Builder][crate::Builder], and the [WeightConflict] returned by
+//! [check_weights][crate::Builder::check_weights].
+
+use crate::parsers::ParserName;
+
+use std::fmt;
+
+/// A error representing some problem with a [Builder][crate::Builder]'s configuration, which
+/// would prevent it from producing a usable [Config][crate::Config]. Like
+/// [AccessError][super::access::AccessError], this type exposes a [kind][BuildError::kind]
+/// method that returns an enum describing the problem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildError {
+    inner: Inner,
+}
+
+impl BuildError {
+    /// Returns a enum describes the type of error encountered
+    pub fn kind(&self) -> Kind {
+        self.inner.kind()
+    }
+
+    /// Create a new error for a builder without any enabled parsers
+    pub fn no_parsers() -> Self {
+        Self {
+            inner: Inner::NoParsers,
+        }
+    }
+
+    /// Create a new error for two parsers which share the same weight, making the order they are
+    /// tried in ambiguous
+    pub fn weight_collision(first: ParserName, second: ParserName, weight: u8) -> Self {
+        WeightConflict::new(first, second, weight).into()
+    }
+}
+
+impl From<WeightConflict> for BuildError {
+    fn from(conflict: WeightConflict) -> Self {
+        Self {
+            inner: Inner::WeightCollision(conflict),
+        }
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid builder: {}", self.inner)
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// Two parsers which share the same weight, making the order they are tried in ambiguous
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeightConflict {
+    first: ParserName,
+    second: ParserName,
+    weight: u8,
+}
+
+impl WeightConflict {
+    /// Create a new conflict between the given parsers
+    pub fn new(first: ParserName, second: ParserName, weight: u8) -> Self {
+        Self {
+            first,
+            second,
+            weight,
+        }
+    }
+
+    /// Returns the parser which would currently be tried first
+    pub fn first(&self) -> ParserName {
+        self.first
+    }
+
+    /// Returns the parser which would currently be tried second
+    pub fn second(&self) -> ParserName {
+        self.second
+    }
+
+    /// Returns the weight both parsers share
+    pub fn weight(&self) -> u8 {
+        self.weight
+    }
+}
+
+impl fmt::Display for WeightConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the {} and {} parsers both have a weight of {}",
+            self.first, self.second, self.weight
+        )
+    }
+}
+
+impl std::error::Error for WeightConflict {}
+
+/// A cheap descriptor of the kind of build error encountered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Kind {
+    /// The builder has no parsers enabled
+    NoParsers,
+    /// Two parsers have the same weight, while [ordered by weight][crate::Order::Weight]
+    WeightCollision,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            Self::NoParsers => "no parsers",
+            Self::WeightCollision => "weight collision",
+        };
+
+        write!(f, "{}", kind)
+    }
+}
+
+/// The actual representation of a BuildError
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Inner {
+    NoParsers,
+    WeightCollision(WeightConflict),
+}
+
+impl Inner {
+    fn kind(&self) -> Kind {
+        match self {
+            Self::NoParsers => Kind::NoParsers,
+            Self::WeightCollision(_) => Kind::WeightCollision,
+        }
+    }
+}
+
+impl fmt::Display for Inner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoParsers => write!(f, "at least one parser must be enabled"),
+            Self::WeightCollision(conflict) => write!(f, "{}", conflict),
+        }
+    }
+}
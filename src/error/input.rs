@@ -7,17 +7,56 @@ pub use kind::EKind;
 /// An error originating from an attempt to parse some input into a well understood
 /// [Input][crate::input::Input]. This type may accumulate multiple errors, particularly in cases
 /// where multiple attempts at parsing are made.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Errors compare equal if they contain the same kinds and labels, any context or list of
+/// [tried][InputError::tried] parsers is ignored.
+#[derive(Debug, Clone)]
 pub struct InputError {
     flags: kind::EKind,
+    labels: Vec<String>,
+    context: Vec<String>,
+    /// The input no parser matched, and the name of every parser tried
+    unmatched: Option<(String, Vec<&'static str>)>,
 }
 
 impl InputError {
-    const ALL_KINDS: [EKind; 4] = [EKind::TEXT, EKind::STDIN, EKind::FILE, EKind::REQUIRES_UTF8];
+    const ALL_KINDS: [EKind; 13] = [
+        EKind::TEXT,
+        EKind::STDIN,
+        EKind::FILE,
+        EKind::URL,
+        EKind::ENV,
+        EKind::GLOB,
+        EKind::BASE64,
+        EKind::TCP,
+        EKind::CLIPBOARD,
+        EKind::REQUIRES_UTF8,
+        EKind::TOO_MANY_INPUTS,
+        EKind::FILE_NOT_FOUND,
+        EKind::MISSING_VALUE,
+    ];
 
     /// Create a new error from the given kind
     pub fn new(kind: EKind) -> Self {
-        Self { flags: kind }
+        Self {
+            flags: kind,
+            labels: Vec::new(),
+            context: Vec::new(),
+            unmatched: None,
+        }
+    }
+
+    /// Create a new error of the given kind from an error returned by a nom parser, capturing
+    /// the nom error's description as context. This is primarily useful for custom parsers that
+    /// need to report failures using this crate's error type.
+    pub fn from_nom<E>(kind: EKind, err: nom::Err<E>) -> Self
+    where
+        E: fmt::Debug,
+    {
+        let mut this = Self::new(kind);
+        this.context.push(err.to_string());
+
+        this
     }
 
     /// Convenience function for adding additional errors
@@ -40,39 +79,107 @@ impl InputError {
         self
     }
 
+    /// Attach the label of a parser which failed to this error
+    pub fn add_label(&mut self, label: impl AsRef<str>) -> &mut Self {
+        self.labels.push(label.as_ref().to_string());
+
+        self
+    }
+
+    /// Attach additional context describing why parsing failed to this error
+    pub fn add_context(&mut self, context: impl AsRef<str>) -> &mut Self {
+        self.context.push(context.as_ref().to_string());
+
+        self
+    }
+
     /// Extend this error from another
     pub fn extend(&mut self, other: Self) -> &mut Self {
         self.insert(other.flags);
+        self.labels.extend(other.labels);
+        self.context.extend(other.context);
 
         self
     }
 
+    /// Iterate over the labels of the failed parsers, in the order they were added
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.labels.iter().map(String::as_str)
+    }
+
+    /// Iterate over any additional context describing why parsing failed
+    pub fn context(&self) -> impl Iterator<Item = &str> {
+        self.context.iter().map(String::as_str)
+    }
+
+    /// Iterate over the [name][crate::parsers::ParserName::as_str] of every parser a
+    /// [Config][crate::Config] tried before giving up, in the order they were tried. This is
+    /// empty for errors which didn't come from a Config.
+    pub fn tried(&self) -> impl Iterator<Item = &str> {
+        self.unmatched
+            .iter()
+            .flat_map(|(_, tried)| tried.iter().copied())
+    }
+
+    /// Record that no parser matched the given input, after trying each of the named parsers
+    pub(crate) fn set_unmatched(&mut self, input: impl AsRef<str>, tried: Vec<&'static str>) {
+        self.unmatched = Some((input.as_ref().to_string(), tried));
+    }
+
     /// Check if this error contains the given kind
     pub fn contains(&self, kind: EKind) -> bool {
         self.flags.contains(kind)
     }
 
-    /// Count the number of kinds this error contains
-    pub fn count(&self) -> usize {
+    /// Iterate over each kind this error contains, one flag at a time
+    pub fn kinds(&self) -> impl Iterator<Item = EKind> + '_ {
         Self::ALL_KINDS
             .iter()
-            .filter(|&&k| self.contains(k))
-            .count()
+            .copied()
+            .filter(move |&k| self.contains(k))
+    }
+
+    /// Count the number of kinds this error contains
+    pub fn count(&self) -> usize {
+        self.kinds().count()
     }
 }
 
 impl fmt::Display for InputError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.count() > 1 {
+        if let Some((ref input, ref tried)) = self.unmatched {
+            write!(
+                f,
+                "no parser matched '{}' (tried: {})",
+                input,
+                tried.join(", ")
+            )?;
+        } else if self.count() > 1 {
             write!(f, "Multiple parsers failed [{:?}]", self.flags)?;
         } else {
             write!(f, "Parser failed [{:?}]", self.flags)?;
         }
 
+        if !self.labels.is_empty() {
+            write!(f, " ({})", self.labels.join(", "))?;
+        }
+
+        if !self.context.is_empty() {
+            write!(f, ": {}", self.context.join("; "))?;
+        }
+
         Ok(())
     }
 }
 
+impl PartialEq for InputError {
+    fn eq(&self, other: &Self) -> bool {
+        self.flags == other.flags && self.labels == other.labels
+    }
+}
+
+impl Eq for InputError {}
+
 impl std::error::Error for InputError {}
 
 impl From<EKind> for InputError {
@@ -81,6 +188,59 @@ impl From<EKind> for InputError {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_single() {
+        let err = InputError::new(EKind::FILE);
+
+        assert_eq!(err.to_string(), "Parser failed [FILE]")
+    }
+
+    #[test]
+    fn display_labels() {
+        let err = InputError::new(EKind::FILE).with(|this| {
+            this.add_label("config file")
+                .extend(InputError::new(EKind::TEXT).with(|e| e.add_label("notes")))
+        });
+
+        assert_eq!(
+            err.to_string(),
+            "Multiple parsers failed [TEXT | FILE] (config file, notes)"
+        )
+    }
+
+    #[test]
+    fn kinds_iter() {
+        let err = InputError::new(EKind::TEXT).with(|this| this.extend(EKind::FILE.into()));
+
+        let kinds: Vec<_> = err.kinds().collect();
+
+        assert_eq!(kinds, vec![EKind::TEXT, EKind::FILE]);
+        assert_eq!(err.count(), 2)
+    }
+
+    #[test]
+    fn from_nom_error() {
+        use nom::error::{make_error, ErrorKind};
+
+        let nom_err: nom::Err<nom::error::Error<&str>> =
+            nom::Err::Error(make_error("bad input", ErrorKind::Tag));
+
+        let err = InputError::from_nom(EKind::FILE, nom_err);
+
+        assert!(err.contains(EKind::FILE));
+        assert_eq!(err.count(), 1);
+
+        let context: Vec<_> = err.context().collect();
+        assert_eq!(context.len(), 1);
+        assert!(context[0].contains("bad input"));
+        assert!(err.to_string().contains("bad input"))
+    }
+}
+
 mod kind {
     use bitflags::bitflags;
 
@@ -95,11 +255,33 @@ mod kind {
             const STDIN = 0b000_0000_0000_0000_0000_0000_0000_0010;
             /// Error originates from the [File][crate::parsers::File] parser
             const FILE = 0b000_0000_0000_0000_0000_0000_0000_0100;
+            /// Error originates from the [Url][crate::parsers::Url] parser
+            const URL = 0b000_0000_0000_0000_0000_0000_0000_1000;
+            /// Error originates from the [Env][crate::parsers::Env] parser
+            const ENV = 0b000_0000_0000_0000_0000_0000_0001_0000;
+            /// Error originates from the [Glob][crate::parsers::Glob] parser
+            const GLOB = 0b000_0000_0000_0000_0000_0000_0010_0000;
+            /// Error originates from the [Base64][crate::parsers::Base64] parser
+            const BASE64 = 0b000_0000_0000_0000_0000_0000_0100_0000;
+            /// Error originates from the [Tcp][crate::parsers::Tcp] parser
+            const TCP = 0b000_0000_0000_0000_0000_0000_1000_0000;
+            /// Error originates from the [Clipboard][crate::parsers::Clipboard] parser
+            const CLIPBOARD = 0b000_0000_0000_0000_0000_0001_0000_0000;
 
             // General Errors
 
             /// A parser reported that it requires UTF8 input
             const REQUIRES_UTF8 = 0b000_0000_0000_0001_0000_0000_0000_0000;
+            /// An input expanded into more inputs than allowed by
+            /// [max_expanded][crate::Builder::max_expanded]
+            const TOO_MANY_INPUTS = 0b000_0000_0000_0010_0000_0000_0000_0000;
+            /// A file input doesn't exist, when
+            /// [must_exist][crate::parsers::File::must_exist] is set
+            const FILE_NOT_FOUND = 0b000_0000_0000_0100_0000_0000_0000_0000;
+            /// There was no input to parse, e.g an argument passed to
+            /// [from_matches][crate::from_matches] wasn't given, or a list passed to
+            /// [parse_list][crate::Config::parse_list] contained an empty item
+            const MISSING_VALUE = 0b000_0000_0000_1000_0000_0000_0000_0000;
         }
     }
 }
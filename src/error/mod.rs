@@ -3,4 +3,5 @@
 //! See the individual modules for more information.
 
 pub mod access;
+pub mod build;
 pub mod input;
@@ -0,0 +1,136 @@
+//! Glob expansion used by the [Glob][crate::parsers::Glob] parser, backed by the [glob] crate.
+//!
+//! Supported syntax is `*` (any run of characters within a path component), `?` (any single
+//! character), `[abc]`, `[a-z]` and `[!abc]` character classes, and `**` as a whole component
+//! which matches zero or more directories. As in most shells, wildcards don't match a leading
+//! `.` in a file name unless the pattern itself names a hidden file or directory.
+
+use std::{
+    io,
+    path::{Component, Path, PathBuf},
+};
+
+/// Checks whether the given pattern contains any glob syntax
+pub(crate) fn is_pattern(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '['])
+}
+
+/// Expand the given pattern into the regular files it matches, relative to the current
+/// directory, in sorted order. If a limit is given, expansion stops early once more than limit
+/// files have been found.
+pub(crate) fn expand(pattern: &str, limit: Option<usize>) -> io::Result<Vec<PathBuf>> {
+    let paths =
+        glob::glob(pattern).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.msg))?;
+    let limit = limit.unwrap_or(usize::MAX);
+    let hidden = Hidden::new(pattern);
+    let mut found = Vec::new();
+
+    for path in paths {
+        let path = match path {
+            Ok(path) => path,
+            // Directories which can't be read are treated as empty
+            Err(e) if e.error().kind() == io::ErrorKind::PermissionDenied => continue,
+            Err(e) => return Err(e.into()),
+        };
+
+        if path.is_file() && !hidden.excludes(&path) {
+            found.push(path);
+        }
+
+        if found.len() > limit {
+            break;
+        }
+    }
+
+    found.sort();
+
+    Ok(found)
+}
+
+/// Decides whether a matched path is hidden from the pattern, i.e a wildcard matched a name
+/// starting with `.`
+struct Hidden {
+    /// The number of leading components of the pattern without any glob syntax
+    literal: usize,
+    /// Whether the pattern itself names a hidden file or directory
+    explicit: bool,
+}
+
+impl Hidden {
+    fn new(pattern: &str) -> Self {
+        let components: Vec<_> = Path::new(pattern).components().collect();
+        let literal = components
+            .iter()
+            .take_while(|c| !is_pattern(&c.as_os_str().to_string_lossy()))
+            .count();
+        let explicit = components[literal..].iter().any(|c| is_dotted(c));
+
+        Self { literal, explicit }
+    }
+
+    fn excludes(&self, path: &Path) -> bool {
+        !self.explicit && path.components().skip(self.literal).any(|c| is_dotted(&c))
+    }
+}
+
+fn is_dotted(component: &Component<'_>) -> bool {
+    match component {
+        Component::Normal(name) => name.to_string_lossy().starts_with('.'),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::fixture;
+
+    #[test]
+    fn expand_fixtures() {
+        let found = expand(&fixture("utf16*.txt").to_string_lossy(), None).unwrap();
+
+        assert_eq!(found, [fixture("utf16be.txt"), fixture("utf16le.txt")])
+    }
+
+    #[test]
+    fn expand_recursive() {
+        let found = expand("tes*/**/*.bin", None).unwrap();
+
+        assert_eq!(found, [PathBuf::from("tests/fixtures/binary.bin")])
+    }
+
+    #[test]
+    fn expand_limit() {
+        let found = expand("tests/fixtures/*", Some(2)).unwrap();
+
+        assert_eq!(found.len(), 3)
+    }
+
+    #[test]
+    fn expand_no_matches() {
+        let found = expand("tests/fixtures/*.nothing", None).unwrap();
+
+        assert!(found.is_empty())
+    }
+
+    #[test]
+    fn expand_invalid_pattern() {
+        let err = expand("tests/***", None).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput)
+    }
+
+    #[test]
+    fn hidden() {
+        let hidden = Hidden::new("logs/*/*.txt");
+
+        assert!(hidden.excludes(Path::new("logs/.old/a.txt")));
+        assert!(hidden.excludes(Path::new("logs/new/.a.txt")));
+        assert!(!hidden.excludes(Path::new("logs/new/a.txt")));
+
+        // Hidden components before any glob syntax, or named by the pattern, are fine
+        assert!(!Hidden::new(".logs/*.txt").excludes(Path::new(".logs/a.txt")));
+        assert!(!Hidden::new("logs/.*").excludes(Path::new("logs/.a")))
+    }
+}
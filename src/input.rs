@@ -1,15 +1,31 @@
-use std::{convert::TryFrom, fmt, io, str::FromStr};
+use std::{
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt, io,
+    str::FromStr,
+    sync::{Arc, OnceLock},
+};
 
 use crate::{
     builder::{Builder, Config},
-    error::{access::AccessError, input::InputError},
-    parsers::InputType,
+    error::{
+        access::{AccessError, LimitExceeded},
+        input::InputError,
+    },
+    parsers::{
+        is_fifo, Encoding, Endianness, FilePath, InputType, LazyText, OpenFile, SharedReader,
+    },
 };
 
-/// Represents some kind of input source which can be read from.
-#[derive(Debug)]
+/// Represents some kind of input source which can be read from. Clones refer to the same source,
+/// e.g clones of a [from_reader][Input::from_reader] input read from the same reader.
+#[derive(Debug, Clone)]
 pub struct Input {
     kind: InputType,
+    options: ReadOptions,
+    stdin_buffer: OnceLock<Vec<u8>>,
+    /// The argument this input was parsed from, if any
+    spec: Option<Box<str>>,
 }
 
 impl Input {
@@ -26,143 +42,3559 @@ impl Input {
         Config::default().parse(input.as_ref())
     }
 
+    /// Create a text input whose content is produced by the given closure. The closure is only
+    /// called when the input is [accessed](Input::access), and is called again on every access.
+    pub fn from_text_fn<F>(f: F) -> Self
+    where
+        F: Fn() -> String + Send + Sync + 'static,
+    {
+        Self::with_options(
+            InputType::LazyText(LazyText::new(f)),
+            ReadOptions::default(),
+        )
+    }
+
+    /// Create a text input from the given text, without parsing it. Unlike
+    /// [with_defaults][Input::with_defaults], text that looks like another kind of input, e.g
+    /// `@notes.txt`, is kept as text.
+    pub fn from_text(text: impl Into<String>) -> Self {
+        Self::with_options(InputType::UTF8(text.into()), ReadOptions::default())
+    }
+
+    /// Create an input from binary data already held in memory, which is read back unchanged
+    pub fn from_bytes(bytes: impl Into<Vec<u8>>) -> Self {
+        Self::with_options(InputType::Bytes(bytes.into()), ReadOptions::default())
+    }
+
+    /// Create an input from a file which has already been opened, e.g by a caller that checked
+    /// the file's permissions through the handle. The file is read through the given handle
+    /// rather than being reopened by path, so it can't be swapped out in between.
+    ///
+    /// Every access reads through a duplicate of the handle, which shares the file's position,
+    /// so accessing the input again continues from where the last read stopped. The input is
+    /// [a file](Input::is_file), but has no [path](Input::path).
+    pub fn from_open_file(file: std::fs::File) -> Self {
+        Self::with_options(
+            InputType::OpenFile(OpenFile::new(file)),
+            ReadOptions::default(),
+        )
+    }
+
+    /// Create an input which reads from the given reader, e.g a socket or a decompressor that
+    /// is already set up. Like stdin, the reader is shared between every access of the input,
+    /// so accessing it again continues from where the last read stopped.
+    ///
+    /// The reader must be [Send], as inputs may be accessed from other threads.
+    pub fn from_reader(reader: impl io::Read + Send + 'static) -> Self {
+        Self::with_options(
+            InputType::Reader(SharedReader::new(reader)),
+            ReadOptions::default(),
+        )
+    }
+
+    /// Returns which kind of source this input was resolved to
+    pub fn kind(&self) -> InputKind {
+        match self.kind {
+            InputType::Stdin(_) => InputKind::Stdin,
+            InputType::File(_) | InputType::OpenFile(_) => InputKind::File,
+            InputType::Files(_) => InputKind::Files,
+            InputType::Url(_) => InputKind::Url,
+            InputType::Env(_) => InputKind::Env,
+            InputType::Base64(_) => InputKind::Base64,
+            InputType::Bytes(_) => InputKind::Bytes,
+            InputType::Reader(_) => InputKind::Reader,
+            #[cfg(feature = "tcp")]
+            InputType::Tcp(_) => InputKind::Tcp,
+            #[cfg(feature = "clipboard")]
+            InputType::Clipboard => InputKind::Clipboard,
+            InputType::UTF8(_) | InputType::LazyText(_) => InputKind::Text,
+        }
+    }
+
+    /// Returns the argument this input was parsed from exactly as it was given, e.g `@./a.txt`,
+    /// for echoing back in diagnostics. Arguments which weren't valid UTF-8 are converted
+    /// lossily. Inputs not produced by a [Config], like those from [from_text](Input::from_text),
+    /// return None.
+    pub fn spec(&self) -> Option<&str> {
+        self.spec.as_deref()
+    }
+
+    /// Returns true if this input reads from stdin
+    pub fn is_stdin(&self) -> bool {
+        self.kind() == InputKind::Stdin
+    }
+
+    /// Returns true if this input is a single file. Note that several files, e.g from a `Glob`,
+    /// are not
+    pub fn is_file(&self) -> bool {
+        self.kind() == InputKind::File
+    }
+
+    /// Returns true if this input is text, whether given directly or produced by a closure
+    pub fn is_text(&self) -> bool {
+        self.kind() == InputKind::Text
+    }
+
+    /// Returns true if this input is a single file which is a named pipe (FIFO), which blocks
+    /// when accessed until another process writes to it. This checks the file's metadata
+    /// without opening it, and is always false on platforms other than unix or for files which
+    /// don't exist. See also [follow_fifo][crate::parsers::File::follow_fifo].
+    pub fn is_fifo(&self) -> bool {
+        match self.kind {
+            InputType::File(ref f) => f.is_fifo(),
+            InputType::OpenFile(ref f) => f.metadata().is_ok_and(|meta| is_fifo(&meta.file_type())),
+            _ => false,
+        }
+    }
+
+    /// Returns the path of the file behind this input, without accessing it. Inputs which aren't
+    /// a single file, including several files from a `Glob`, and files [opened by the
+    /// caller](Input::from_open_file) return None.
+    pub fn path(&self) -> Option<&std::path::Path> {
+        match self.kind {
+            InputType::File(ref f) => Some(f.path.as_path()),
+            _ => None,
+        }
+    }
+
+    /// Returns true if this input is a file marked for appending, see
+    /// [append_marker][crate::parsers::File::append_marker]
+    pub fn is_append(&self) -> bool {
+        matches!(self.kind, InputType::File(ref f) if f.options.append.is_some())
+    }
+
+    /// Returns the size of the input once decompressed, if it is a compressed file that records
+    /// its original size and transparent decompression (e.g `File::decompress_gzip` or
+    /// `File::decompress_zstd`) is enabled for it. This is intended for preallocating buffers,
+    /// and may be inaccurate.
+    ///
+    /// Gzip only records the size modulo 2^32, so the hint is wrong for files which decompress
+    /// to 4GiB or more. For gzip files with several members, only the last member's size is
+    /// known. Zstd records the size in the header of each frame, but only if it was known when
+    /// compressing, and only the first frame's size is used. Other inputs, including
+    /// decompressed stdin, return None.
+    pub fn decompressed_size_hint(&self) -> Option<u64> {
+        match self.kind {
+            #[cfg(feature = "gzip")]
+            InputType::File(ref f)
+                if f.options.decompress_gzip && crate::compression::gzip::is_gzip_path(&f.path) =>
+            {
+                crate::compression::gzip::size_hint(&f.path)
+                    .ok()
+                    .map(u64::from)
+            }
+            #[cfg(feature = "zstd")]
+            InputType::File(ref f)
+                if f.options.decompress_zstd && crate::compression::zstd::is_zstd_path(&f.path) =>
+            {
+                crate::compression::zstd::size_hint(&f.path).ok().flatten()
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the number of bytes this input's source holds, without reading it, e.g for
+    /// reporting progress. This is the size of the source itself, before any settings which
+    /// change the content as it is read, like [wrap_content][Builder::wrap_content], are
+    /// applied.
+    ///
+    /// Files report their size on disk, and several files their combined size. Text, binary
+    /// data and environment variables report the length of their UTF-8 or raw bytes. Sources
+    /// whose length isn't known until they are read return None: stdin, URLs, readers from
+    /// [from_reader](Input::from_reader), text produced by a closure, and files which are
+    /// decompressed or read from an archive.
+    ///
+    /// Missing files and unset environment variables fail with the same error as accessing
+    /// them would.
+    pub fn len(&self) -> Result<Option<u64>, AccessError> {
+        let file_len = |f: &FilePath| {
+            std::fs::metadata(&f.path)
+                .map(|meta| meta.len())
+                .map_err(|e| AccessError::file_with_context(e, f.path.as_path()))
+        };
+
+        match self.kind {
+            #[cfg(feature = "zip")]
+            InputType::File(ref f) if f.member.is_some() => Ok(None),
+            #[cfg(feature = "gzip")]
+            InputType::File(ref f)
+                if f.options.decompress_gzip && crate::compression::gzip::is_gzip_path(&f.path) =>
+            {
+                Ok(None)
+            }
+            #[cfg(feature = "zstd")]
+            InputType::File(ref f)
+                if f.options.decompress_zstd && crate::compression::zstd::is_zstd_path(&f.path) =>
+            {
+                Ok(None)
+            }
+            InputType::File(ref f) => file_len(f).map(Some),
+            InputType::Files(ref files) => {
+                files.iter().map(file_len).sum::<Result<u64, _>>().map(Some)
+            }
+            InputType::OpenFile(ref f) => f
+                .metadata()
+                .map(|meta| Some(meta.len()))
+                .map_err(AccessError::read),
+            InputType::Env(ref name) => std::env::var(name)
+                .map(|value| Some(value.len() as u64))
+                .map_err(|e| AccessError::env(e, name)),
+            InputType::Base64(ref b) | InputType::Bytes(ref b) => Ok(Some(b.len() as u64)),
+            InputType::UTF8(ref text) => Ok(Some(text.len() as u64)),
+            InputType::Stdin(_)
+            | InputType::Url(_)
+            | InputType::LazyText(_)
+            | InputType::Reader(_) => Ok(None),
+            #[cfg(feature = "tcp")]
+            InputType::Tcp(_) => Ok(None),
+            #[cfg(feature = "clipboard")]
+            InputType::Clipboard => Ok(None),
+        }
+    }
+
+    /// Estimate whether reading from this input would block waiting for data, e.g when stdin is
+    /// an interactive terminal the user hasn't typed anything into yet. This lets interactive
+    /// programs decide whether to show a prompt before reading.
+    ///
+    /// Only stdin which is a terminal is ever considered blocking, and only if it hasn't already
+    /// been [buffered][Builder::buffer_stdin]. Files, text and piped or redirected stdin are
+    /// never considered blocking, even if the writer of a pipe hasn't produced anything yet. On
+    /// unix, the terminal is polled without waiting. Elsewhere, this only checks whether stdin
+    /// is a terminal.
+    ///
+    /// This is a best effort estimate, data may arrive or run out between this check and the
+    /// read. Only the terminal itself is checked, so any part of an earlier line which
+    /// [io::Stdin] has already buffered isn't seen, and a terminal with no new data is
+    /// considered blocking even if a read would return those buffered bytes.
+    pub fn would_block(&self) -> bool {
+        self.would_block_from(&ProcessStdin)
+    }
+
+    fn would_block_from(&self, stdin: &dyn StdinSource) -> bool {
+        if !self.is_stdin() || self.stdin_buffer.get().is_some() {
+            return false;
+        }
+
+        stdin.would_block()
+    }
+
     /// Attempt to access the input source. Note that this function may block, depending on the
     /// what underlying input source is.
+    ///
+    /// Every stdin input reads from the process's one buffered stdin, which is locked for each
+    /// read, so concurrent reads never see the same bytes twice, though they may interleave.
+    /// As stdin is a stream, its data is only seen once: a later access continues from wherever
+    /// earlier readers stopped, unless stdin is [buffered][Builder::buffer_stdin].
     pub fn access(&self) -> Result<InputReader, AccessError> {
-        Read::try_from(&self.kind).map(InputReader::new)
+        self.access_from(&ProcessStdin)
     }
 
-    pub(crate) fn from_input_type(i: InputType) -> Self {
-        Self { kind: i }
+    /// Like [access](Input::access), but stdin inputs read from the given stdin
+    pub(crate) fn access_from(&self, stdin: &dyn StdinSource) -> Result<InputReader, AccessError> {
+        let source = match self.options.decoder {
+            Some(ref decoder) => self
+                .source_from(stdin)
+                .map(|source| decoder.decode(source))?,
+            None => self.source_from(stdin)?,
+        };
+        let mut reader = InputReader::new(source);
+
+        if let InputType::File(ref f) = self.kind {
+            reader.utf16 = f.options.utf16;
+            reader.fallback_encoding = f.options.fallback_encoding;
+        }
+
+        if let Some((ref prefix, ref suffix)) = self.options.wrap {
+            reader.wrap = Some(Wrap::new(prefix, suffix));
+        }
+
+        reader.limit = self.options.max_bytes.map(Limit::new);
+        reader.eof_on_broken_pipe = self.options.eof_on_broken_pipe;
+        reader.strip_ansi = self.options.strip_ansi;
+
+        Ok(reader)
     }
-}
 
-impl FromStr for Input {
-    type Err = InputError;
+    /// Like [access](Input::access), but reads from the returned reader fail once more than
+    /// max_bytes have been read, as with [max_bytes][Builder::max_bytes]. If the input already
+    /// has a lower limit configured, that limit is kept.
+    ///
+    /// Errors from exceeding the limit can be converted with [AccessError::read] into an error
+    /// of [Kind::LimitExceeded][crate::error::access::Kind::LimitExceeded].
+    pub fn access_with_limit(&self, max_bytes: u64) -> Result<InputReader, AccessError> {
+        let mut reader = self.access()?;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Self::with_defaults(s)
+        let max = match reader.limit {
+            Some(ref limit) => limit.max.min(max_bytes),
+            None => max_bytes,
+        };
+        reader.limit = Some(Limit::new(max));
+
+        Ok(reader)
     }
-}
 
-/// An opaque handle that implements std::io::Read
-#[derive(Debug)]
-pub struct InputReader {
-    input: Read,
-}
+    /// Like [access](Input::access), but returns an enum of the concrete reader for the input's
+    /// source, allowing tight read loops to match on the variant rather than going through
+    /// [InputReader].
+    ///
+    /// Note that this is the raw source, any [utf16][crate::parsers::File::utf16] transcoding or
+    /// [wrap_content][Builder::wrap_content] settings are _not_ applied.
+    pub fn access_enum(&self) -> Result<RawReader, AccessError> {
+        self.source_from(&ProcessStdin).map(RawReader::from)
+    }
 
-impl InputReader {
-    fn new(input: Read) -> Self {
-        Self { input }
+    /// Access the input source, returning a reader which can also [seek][io::Seek], e.g for
+    /// sniffing the format of the input before rewinding. Files, and inputs held in memory like
+    /// text, support this. Archive members and decompressed files don't, as they are
+    /// decompressed as they are read.
+    ///
+    /// Inputs which can only be read from start to end, i.e stdin, URLs, several files read
+    /// one after another and readers from [from_reader](Input::from_reader), fail with
+    /// [Kind::NotSeekable][crate::error::access::Kind::NotSeekable] without being accessed.
+    ///
+    /// Like [access_enum](Input::access_enum), this is the raw source, so settings which
+    /// transform the content as it is read are _not_ applied.
+    pub fn access_seek(&self) -> Result<impl io::Read + io::Seek, AccessError> {
+        match self.kind() {
+            kind @ (InputKind::Stdin | InputKind::Url | InputKind::Files | InputKind::Reader) => {
+                Err(AccessError::not_seekable(kind))
+            }
+            #[cfg(feature = "tcp")]
+            kind @ InputKind::Tcp => Err(AccessError::not_seekable(kind)),
+            _ => match Read::try_from(&self.kind)? {
+                Read::File(file) => Ok(SeekReader::File(file)),
+                Read::Text(cursor) => Ok(SeekReader::Text(cursor)),
+                Read::Bytes(cursor) => Ok(SeekReader::Bytes(cursor)),
+                #[cfg(feature = "mmap")]
+                Read::Mapped(cursor) => Ok(SeekReader::Mapped(cursor)),
+                _ => Err(AccessError::not_seekable(self.kind())),
+            },
+        }
     }
 
-    /// Convenience function for reading all the available input into a String. This function
-    /// internally contains similar semantics to [read_to_string][io::Read::read_to_string],
-    /// notably it will not consume the buffer in the case of a UTF8 error.
-    pub fn read_to_string(&mut self) -> Result<String, io::Error> {
-        let mut buf = String::new();
+    /// Access the input source, returning a lazy iterator over its records, as delimited by the
+    /// given separator. This generalizes line reading to arbitrary delimiters, for example
+    /// `b'\0'` for the output of `find -print0`.
+    ///
+    /// The separator is not included in the returned records, and a trailing separator does not
+    /// produce an empty final record.
+    pub fn access_records(
+        &self,
+        sep: u8,
+    ) -> Result<impl Iterator<Item = io::Result<Vec<u8>>>, AccessError> {
+        self.access()
+            .map(|reader| io::BufRead::split(io::BufReader::new(reader), sep))
+    }
+
+    /// Access the input source, returning a lazy iterator over its lines. This is shorthand for
+    /// [access](Input::access) followed by [InputReader::lines].
+    pub fn lines(&self) -> Result<impl Iterator<Item = io::Result<String>>, AccessError> {
+        self.access().map(InputReader::lines)
+    }
+
+    /// Access the input source, returning a lazy iterator over only the lines for which the
+    /// given predicate returns true, like a `grep` at the source. Lines are split as
+    /// [InputReader::lines] splits them, and any error while reading is always returned.
+    pub fn access_filtered<F>(
+        &self,
+        mut pred: F,
+    ) -> Result<impl Iterator<Item = io::Result<String>>, AccessError>
+    where
+        F: FnMut(&str) -> bool,
+    {
+        self.lines()
+            .map(|lines| lines.filter(move |line| line.as_ref().map_or(true, |line| pred(line))))
+    }
+
+    /// Access the input source, returning an iterator over its lines from last to first, like
+    /// `tac`. Lines are split as [InputReader::lines] splits them, so a trailing newline does not
+    /// produce an empty final line.
+    ///
+    /// Files are scanned backwards from their end a block at a time, so only the lines not yet
+    /// returned are read. Every other input, including files which are transcoded, decompressed
+    /// or [wrapped][Builder::wrap_content], is read fully into memory first. Stdin can't be
+    /// read in reverse and fails with [Kind::Stdin][crate::error::access::Kind::Stdin].
+    pub fn access_lines_rev(
+        &self,
+    ) -> Result<impl Iterator<Item = io::Result<String>>, AccessError> {
+        if self.is_stdin() {
+            return Err(AccessError::stdin_not_seekable());
+        }
+
+        let plain = self.options.wrap.is_none()
+            && self.options.max_bytes.is_none()
+            && !self.options.strip_ansi
+            && self.options.decoder.is_none();
+
+        match (Read::try_from(&self.kind)?, &self.kind) {
+            (Read::File(file), InputType::File(f))
+                if plain && f.options.utf16.is_none() && f.options.fallback_encoding.is_none() =>
+            {
+                RevLines::file(file).map_err(AccessError::read)
+            }
+            _ => self.read_to_string().map(RevLines::text),
+        }
+    }
+
+    /// Check that the input source isn't an empty file, without reading from it. Several files
+    /// are each checked in turn, as are files [opened by the caller](Input::from_open_file).
+    /// Members of an archive always pass this check, as their size isn't known until the
+    /// archive is read, as do inputs that aren't files.
+    ///
+    /// Fails with [Kind::EmptyFile][crate::error::access::Kind::EmptyFile] for zero length files,
+    /// or [Kind::File][crate::error::access::Kind::File] if a file's metadata is inaccessible
+    /// ([Kind::Read][crate::error::access::Kind::Read] for files opened by the caller).
+    pub fn validate_nonempty(&self) -> Result<(), AccessError> {
+        let is_empty = |meta: std::fs::Metadata| meta.is_file() && meta.len() == 0;
+        let check_path = |f: &FilePath| {
+            let path = f.path.as_path();
+            let meta =
+                std::fs::metadata(path).map_err(|e| AccessError::file_with_context(e, path))?;
+
+            match is_empty(meta) {
+                true => Err(AccessError::empty_file(path)),
+                false => Ok(()),
+            }
+        };
+
+        match self.kind {
+            #[cfg(feature = "zip")]
+            InputType::File(ref f) if f.member.is_some() => Ok(()),
+            InputType::File(ref f) => check_path(f),
+            InputType::Files(ref files) => files.iter().try_for_each(check_path),
+            InputType::OpenFile(ref f) => match f.metadata().map(is_empty) {
+                Ok(true) => Err(AccessError::empty_open_file()),
+                Ok(false) => Ok(()),
+                Err(e) => Err(AccessError::read(e)),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Convenience function for accessing the input source and reading it to a String in one
+    /// go. Any error while reading is reported as an [AccessError] of
+    /// [Kind::Read][crate::error::access::Kind::Read].
+    pub fn read_to_string(&self) -> Result<String, AccessError> {
+        self.access()?.read_to_string().map_err(AccessError::read)
+    }
+
+    /// Convenience function for accessing the input source and reading all of its bytes. Unlike
+    /// [read_to_string](Input::read_to_string) the content does not need to be valid UTF-8, and
+    /// text inputs are returned as their UTF-8 encoding.
+    pub fn read_to_bytes(&self) -> Result<Vec<u8>, AccessError> {
+        let mut buf = Vec::new();
 
-        io::Read::read_to_string(&mut self.input, &mut buf)?;
+        io::Read::read_to_end(&mut self.access()?, &mut buf).map_err(AccessError::read)?;
 
         Ok(buf)
     }
+
+    pub(crate) fn with_options(i: InputType, options: ReadOptions) -> Self {
+        Self {
+            kind: i,
+            options,
+            stdin_buffer: OnceLock::new(),
+            spec: None,
+        }
+    }
+
+    /// Record the argument this input was parsed from
+    pub(crate) fn with_spec(self, spec: impl Into<Box<str>>) -> Self {
+        Self {
+            spec: Some(spec.into()),
+            ..self
+        }
+    }
+
+    /// Consume this input, returning the source it was resolved to
+    pub(crate) fn into_kind(self) -> InputType {
+        self.kind
+    }
+
+    /// Open the underlying source of this input, reading stdin inputs from the given stdin
+    fn source_from(&self, stdin: &dyn StdinSource) -> Result<Read, AccessError> {
+        match self.kind {
+            InputType::Stdin(ref options) => {
+                options.apply()?;
+
+                let stdin = match self.options.buffer_stdin {
+                    true => self.buffered_stdin(stdin)?,
+                    false => self.guarded_stdin(stdin)?,
+                };
+
+                let stdin = match options.require_nonempty {
+                    true => Read::require_nonempty(stdin)?,
+                    false => stdin,
+                };
+
+                #[cfg(feature = "gzip")]
+                if options.auto_decompress {
+                    return Read::sniff_gzip(stdin).map_err(AccessError::read);
+                }
+
+                Ok(stdin)
+            }
+            ref kind => Read::try_from(kind),
+        }
+    }
+
+    /// Open stdin, failing if it was already accessed and [guard_stdin][Builder::guard_stdin]
+    /// is set
+    fn guarded_stdin(&self, stdin: &dyn StdinSource) -> Result<Read, AccessError> {
+        let accessed = stdin.mark_accessed();
+
+        match self.options.guard_stdin && accessed {
+            true => Err(AccessError::stdin_already_consumed()),
+            false => Ok(stdin.open()),
+        }
+    }
+
+    /// Read stdin into memory on first use, replaying the buffered bytes afterwards
+    fn buffered_stdin(&self, stdin: &dyn StdinSource) -> Result<Read, AccessError> {
+        if let Some(buf) = self.stdin_buffer.get() {
+            return Ok(Read::bytes(buf.clone()));
+        }
+
+        let mut buf = Vec::new();
+        let mut stdin = InputReader::new(self.guarded_stdin(stdin)?);
+        stdin.limit = self.options.max_bytes.map(Limit::new);
+        stdin.eof_on_broken_pipe = self.options.eof_on_broken_pipe;
+
+        io::Read::read_to_end(&mut stdin, &mut buf).map_err(AccessError::read)?;
+
+        Ok(Read::bytes(self.stdin_buffer.get_or_init(|| buf).clone()))
+    }
 }
 
-impl io::Read for InputReader {
-    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        io::Read::read(&mut self.input, buf)
+impl Input {
+    /// The position of this input's kind in the order described on [Ord for Input](#impl-Ord-for-Input)
+    fn rank(&self) -> u8 {
+        match self.kind {
+            InputType::File(_) => 0,
+            InputType::OpenFile(_) => 1,
+            InputType::Files(_) => 2,
+            InputType::Url(_) => 3,
+            #[cfg(feature = "tcp")]
+            InputType::Tcp(_) => 4,
+            InputType::Env(_) => 5,
+            #[cfg(feature = "clipboard")]
+            InputType::Clipboard => 6,
+            InputType::Base64(_) => 7,
+            InputType::Bytes(_) => 8,
+            InputType::UTF8(_) => 9,
+            InputType::LazyText(_) => 10,
+            InputType::Reader(_) => 11,
+            InputType::Stdin(_) => 12,
+        }
     }
 }
 
-enum Read {
-    File(std::fs::File),
-    Stdin(std::io::Stdin),
-    Text(io::Cursor<String>),
+/// A cheap descriptor of the source an [Input] was resolved to, see [Input::kind]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum InputKind {
+    /// The process's stdin
+    Stdin,
+    /// A single file
+    File,
+    /// Several files, read one after another
+    Files,
+    /// Text given directly as input, or produced by a closure
+    Text,
+    /// A URL, fetched when accessed
+    Url,
+    /// An environment variable, read when accessed
+    Env,
+    /// Base64 encoded data given directly as input
+    Base64,
+    /// Binary data, see [Input::from_bytes]
+    Bytes,
+    /// A reader given by the caller, see [Input::from_reader]
+    Reader,
+    /// A TCP server, connected to when accessed
+    #[cfg(feature = "tcp")]
+    Tcp,
+    /// The system clipboard, read when accessed
+    #[cfg(feature = "clipboard")]
+    Clipboard,
 }
 
-impl Read {
-    fn stdin() -> Self {
-        Self::Stdin(io::stdin())
-    }
+/// Inputs are totally ordered by their kind, then by their content:
+///
+/// 1. Files, by path (then archive member, if any, then whether it is opened for appending)
+/// 2. Files [opened by the caller][Input::from_open_file], in an arbitrary but consistent order
+/// 3. Several files, e.g from a `Glob`, by their list of paths
+/// 4. URLs, by the URL
+/// 5. TCP servers, by their address
+/// 6. Environment variables, by the variable's name
+/// 7. The clipboard
+/// 8. Base64 data, by the decoded bytes
+/// 9. Binary data, by the bytes themselves
+/// 10. Text, by the text itself
+/// 11. Text produced by a closure, in an arbitrary but consistent order
+/// 12. Readers [given by the caller][Input::from_reader], in an arbitrary but consistent order
+/// 13. Stdin, which is always last
+///
+/// Only the source is compared, two inputs with the same source but different read settings
+/// (e.g [wrap_content][Builder::wrap_content]) are considered equal.
+impl Ord for Input {
+    fn cmp(&self, other: &Self) -> Ordering {
+        use InputType::*;
 
-    fn file(f: std::fs::File) -> Self {
-        Self::File(f)
+        match (&self.kind, &other.kind) {
+            (File(a), File(b)) => file_key(a).cmp(&file_key(b)),
+            (Files(a), Files(b)) => a.iter().map(file_key).cmp(b.iter().map(file_key)),
+            (Url(a), Url(b)) | (Env(a), Env(b)) | (UTF8(a), UTF8(b)) => a.cmp(b),
+            (Base64(a), Base64(b)) | (Bytes(a), Bytes(b)) => a.cmp(b),
+            (LazyText(a), LazyText(b)) => a.addr().cmp(&b.addr()),
+            (OpenFile(a), OpenFile(b)) => a.addr().cmp(&b.addr()),
+            (Reader(a), Reader(b)) => a.addr().cmp(&b.addr()),
+            #[cfg(feature = "tcp")]
+            (Tcp(a), Tcp(b)) => a.cmp(b),
+            _ => self.rank().cmp(&other.rank()),
+        }
     }
+}
 
-    fn text(s: impl AsRef<str>) -> Self {
-        let s = s.as_ref().to_string();
+impl PartialOrd for Input {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-        Self::Text(io::Cursor::new(s))
+impl PartialEq for Input {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
     }
 }
 
-impl TryFrom<&InputType> for Read {
-    type Error = AccessError;
+impl Eq for Input {}
 
-    fn try_from(kind: &InputType) -> Result<Self, Self::Error> {
-        match kind {
-            InputType::Stdin => Ok(Read::stdin()),
-            InputType::File(ref f) => std::fs::File::open(f.path.as_path())
-                .map(Read::file)
-                .map_err(|e| AccessError::file_with_context(e, f.path.as_path())),
-            InputType::UTF8(ref s) => Ok(Self::text(s)),
+fn file_key(f: &FilePath) -> (&std::path::Path, Option<&str>, bool) {
+    #[cfg(feature = "zip")]
+    let member = f.member.as_deref();
+    #[cfg(not(feature = "zip"))]
+    let member = None;
+
+    (f.path.as_path(), member, f.options.append.is_some())
+}
+
+/// Inputs are displayed in a canonical form, using the default marker of the parser which
+/// produces each kind of input: `-` for stdin, `@path` for files, `env:NAME` for environment
+/// variables and so on. [FromStr] reads the displayed form of every input the
+/// [default][Config::default] config produces back as an equal input:
+///
+/// - Text which the default config would parse as something else, like `@notafile` or `-`, is
+///   prefixed with `\`, which FromStr reads as an [escape][Builder::escape]
+/// - On unix, paths which aren't valid UTF-8 are displayed as percent-encoded `file:` URIs,
+///   which FromStr also accepts. A relative path is made absolute first, so it reads back as
+///   the same file, but by its absolute path rather than as an equal input.
+///
+/// Other kinds of input read back with a [Config] which enables their parser and uses `\` as
+/// its escape. Archive members are displayed as `@archive.zip!member`, which reads back with
+/// an `archive_separator` of `!`, and files opened for appending are displayed with the
+/// [append_marker][crate::parsers::File::append_marker] they were given with, e.g `+out.log`,
+/// which reads back with the same append marker.
+///
+/// Some inputs can't be parsed back as the same input at all:
+///
+/// - Several files are displayed as each file separated by a space, e.g `@a @b`
+/// - Binary data is displayed as base64, which reads back as a [Base64][InputKind::Base64]
+///   input of the same bytes
+/// - Text produced by a closure is displayed as the argument it was parsed from, if any, or
+///   `<lazy text>`, as displaying it never calls the closure
+/// - Files opened by the caller, which have no path, are displayed as `<open file>`, and
+///   readers given by the caller as `<reader>`
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::parsers::{encode_base64, Base64, Env, Stdin};
+
+        match self.kind {
+            InputType::Stdin(_) => f.write_str(Stdin::DEFAULT_MARKER),
+            InputType::File(ref file) => write_file(f, file),
+            InputType::Files(ref files) => {
+                for (i, file) in files.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(" ")?;
+                    }
+                    write_file(f, file)?;
+                }
+
+                Ok(())
+            }
+            InputType::Url(ref url) => f.write_str(url),
+            InputType::Env(ref name) => write!(f, "{}{}", Env::DEFAULT_MARKER, name),
+            InputType::Base64(ref b) | InputType::Bytes(ref b) => {
+                write!(f, "{}{}", Base64::DEFAULT_MARKER, encode_base64(b))
+            }
+            InputType::UTF8(ref text) => write_text(f, text),
+            InputType::LazyText(_) => f.write_str(self.spec().unwrap_or("<lazy text>")),
+            InputType::OpenFile(_) => f.write_str("<open file>"),
+            InputType::Reader(_) => f.write_str("<reader>"),
+            #[cfg(feature = "tcp")]
+            InputType::Tcp(ref addr) => {
+                write!(f, "{}{}", crate::parsers::Tcp::DEFAULT_MARKER, addr)
+            }
+            #[cfg(feature = "clipboard")]
+            InputType::Clipboard => f.write_str(crate::parsers::Clipboard::DEFAULT_MARKER),
         }
     }
 }
 
-impl io::Read for Read {
-    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        use Read::*;
-        match self {
-            File(ref mut file) => io::Read::read(file, buf),
-            Stdin(ref mut stdin) => io::Read::read(stdin, buf),
-            Text(ref mut cursor) => io::Read::read(cursor, buf),
+fn write_file(f: &mut fmt::Formatter<'_>, file: &FilePath) -> fmt::Result {
+    let marker = file
+        .options
+        .append
+        .as_deref()
+        .unwrap_or(crate::parsers::File::DEFAULT_MARKER);
+
+    #[cfg(unix)]
+    if file.path.to_str().is_none() && file.options.append.is_none() {
+        if let Some(uri) = std::path::absolute(&file.path)
+            .ok()
+            .and_then(|path| crate::parsers::file_uri(&path))
+        {
+            return f.write_str(&uri);
         }
     }
+
+    write!(f, "{}{}", marker, file.path.display())?;
+
+    #[cfg(feature = "zip")]
+    if let Some(ref member) = file.member {
+        write!(f, "!{}", member)?;
+    }
+
+    Ok(())
 }
 
-impl fmt::Debug for Read {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use Read::*;
-        let mut dbg = f.debug_struct("Read");
+/// Write text, escaped if the [canonical][Config::canonical] config would parse it as something
+/// else
+fn write_text(f: &mut fmt::Formatter<'_>, text: &str) -> fmt::Result {
+    match Config::canonical().escape_text(text) {
+        Some(escaped) => f.write_str(&escaped),
+        None => f.write_str(text),
+    }
+}
 
-        match self {
-            File(f) => dbg.field("file", &f),
-            Stdin(s) => dbg.field("stdin", &s),
-            Text(t) => dbg.field("cursor", &t),
-        };
+/// Inputs are parsed with the [default][Config::default] config, which additionally reads a
+/// leading `\` as an [escape][Builder::escape] and accepts `file:` URIs, so the
+/// [Display][fmt::Display] form of any input the default config produces reads back as an equal
+/// input.
+impl FromStr for Input {
+    type Err = InputError;
 
-        dbg.finish()
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Config::canonical().parse(s)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Seam for where stdin inputs read from, so tests can replace the process's stdin with fixed
+/// data
+pub(crate) trait StdinSource {
+    /// Open a reader over stdin
+    fn open(&self) -> Read;
 
-    #[test]
-    fn input_from_default() {
-        let input = "@/some/file/path";
-        let res = Input::with_defaults(input);
+    /// Record that stdin has been accessed, returning whether it already had been
+    fn mark_accessed(&self) -> bool;
 
-        assert!(res.is_ok())
+    /// Whether reading from stdin would block waiting for data, see [Input::would_block]
+    fn would_block(&self) -> bool;
+}
+
+/// The process's real stdin
+pub(crate) struct ProcessStdin;
+
+impl StdinSource for ProcessStdin {
+    fn open(&self) -> Read {
+        Read::stdin()
     }
 
-    #[test]
-    fn input_from_str() {
-        let input = "some text";
-        let res = Input::from_str(input);
+    fn mark_accessed(&self) -> bool {
+        stdin_guard::mark_accessed()
+    }
 
-        assert!(res.is_ok())
+    fn would_block(&self) -> bool {
+        stdin_would_block()
     }
+}
 
-    #[test]
-    fn input_reader() {
-        let input = "some random text";
-        let i = Input::with_defaults(input).unwrap();
+#[cfg(unix)]
+fn stdin_would_block() -> bool {
+    if !io::IsTerminal::is_terminal(&io::stdin()) {
+        return false;
+    }
 
-        let output = i.access().unwrap().read_to_string().unwrap();
+    let mut fd = libc::pollfd {
+        fd: libc::STDIN_FILENO,
+        events: libc::POLLIN,
+        revents: 0,
+    };
 
-        assert_eq!(input, output.as_str())
+    // SAFETY: fd points to exactly one valid pollfd, and a zero timeout never waits. A non zero
+    // result means stdin is readable, closed or errored, and a read returns immediately
+    unsafe { libc::poll(&mut fd, 1, 0) == 0 }
+}
+
+/// Tracks whether any input has accessed stdin, see [Builder::guard_stdin]
+mod stdin_guard {
+    static ACCESSED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+    /// Record that stdin has been accessed, returning whether it already had been
+    pub fn mark_accessed() -> bool {
+        ACCESSED.swap(true, std::sync::atomic::Ordering::AcqRel)
+    }
+}
+
+#[cfg(not(unix))]
+fn stdin_would_block() -> bool {
+    io::IsTerminal::is_terminal(&io::stdin())
+}
+
+/// Settings configured on a [Builder] which are applied when an [Input] is read from
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ReadOptions {
+    pub wrap: Option<(String, String)>,
+    pub buffer_stdin: bool,
+    pub max_bytes: Option<u64>,
+    pub eof_on_broken_pipe: bool,
+    pub strip_ansi: bool,
+    pub decoder: Option<Decoder>,
+    pub guard_stdin: bool,
+}
+
+/// A closure every opened source is passed through, see [Builder::with_decoder]
+#[derive(Clone)]
+pub(crate) struct Decoder(Arc<DecoderFn>);
+
+pub(crate) type DecoderFn =
+    dyn Fn(Box<dyn io::Read + Send>) -> Box<dyn io::Read + Send> + Send + Sync;
+
+impl Decoder {
+    pub fn new(f: Arc<DecoderFn>) -> Self {
+        Self(f)
+    }
+
+    fn decode(&self, source: Read) -> Read {
+        Read::Decoded((self.0)(Box::new(source)))
+    }
+}
+
+impl fmt::Debug for Decoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Decoder")
+    }
+}
+
+/// An opaque handle that implements std::io::Read
+#[derive(Debug)]
+pub struct InputReader {
+    input: Read,
+    utf16: Option<Endianness>,
+    fallback_encoding: Option<&'static Encoding>,
+    wrap: Option<Wrap>,
+    limit: Option<Limit>,
+    eof_on_broken_pipe: bool,
+    strip_ansi: bool,
+}
+
+impl InputReader {
+    fn new(input: Read) -> Self {
+        Self {
+            input,
+            utf16: None,
+            fallback_encoding: None,
+            wrap: None,
+            limit: None,
+            eof_on_broken_pipe: false,
+            strip_ansi: false,
+        }
+    }
+
+    /// Split this reader into its source, respecting any limit, and its wrap
+    fn parts(&mut self) -> (Source<'_>, Option<&mut Wrap>) {
+        let source = Source {
+            input: &mut self.input,
+            limit: self.limit.as_mut(),
+            eof_on_broken_pipe: self.eof_on_broken_pipe,
+        };
+
+        (source, self.wrap.as_mut())
+    }
+
+    /// Convenience function for reading all the available input into a String. This function
+    /// internally contains similar semantics to [read_to_string][io::Read::read_to_string],
+    /// notably it will not consume the buffer in the case of a UTF8 error.
+    ///
+    /// If the input is a file configured with [utf16][crate::parsers::File::utf16], its contents
+    /// are transcoded from UTF-16 instead, and if it is configured with a
+    /// [fallback_encoding][crate::parsers::File::fallback_encoding], contents which aren't valid
+    /// UTF-8 are decoded with that encoding. Any ANSI escape sequences are removed afterwards if
+    /// [strip_ansi][Builder::strip_ansi] is set.
+    pub fn read_to_string(&mut self) -> Result<String, io::Error> {
+        let text = self.read_text()?;
+
+        match self.strip_ansi {
+            true => Ok(crate::ansi::strip(&text)),
+            false => Ok(text),
+        }
+    }
+
+    fn read_text(&mut self) -> Result<String, io::Error> {
+        if let Some(endianness) = self.utf16 {
+            let mut buf = Vec::new();
+
+            let (mut source, wrap) = self.parts();
+
+            io::Read::read_to_end(&mut source, &mut buf)?;
+            let text = decode_utf16(&buf, endianness)?;
+
+            return match wrap {
+                Some(wrap) => wrap.wrap_string(&text),
+                None => Ok(text),
+            };
+        }
+
+        if let Some(encoding) = self.fallback_encoding {
+            let mut buf = Vec::new();
+
+            let (mut source, wrap) = self.parts();
+
+            io::Read::read_to_end(&mut source, &mut buf)?;
+            let text = String::from_utf8(buf).unwrap_or_else(|e| {
+                encoding
+                    .decode_without_bom_handling(e.as_bytes())
+                    .0
+                    .into_owned()
+            });
+
+            return match wrap {
+                Some(wrap) => wrap.wrap_string(&text),
+                None => Ok(text),
+            };
+        }
+
+        let mut buf = String::new();
+
+        io::Read::read_to_string(self, &mut buf)?;
+
+        Ok(buf)
+    }
+}
+
+impl InputReader {
+    /// Consume this reader, returning an iterator over the lines of the input. This behaves the
+    /// same as [BufRead::lines][io::BufRead::lines], buffering the reader internally.
+    pub fn lines(self) -> io::Lines<io::BufReader<Self>> {
+        io::BufRead::lines(io::BufReader::new(self))
+    }
+}
+
+impl io::Read for InputReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.parts() {
+            (mut source, Some(wrap)) => wrap.read(&mut source, buf),
+            (mut source, None) => io::Read::read(&mut source, buf),
+        }
+    }
+
+    #[cfg(grab_nightly)]
+    fn read_buf(&mut self, buf: io::BorrowedCursor<'_>) -> io::Result<()> {
+        match self.parts() {
+            (mut source, Some(wrap)) => read_buf_with(buf, |bytes| wrap.read(&mut source, bytes)),
+            (mut source, None) => io::Read::read_buf(&mut source, buf),
+        }
+    }
+}
+
+/// Read into the given cursor through an initialized buffer, for readers that can't read into
+/// uninitialized memory directly
+#[cfg(grab_nightly)]
+fn read_buf_with<F>(mut buf: io::BorrowedCursor<'_>, read: F) -> io::Result<()>
+where
+    F: FnOnce(&mut [u8]) -> io::Result<usize>,
+{
+    let mut bytes = [0; 8 * 1024];
+    let len = bytes.len().min(buf.capacity());
+
+    let n = read(&mut bytes[..len])?;
+    buf.append(&bytes[..n]);
+
+    Ok(())
+}
+
+/// The source of an [InputReader], with any limit applied
+struct Source<'a> {
+    input: &'a mut Read,
+    limit: Option<&'a mut Limit>,
+    eof_on_broken_pipe: bool,
+}
+
+impl io::Read for Source<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let result = match self.limit {
+            Some(ref mut limit) => limit.read(self.input, buf),
+            None => io::Read::read(self.input, buf),
+        };
+
+        match result {
+            Err(e) if self.eof_on_broken_pipe && e.kind() == io::ErrorKind::BrokenPipe => Ok(0),
+            result => result,
+        }
+    }
+
+    #[cfg(grab_nightly)]
+    fn read_buf(&mut self, buf: io::BorrowedCursor<'_>) -> io::Result<()> {
+        match (&self.limit, self.eof_on_broken_pipe) {
+            (None, false) => io::Read::read_buf(self.input, buf),
+            _ => read_buf_with(buf, |bytes| io::Read::read(self, bytes)),
+        }
+    }
+}
+
+/// Fails reads from some reader once more than a maximum number of bytes have been read. Unlike
+/// [io::Take], exceeding the limit is an error rather than an early EOF.
+#[derive(Debug)]
+struct Limit {
+    max: u64,
+    remaining: u64,
+}
+
+impl Limit {
+    fn new(max: u64) -> Self {
+        Self {
+            max,
+            remaining: max,
+        }
+    }
+
+    fn read<R>(&mut self, inner: &mut R, buf: &mut [u8]) -> io::Result<usize>
+    where
+        R: io::Read + ?Sized,
+    {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        // Check whether there is anything past the limit
+        if self.remaining == 0 {
+            return match inner.read(&mut [0u8])? {
+                0 => Ok(0),
+                _ => Err(LimitExceeded::new(self.max).into()),
+            };
+        }
+
+        let len = buf
+            .len()
+            .min(usize::try_from(self.remaining).unwrap_or(usize::MAX));
+        let n = inner.read(&mut buf[..len])?;
+        self.remaining -= n as u64;
+
+        Ok(n)
+    }
+}
+
+/// Surrounds the content read from some reader with a prefix and suffix
+#[derive(Debug)]
+struct Wrap {
+    prefix: io::Cursor<String>,
+    suffix: io::Cursor<String>,
+    body_done: bool,
+}
+
+impl Wrap {
+    fn new(prefix: &str, suffix: &str) -> Self {
+        Self {
+            prefix: io::Cursor::new(prefix.to_string()),
+            suffix: io::Cursor::new(suffix.to_string()),
+            body_done: false,
+        }
+    }
+
+    fn read<R>(&mut self, body: &mut R, buf: &mut [u8]) -> io::Result<usize>
+    where
+        R: io::Read,
+    {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        match io::Read::read(&mut self.prefix, buf)? {
+            0 => {}
+            n => return Ok(n),
+        }
+
+        if !self.body_done {
+            match io::Read::read(body, buf)? {
+                0 => self.body_done = true,
+                n => return Ok(n),
+            }
+        }
+
+        io::Read::read(&mut self.suffix, buf)
+    }
+
+    /// Wrap an already read body, consuming the remaining prefix and suffix
+    fn wrap_string(&mut self, body: &str) -> io::Result<String> {
+        let mut buf = String::new();
+
+        io::Read::read_to_string(&mut self.prefix, &mut buf)?;
+        buf.push_str(body);
+        io::Read::read_to_string(&mut self.suffix, &mut buf)?;
+
+        Ok(buf)
+    }
+}
+
+/// Decodes the given bytes as UTF-16, stripping the BOM if present. An explicit endianness
+/// takes precedence over the BOM.
+fn decode_utf16(bytes: &[u8], endianness: Endianness) -> io::Result<String> {
+    const LE_BOM: [u8; 2] = [0xFF, 0xFE];
+    const BE_BOM: [u8; 2] = [0xFE, 0xFF];
+
+    let (little, bytes) = match (endianness, bytes.get(..2)) {
+        (Endianness::Little, Some(bom)) if bom == LE_BOM => (true, &bytes[2..]),
+        (Endianness::Big, Some(bom)) if bom == BE_BOM => (false, &bytes[2..]),
+        (Endianness::Auto, Some(bom)) if bom == LE_BOM => (true, &bytes[2..]),
+        (Endianness::Auto, Some(bom)) if bom == BE_BOM => (false, &bytes[2..]),
+        (Endianness::Big, _) => (false, bytes),
+        (Endianness::Little, _) | (Endianness::Auto, _) => (true, bytes),
+    };
+
+    if bytes.len() % 2 != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "UTF-16 input has an odd number of bytes",
+        ));
+    }
+
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| {
+            if little {
+                u16::from_le_bytes([pair[0], pair[1]])
+            } else {
+                u16::from_be_bytes([pair[0], pair[1]])
+            }
+        })
+        .collect();
+
+    String::from_utf16(&units).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// The concrete reader of an input's source, as returned by [Input::access_enum]. Every variant
+/// implements [io::Read], as does this enum.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RawReader {
+    /// A file opened for reading
+    File(std::fs::File),
+    /// Several files, read one after another
+    Files(FileChain),
+    /// The process's stdin
+    Stdin(io::Stdin),
+    /// Text given directly as input
+    Text(io::Cursor<String>),
+    /// Data already held in memory, e.g buffered stdin
+    Bytes(io::Cursor<Vec<u8>>),
+    /// A reader given to [Input::from_reader]
+    Reader(ReaderHandle),
+    /// A file memory mapped by [mmap][crate::parsers::File::mmap]
+    #[cfg(feature = "mmap")]
+    Mapped(MappedFile),
+    /// The body of a fetched URL
+    #[cfg(feature = "http")]
+    Url(UrlBody),
+    /// A connection to a TCP server
+    #[cfg(feature = "tcp")]
+    Tcp(std::net::TcpStream),
+    /// Stdin, after checking it for gzip data with `auto_decompress`, or for emptiness with
+    /// [require_nonempty][crate::parsers::Stdin::require_nonempty]
+    Peeked(PeekedStdin),
+    /// An archive member, compressed file or compressed stdin, decompressed as it is read
+    #[cfg(any(feature = "zip", feature = "gzip", feature = "zstd"))]
+    Decompressed(Decompressed),
+}
+
+impl io::Read for RawReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        use RawReader::*;
+        match self {
+            File(ref mut file) => io::Read::read(file, buf),
+            Files(ref mut files) => io::Read::read(files, buf),
+            Stdin(ref mut stdin) => io::Read::read(stdin, buf),
+            Text(ref mut cursor) => io::Read::read(cursor, buf),
+            Bytes(ref mut cursor) => io::Read::read(cursor, buf),
+            Reader(ref mut reader) => io::Read::read(reader, buf),
+            #[cfg(feature = "mmap")]
+            Mapped(ref mut map) => io::Read::read(map, buf),
+            #[cfg(feature = "http")]
+            Url(ref mut body) => io::Read::read(body, buf),
+            #[cfg(feature = "tcp")]
+            Tcp(ref mut stream) => io::Read::read(stream, buf),
+            Peeked(ref mut stdin) => io::Read::read(stdin, buf),
+            #[cfg(any(feature = "zip", feature = "gzip", feature = "zstd"))]
+            Decompressed(ref mut reader) => io::Read::read(reader, buf),
+        }
+    }
+}
+
+impl From<Read> for RawReader {
+    fn from(read: Read) -> Self {
+        match read {
+            Read::File(f) => Self::File(f),
+            Read::Files(f) => Self::Files(f),
+            Read::Stdin(s) => Self::Stdin(s),
+            Read::Text(t) => Self::Text(t),
+            Read::Bytes(b) => Self::Bytes(b),
+            Read::Reader(r) => Self::Reader(ReaderHandle(r)),
+            #[cfg(feature = "mmap")]
+            Read::Mapped(m) => Self::Mapped(MappedFile(m)),
+            #[cfg(feature = "http")]
+            Read::Url(body) => Self::Url(UrlBody(body)),
+            #[cfg(feature = "tcp")]
+            Read::Tcp(stream) => Self::Tcp(stream),
+            Read::Peeked(chain) => Self::Peeked(PeekedStdin(chain)),
+            Read::Decoded(reader) => Self::Reader(ReaderHandle(SharedReader::new(reader))),
+            #[cfg(any(feature = "zip", feature = "gzip", feature = "zstd"))]
+            Read::Decompressed(reader) => Self::Decompressed(Decompressed(reader)),
+        }
+    }
+}
+
+/// An opaque reader over a reader given to [Input::from_reader], which is shared with the input
+#[derive(Debug)]
+pub struct ReaderHandle(SharedReader);
+
+impl io::Read for ReaderHandle {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+/// An opaque reader over a memory mapped file, which is unmapped when dropped
+#[cfg(feature = "mmap")]
+#[derive(Debug)]
+pub struct MappedFile(io::Cursor<memmap2::Mmap>);
+
+#[cfg(feature = "mmap")]
+impl io::Read for MappedFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl io::Seek for MappedFile {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        self.0.seek(pos)
+    }
+}
+
+/// An opaque reader over the body of a fetched URL
+#[cfg(feature = "http")]
+#[derive(Debug)]
+pub struct UrlBody(crate::http::Body);
+
+#[cfg(feature = "http")]
+impl io::Read for UrlBody {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+/// An opaque reader which decompresses an archive member, compressed file or compressed stdin as
+/// it is read
+#[cfg(any(feature = "zip", feature = "gzip", feature = "zstd"))]
+pub struct Decompressed(Box<dyn io::Read + Send>);
+
+#[cfg(any(feature = "zip", feature = "gzip", feature = "zstd"))]
+impl io::Read for Decompressed {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+#[cfg(any(feature = "zip", feature = "gzip", feature = "zstd"))]
+impl fmt::Debug for Decompressed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Decompressed").finish_non_exhaustive()
+    }
+}
+
+/// An opaque reader over stdin, which replays the bytes read while checking for gzip data or
+/// emptiness before continuing with the rest of stdin
+#[derive(Debug)]
+pub struct PeekedStdin(Box<io::Chain<io::Cursor<Vec<u8>>, Read>>);
+
+impl io::Read for PeekedStdin {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+/// Reads several files one after another, opening each only once the previous is exhausted
+#[derive(Debug)]
+pub struct FileChain {
+    pending: std::collections::VecDeque<FilePath>,
+    current: Option<std::fs::File>,
+}
+
+impl FileChain {
+    fn new(files: &[FilePath]) -> Self {
+        Self {
+            pending: files.iter().cloned().collect(),
+            current: None,
+        }
+    }
+}
+
+impl io::Read for FileChain {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let file = match self.current {
+                Some(ref mut file) => file,
+                None => match self.pending.pop_front() {
+                    Some(next) => {
+                        let file = next.open().map_err(|e| {
+                            io::Error::new(
+                                e.kind(),
+                                format!("unable to open {}: {}", next.path.display(), e),
+                            )
+                        })?;
+
+                        self.current.insert(file)
+                    }
+                    None => return Ok(0),
+                },
+            };
+
+            match io::Read::read(file, buf)? {
+                0 if !buf.is_empty() => self.current = None,
+                n => return Ok(n),
+            }
+        }
+    }
+}
+
+/// The seekable sources of an input, see [Input::access_seek]
+enum SeekReader {
+    File(std::fs::File),
+    Text(io::Cursor<String>),
+    Bytes(io::Cursor<Vec<u8>>),
+    #[cfg(feature = "mmap")]
+    Mapped(io::Cursor<memmap2::Mmap>),
+}
+
+impl io::Read for SeekReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::File(ref mut file) => io::Read::read(file, buf),
+            Self::Text(ref mut cursor) => io::Read::read(cursor, buf),
+            Self::Bytes(ref mut cursor) => io::Read::read(cursor, buf),
+            #[cfg(feature = "mmap")]
+            Self::Mapped(ref mut cursor) => io::Read::read(cursor, buf),
+        }
+    }
+}
+
+impl io::Seek for SeekReader {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        match self {
+            Self::File(ref mut file) => io::Seek::seek(file, pos),
+            Self::Text(ref mut cursor) => io::Seek::seek(cursor, pos),
+            Self::Bytes(ref mut cursor) => io::Seek::seek(cursor, pos),
+            #[cfg(feature = "mmap")]
+            Self::Mapped(ref mut cursor) => io::Seek::seek(cursor, pos),
+        }
+    }
+}
+
+/// Lines of an input, in reverse order. See [Input::access_lines_rev]
+enum RevLines {
+    /// A file, scanned backwards from pos
+    File {
+        file: std::fs::File,
+        pos: u64,
+        /// Bytes read but not yet returned, starting at pos
+        pending: std::collections::VecDeque<u8>,
+        /// How many bytes at the start of pending haven't been searched for a newline yet
+        unscanned: usize,
+        done: bool,
+    },
+    Text(std::vec::IntoIter<String>),
+}
+
+impl RevLines {
+    const BLOCK_SIZE: u64 = 8 * 1024;
+
+    fn file(mut file: std::fs::File) -> io::Result<Self> {
+        let pos = io::Seek::seek(&mut file, io::SeekFrom::End(0))?;
+        let mut this = Self::File {
+            file,
+            pos,
+            pending: std::collections::VecDeque::new(),
+            unscanned: 0,
+            done: pos == 0,
+        };
+
+        this.fill()?;
+
+        // A trailing newline doesn't start another line
+        if let Self::File {
+            ref mut pending,
+            ref mut unscanned,
+            ..
+        } = this
+        {
+            if pending.back() == Some(&b'\n') {
+                pending.pop_back();
+                *unscanned -= 1;
+            }
+        }
+
+        Ok(this)
+    }
+
+    fn text(text: String) -> Self {
+        let mut lines: Vec<_> = text.lines().map(String::from).collect();
+        lines.reverse();
+
+        Self::Text(lines.into_iter())
+    }
+
+    /// Read the block before pos, prepending it to the pending bytes
+    fn fill(&mut self) -> io::Result<()> {
+        if let Self::File {
+            file,
+            pos,
+            pending,
+            unscanned,
+            ..
+        } = self
+        {
+            let len = (*pos).min(Self::BLOCK_SIZE);
+            *pos -= len;
+
+            let mut block = vec![0; len as usize];
+            io::Seek::seek(file, io::SeekFrom::Start(*pos))?;
+            io::Read::read_exact(file, &mut block)?;
+
+            pending.reserve(block.len());
+            for &b in block.iter().rev() {
+                pending.push_front(b);
+            }
+            *unscanned += block.len();
+        }
+
+        Ok(())
+    }
+
+    fn next_line(&mut self) -> io::Result<Option<String>> {
+        loop {
+            let (pos, pending, unscanned, done) = match self {
+                Self::File {
+                    pos,
+                    pending,
+                    unscanned,
+                    done,
+                    ..
+                } => (*pos, pending, unscanned, done),
+                Self::Text(lines) => return Ok(lines.next()),
+            };
+
+            // Bytes past the unscanned ones were already searched, and hold no newline
+            let newline = pending.range(..*unscanned).rposition(|&b| b == b'\n');
+            *unscanned = newline.unwrap_or(0);
+
+            let line = match newline {
+                Some(i) => {
+                    let line = pending.split_off(i + 1);
+                    pending.pop_back();
+
+                    line
+                }
+                None if pos == 0 && !*done => {
+                    *done = true;
+
+                    std::mem::take(pending)
+                }
+                None if pos == 0 => return Ok(None),
+                None => {
+                    self.fill()?;
+                    continue;
+                }
+            };
+
+            let mut line = String::from_utf8(Vec::from(line))
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            if line.ends_with('\r') {
+                line.pop();
+            }
+
+            return Ok(Some(line));
+        }
+    }
+}
+
+impl Iterator for RevLines {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_line().transpose()
+    }
+}
+
+pub(crate) enum Read {
+    File(std::fs::File),
+    Files(FileChain),
+    Stdin(std::io::Stdin),
+    Text(io::Cursor<String>),
+    Bytes(io::Cursor<Vec<u8>>),
+    Reader(SharedReader),
+    /// Holds the mapping alive for as long as it is read
+    #[cfg(feature = "mmap")]
+    Mapped(io::Cursor<memmap2::Mmap>),
+    #[cfg(feature = "http")]
+    Url(crate::http::Body),
+    #[cfg(feature = "tcp")]
+    Tcp(std::net::TcpStream),
+    Peeked(Box<io::Chain<io::Cursor<Vec<u8>>, Read>>),
+    /// A source passed through a [Decoder]
+    Decoded(Box<dyn io::Read + Send>),
+    /// An archive member or compressed file, decompressed as it is read
+    #[cfg(any(feature = "zip", feature = "gzip", feature = "zstd"))]
+    Decompressed(Box<dyn io::Read + Send>),
+}
+
+impl Read {
+    fn stdin() -> Self {
+        Self::Stdin(io::stdin())
+    }
+
+    fn file(f: std::fs::File) -> Self {
+        Self::File(f)
+    }
+
+    /// Map the given file if it was parsed with [mmap][crate::parsers::File::mmap], falling back
+    /// to reading it normally if the file can't be mapped
+    #[cfg(feature = "mmap")]
+    fn maybe_mapped(f: std::fs::File, path: &FilePath) -> Self {
+        if !path.options.mmap {
+            return Self::file(f);
+        }
+
+        match f.metadata() {
+            Ok(meta) if meta.is_file() && meta.len() > 0 => {}
+            _ => return Self::file(f),
+        }
+
+        // SAFETY: the mapping is only read, and File::mmap documents that the file must not
+        // change while it is read
+        match unsafe { memmap2::Mmap::map(&f) } {
+            Ok(map) => Self::Mapped(io::Cursor::new(map)),
+            Err(_) => Self::file(f),
+        }
+    }
+
+    fn text(s: impl AsRef<str>) -> Self {
+        let s = s.as_ref().to_string();
+
+        Self::Text(io::Cursor::new(s))
+    }
+
+    fn bytes(b: Vec<u8>) -> Self {
+        Self::Bytes(io::Cursor::new(b))
+    }
+
+    /// Read the first bytes of the given reader, failing if it has none. The bytes read are put
+    /// back in front of the reader.
+    fn require_nonempty(mut read: Self) -> Result<Self, AccessError> {
+        let mut head = vec![0; 8 * 1024];
+
+        let len = loop {
+            match io::Read::read(&mut read, &mut head) {
+                Ok(len) => break len,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(AccessError::read(e)),
+            }
+        };
+
+        if len == 0 {
+            return Err(AccessError::empty_stdin());
+        }
+        head.truncate(len);
+
+        Ok(Self::Peeked(Box::new(io::Read::chain(
+            io::Cursor::new(head),
+            read,
+        ))))
+    }
+
+    /// Check whether the given reader starts with the gzip magic bytes, decompressing it as it is
+    /// read if so. Either way, the bytes read while checking are put back in front of the reader.
+    #[cfg(feature = "gzip")]
+    fn sniff_gzip(mut read: Self) -> io::Result<Self> {
+        use crate::compression::gzip;
+
+        let mut head = Vec::with_capacity(gzip::MAGIC.len());
+        io::Read::read_to_end(
+            &mut io::Read::take(&mut read, gzip::MAGIC.len() as u64),
+            &mut head,
+        )?;
+
+        let is_gzip = head == gzip::MAGIC;
+        let peeked = io::Read::chain(io::Cursor::new(head), read);
+
+        match is_gzip {
+            true => Ok(Self::Decompressed(Box::new(gzip::Decoder::new(
+                io::BufReader::new(peeked),
+            )))),
+            false => Ok(Self::Peeked(Box::new(peeked))),
+        }
+    }
+}
+
+impl TryFrom<&InputType> for Read {
+    type Error = AccessError;
+
+    fn try_from(kind: &InputType) -> Result<Self, Self::Error> {
+        match kind {
+            InputType::Stdin(_) => Ok(Read::stdin()),
+            #[cfg(feature = "zip")]
+            InputType::File(ref f) if f.member.is_some() => {
+                let member = f.member.as_deref().unwrap_or_default();
+
+                crate::compression::zip::read_member(&f.path, member)
+                    .map(|member| Read::Decompressed(Box::new(member)))
+                    .map_err(|e| AccessError::archive_member(e, f.path.as_path(), member))
+            }
+            #[cfg(feature = "gzip")]
+            InputType::File(ref f)
+                if f.options.decompress_gzip && crate::compression::gzip::is_gzip_path(&f.path) =>
+            {
+                crate::compression::gzip::read_file(&f.path)
+                    .map(|file| Read::Decompressed(Box::new(file)))
+                    .map_err(|e| AccessError::file_with_context(e, f.path.as_path()))
+            }
+            #[cfg(feature = "zstd")]
+            InputType::File(ref f)
+                if f.options.decompress_zstd && crate::compression::zstd::is_zstd_path(&f.path) =>
+            {
+                crate::compression::zstd::read_file(&f.path)
+                    .map(|file| Read::Decompressed(Box::new(file)))
+                    .map_err(|e| AccessError::file_with_context(e, f.path.as_path()))
+            }
+            #[cfg(feature = "mmap")]
+            InputType::File(ref f) => f
+                .open()
+                .map(|file| Read::maybe_mapped(file, f))
+                .map_err(|e| AccessError::file_with_context(e, f.path.as_path())),
+            #[cfg(not(feature = "mmap"))]
+            InputType::File(ref f) => f
+                .open()
+                .map(Read::file)
+                .map_err(|e| AccessError::file_with_context(e, f.path.as_path())),
+            InputType::Files(ref files) => Ok(Read::Files(FileChain::new(files))),
+            #[cfg(feature = "http")]
+            InputType::Url(ref url) => crate::http::get(url)
+                .map(Read::Url)
+                .map_err(|e| AccessError::url(e, url)),
+            #[cfg(not(feature = "http"))]
+            InputType::Url(ref url) => Err(AccessError::url(
+                io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "fetching URLs requires the 'http' feature",
+                ),
+                url,
+            )),
+            #[cfg(feature = "tcp")]
+            InputType::Tcp(ref addr) => crate::tcp::connect(addr)
+                .map(Read::Tcp)
+                .map_err(|e| AccessError::tcp(e, addr)),
+            #[cfg(feature = "clipboard")]
+            InputType::Clipboard => crate::clipboard::read()
+                .map(Self::text)
+                .map_err(AccessError::clipboard),
+            InputType::Env(ref name) => std::env::var(name)
+                .map(Self::text)
+                .map_err(|e| AccessError::env(e, name)),
+            InputType::Base64(ref b) | InputType::Bytes(ref b) => Ok(Self::bytes(b.clone())),
+            InputType::UTF8(ref s) => Ok(Self::text(s)),
+            InputType::LazyText(ref f) => Ok(Self::text(f.call())),
+            InputType::OpenFile(ref f) => f.try_clone().map(Read::file).map_err(AccessError::read),
+            InputType::Reader(ref r) => Ok(Read::Reader(r.clone())),
+        }
+    }
+}
+
+impl io::Read for Read {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        use Read::*;
+        match self {
+            File(ref mut file) => io::Read::read(file, buf),
+            Files(ref mut files) => io::Read::read(files, buf),
+            Stdin(ref mut stdin) => io::Read::read(stdin, buf),
+            Text(ref mut cursor) => io::Read::read(cursor, buf),
+            Bytes(ref mut cursor) => io::Read::read(cursor, buf),
+            Reader(ref mut reader) => io::Read::read(reader, buf),
+            #[cfg(feature = "mmap")]
+            Mapped(ref mut map) => io::Read::read(map, buf),
+            #[cfg(feature = "http")]
+            Url(ref mut body) => io::Read::read(body, buf),
+            #[cfg(feature = "tcp")]
+            Tcp(ref mut stream) => io::Read::read(stream, buf),
+            Peeked(ref mut chain) => io::Read::read(chain, buf),
+            Decoded(ref mut reader) => io::Read::read(reader, buf),
+            #[cfg(any(feature = "zip", feature = "gzip", feature = "zstd"))]
+            Decompressed(ref mut reader) => io::Read::read(reader, buf),
+        }
+    }
+
+    #[cfg(grab_nightly)]
+    fn read_buf(&mut self, buf: io::BorrowedCursor<'_>) -> io::Result<()> {
+        use Read::*;
+        match self {
+            File(ref mut file) => io::Read::read_buf(file, buf),
+            Files(ref mut files) => io::Read::read_buf(files, buf),
+            Stdin(ref mut stdin) => io::Read::read_buf(stdin, buf),
+            Text(ref mut cursor) => io::Read::read_buf(cursor, buf),
+            Bytes(ref mut cursor) => io::Read::read_buf(cursor, buf),
+            Reader(ref mut reader) => io::Read::read_buf(reader, buf),
+            #[cfg(feature = "mmap")]
+            Mapped(ref mut map) => io::Read::read_buf(map, buf),
+            #[cfg(feature = "http")]
+            Url(ref mut body) => io::Read::read_buf(body, buf),
+            #[cfg(feature = "tcp")]
+            Tcp(ref mut stream) => io::Read::read_buf(stream, buf),
+            Peeked(ref mut chain) => io::Read::read_buf(chain, buf),
+            Decoded(ref mut reader) => io::Read::read_buf(reader, buf),
+            #[cfg(any(feature = "zip", feature = "gzip", feature = "zstd"))]
+            Decompressed(ref mut reader) => io::Read::read_buf(reader, buf),
+        }
+    }
+}
+
+impl fmt::Debug for Read {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use Read::*;
+        let mut dbg = f.debug_struct("Read");
+
+        match self {
+            File(f) => dbg.field("file", &f),
+            Files(f) => dbg.field("files", &f),
+            Stdin(s) => dbg.field("stdin", &s),
+            Text(t) => dbg.field("cursor", &t),
+            Bytes(b) => dbg.field("bytes", &b),
+            Reader(r) => dbg.field("reader", &r),
+            #[cfg(feature = "mmap")]
+            Mapped(m) => dbg.field("mapped", &m),
+            #[cfg(feature = "http")]
+            Url(u) => dbg.field("url", &u),
+            #[cfg(feature = "tcp")]
+            Tcp(t) => dbg.field("tcp", &t),
+            Peeked(c) => dbg.field("peeked", &c),
+            Decoded(_) => dbg.field("decoded", &"Box<dyn Read>"),
+            #[cfg(any(feature = "zip", feature = "gzip", feature = "zstd"))]
+            Decompressed(_) => dbg.field("decompressed", &"Box<dyn Read>"),
+        };
+
+        dbg.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{fixture, FakeStdin, TempDir};
+
+    /// Read the whole input, as [Input::read_to_string] but with stdin reading from the given
+    /// stdin
+    fn read_from(i: &Input, stdin: &FakeStdin) -> Result<String, AccessError> {
+        i.access_from(stdin)?
+            .read_to_string()
+            .map_err(AccessError::read)
+    }
+
+    /// Like [read_from], but as [Input::read_to_bytes]
+    fn bytes_from(i: &Input, stdin: &FakeStdin) -> Result<Vec<u8>, AccessError> {
+        let mut buf = Vec::new();
+        io::Read::read_to_end(&mut i.access_from(stdin)?, &mut buf).map_err(AccessError::read)?;
+
+        Ok(buf)
+    }
+
+    #[test]
+    fn input_from_default() {
+        let input = "@/some/file/path";
+        let res = Input::with_defaults(input);
+
+        assert!(res.is_ok())
+    }
+
+    #[test]
+    fn input_from_str() {
+        let input = "some text";
+        let res = Input::from_str(input);
+
+        assert!(res.is_ok())
+    }
+
+    #[test]
+    fn input_reader() {
+        let input = "some random text";
+        let i = Input::with_defaults(input).unwrap();
+
+        let output = i.access().unwrap().read_to_string().unwrap();
+
+        assert_eq!(input, output.as_str())
+    }
+
+    fn fixture_spec(name: &str) -> String {
+        format!("@{}", fixture(name).display())
+    }
+
+    #[test]
+    fn input_read_to_string_text() {
+        let input = "some random text";
+        let i = Input::with_defaults(input).unwrap();
+
+        assert_eq!(i.read_to_string().unwrap(), input)
+    }
+
+    #[test]
+    fn input_read_to_string_file() {
+        let i = Input::with_defaults(fixture_spec("empty.txt")).unwrap();
+
+        assert_eq!(i.read_to_string().unwrap(), "")
+    }
+
+    #[test]
+    fn input_from_open_file() {
+        use std::io::{Seek, SeekFrom, Write};
+
+        let dir = TempDir::new("open-file");
+        let path = dir.join("file");
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        file.write_all(b"opened elsewhere").unwrap();
+        file.seek(SeekFrom::Start(0)).unwrap();
+
+        let i = Input::from_open_file(file);
+
+        assert!(i.is_file());
+        assert_eq!(i.path(), None);
+
+        let output = i.access().unwrap().read_to_string().unwrap();
+        assert_eq!(output, "opened elsewhere");
+
+        // The handle's position is shared, and is now at the end
+        assert_eq!(i.read_to_string().unwrap(), "")
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn input_is_fifo() {
+        use crate::{error::access::Kind, parsers::File};
+        use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+        let dir = TempDir::new("fifo");
+        let path = dir.join("fifo");
+        let c_path = CString::new(path.as_os_str().as_bytes()).unwrap();
+        // SAFETY: c_path is a valid, nul terminated string for the duration of the call
+        assert_eq!(unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) }, 0);
+
+        let input = format!("@{}", path.display());
+        let fifo = Input::with_defaults(&input).unwrap();
+        assert!(fifo.is_fifo());
+        assert!(!Input::with_defaults(fixture_spec("lorem.txt"))
+            .unwrap()
+            .is_fifo());
+        assert!(!Input::with_defaults("some text").unwrap().is_fifo());
+
+        // Rejected without opening it, which would block
+        let cfg = Builder::new()
+            .with(|this| this.with_file(File::new().with(|f| f.follow_fifo(false))))
+            .build();
+        let err = cfg.parse(&input).unwrap().access().unwrap_err();
+
+        assert_eq!(err.kind(), Kind::File);
+        assert!(err.to_string().contains("FIFO"))
+    }
+
+    #[test]
+    fn input_read_to_string_stdin() {
+        let stdin = FakeStdin::new("piped text");
+        let i = Input::with_defaults("-").unwrap();
+
+        assert_eq!(read_from(&i, &stdin).unwrap(), "piped text")
+    }
+
+    #[test]
+    fn input_stdin_locked() {
+        const CHILD: &str = "GRAB_TEST_STDIN_LOCKED";
+
+        // Re-run just this test in a child process, with real piped stdin
+        if std::env::var_os(CHILD).is_some() {
+            let i = Input::with_defaults("-").unwrap();
+
+            let mut head = [0; 5];
+            io::Read::read_exact(&mut i.access().unwrap(), &mut head).unwrap();
+            assert_eq!(&head, b"piped");
+
+            // Anything buffered while reading the first chunk is seen by the next reader
+            assert_eq!(i.read_to_string().unwrap(), " bytes");
+            return;
+        }
+
+        use std::{
+            io::Write,
+            process::{Command, Stdio},
+        };
+
+        let mut child = Command::new(std::env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "input::tests::input_stdin_locked",
+                "--test-threads=1",
+            ])
+            .env(CHILD, "1")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(b"piped bytes")
+            .unwrap();
+
+        assert!(child.wait().unwrap().success())
+    }
+
+    #[test]
+    fn input_read_to_string_errors() {
+        use crate::error::access::Kind;
+
+        // Not valid UTF-8 without transcoding
+        let i = Input::with_defaults(fixture_spec("utf16le.txt")).unwrap();
+        assert_eq!(i.read_to_string().unwrap_err().kind(), Kind::Read);
+
+        let i = Input::with_defaults(fixture_spec("does_not_exist.txt")).unwrap();
+        assert_eq!(i.read_to_string().unwrap_err().kind(), Kind::File)
+    }
+
+    #[test]
+    fn input_access_enum() {
+        let i = Input::with_defaults("some text").unwrap();
+        assert!(matches!(i.access_enum(), Ok(RawReader::Text(_))));
+
+        let i = Input::with_defaults(fixture_spec("binary.bin")).unwrap();
+        let mut output = Vec::new();
+        match i.access_enum().unwrap() {
+            RawReader::File(mut f) => io::Read::read_to_end(&mut f, &mut output).unwrap(),
+            bad => panic!("expected File, got: {:?}", bad),
+        };
+        assert_eq!(output, (0..=255).collect::<Vec<u8>>());
+
+        // Wrapping is only applied by InputReader
+        let cfg = Builder::new()
+            .with(|this| this.text().wrap_content("<", ">"))
+            .build();
+        let mut output = String::new();
+        io::Read::read_to_string(
+            &mut cfg.parse("raw").unwrap().access_enum().unwrap(),
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(output, "raw")
+    }
+
+    #[test]
+    fn input_base64() {
+        let cfg = Builder::new().with(|this| this.text().base64()).build();
+
+        let i = cfg.parse("base64:AAH/").unwrap();
+
+        assert_eq!(i.read_to_bytes().unwrap(), [0x00, 0x01, 0xff])
+    }
+
+    #[test]
+    fn input_from_bytes() {
+        let bytes = vec![0x00, 0xff, 0xfe, b'\n', 0x80];
+
+        let i = Input::from_bytes(bytes.clone());
+
+        assert_eq!(i.kind(), InputKind::Bytes);
+        assert_eq!(i.read_to_bytes().unwrap(), bytes);
+        // Reading again produces the same bytes
+        assert_eq!(i.read_to_bytes().unwrap(), bytes)
+    }
+
+    #[test]
+    fn input_from_text() {
+        let i = Input::from_text("@not/a/file");
+
+        assert!(i.is_text());
+        assert_eq!(i.read_to_string().unwrap(), "@not/a/file")
+    }
+
+    #[test]
+    fn input_from_reader() {
+        let i = Input::from_reader(io::Cursor::new(b"first\nsecond".to_vec()));
+
+        assert_eq!(i.kind(), InputKind::Reader);
+
+        let mut lines = i.access().unwrap().lines();
+        assert_eq!(lines.next().unwrap().unwrap(), "first");
+        assert_eq!(lines.next().unwrap().unwrap(), "second");
+
+        // The reader is shared, and has been read to the end
+        assert_eq!(i.read_to_string().unwrap(), "")
+    }
+
+    #[test]
+    fn input_kind_defaults() {
+        let cases = [
+            ("-", InputKind::Stdin),
+            ("@some/file", InputKind::File),
+            ("some text", InputKind::Text),
+        ];
+
+        for &(input, kind) in cases.iter() {
+            assert_eq!(
+                Input::with_defaults(input).unwrap().kind(),
+                kind,
+                "{}",
+                input
+            );
+        }
+
+        assert_eq!(Input::from_text_fn(String::new).kind(), InputKind::Text)
+    }
+
+    #[test]
+    fn input_kind_predicates() {
+        let i = Input::with_defaults("-").unwrap();
+        assert!(i.is_stdin() && !i.is_file() && !i.is_text());
+
+        let i = Input::with_defaults("@some/file").unwrap();
+        assert!(!i.is_stdin() && i.is_file() && !i.is_text());
+
+        let i = Input::with_defaults("some text").unwrap();
+        assert!(!i.is_stdin() && !i.is_file() && i.is_text())
+    }
+
+    #[test]
+    fn input_path_defaults() {
+        use std::path::Path;
+
+        let i = Input::with_defaults("@some/file").unwrap();
+        assert_eq!(i.path(), Some(Path::new("some/file")));
+
+        let i = Input::with_defaults("-").unwrap();
+        assert_eq!(i.path(), None);
+
+        let i = Input::with_defaults("some text").unwrap();
+        assert_eq!(i.path(), None)
+    }
+
+    #[test]
+    fn input_display_round_trip() {
+        let cases = [
+            "-",
+            "@some/file",
+            "@",
+            "some text",
+            "@notafile",
+            "\\@notafile",
+            "-",
+            "--",
+            "\\n",
+            "",
+            "file:///not/a/file",
+        ];
+
+        for &case in cases.iter() {
+            let i = Input::with_defaults(case).unwrap();
+
+            let displayed = i.to_string();
+
+            assert_eq!(displayed.parse::<Input>().unwrap(), i, "{}", displayed);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn input_display_non_utf8_path() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let cfg = Config::default();
+
+        let i = cfg
+            .parse_os(OsStr::from_bytes(b"@/tmp/caf\xE9 50%"))
+            .unwrap();
+        assert_eq!(i.to_string(), "file:///tmp/caf%E9%2050%25");
+        assert_eq!(i.to_string().parse::<Input>().unwrap(), i);
+
+        // Relative paths read back as the same file, by its absolute path
+        let i = cfg.parse_os(OsStr::from_bytes(b"@caf\xE9")).unwrap();
+        let parsed = i.to_string().parse::<Input>().unwrap();
+        let absolute = std::env::current_dir()
+            .unwrap()
+            .join(OsStr::from_bytes(b"caf\xE9"));
+        assert_eq!(parsed.path(), Some(absolute.as_path()))
+    }
+
+    #[test]
+    fn input_display_escapes_text() {
+        let text = |t: &str| Input::with_options(InputType::UTF8(t.into()), Default::default());
+
+        assert_eq!(text("@notafile").to_string(), "\\@notafile");
+        assert_eq!(text("-").to_string(), "\\-");
+        assert_eq!(text("plain").to_string(), "plain");
+        assert_eq!(Input::with_defaults("@x").unwrap().to_string(), "@x");
+        assert_eq!(Input::with_defaults("-").unwrap().to_string(), "-");
+
+        let parsed: Input = text("@notafile").to_string().parse().unwrap();
+        assert_eq!(parsed.read_to_string().unwrap(), "@notafile")
+    }
+
+    #[test]
+    fn input_display_from_str() {
+        let text = |t: &str| Input::with_options(InputType::UTF8(t.into()), Default::default());
+
+        let cases = [
+            text("@notafile"),
+            text("-"),
+            Input::with_defaults("@some/file").unwrap(),
+            Input::with_defaults("-").unwrap(),
+        ];
+
+        for i in cases.iter() {
+            let s = i.to_string();
+            let parsed = s.parse::<Input>().unwrap();
+
+            assert_eq!(parsed, *i, "{}", s);
+            assert_eq!(parsed.kind(), i.kind(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn input_display_round_trip_kinds() {
+        use crate::parsers::File;
+
+        let mut file = File::new();
+        file.append_marker("+");
+        #[cfg(feature = "zip")]
+        file.archive_separator("!");
+
+        let cfg = Builder::new()
+            .with(|this| {
+                this.text()
+                    .stdin()
+                    .with_file(file.clone())
+                    .url()
+                    .env()
+                    .base64()
+                    .escape("\\");
+
+                #[cfg(feature = "tcp")]
+                this.tcp();
+                #[cfg(feature = "clipboard")]
+                this.clipboard();
+
+                this
+            })
+            .build();
+
+        #[allow(unused_mut)]
+        let mut cases = vec![
+            ("-", InputKind::Stdin),
+            ("@some/file", InputKind::File),
+            ("+out.log", InputKind::File),
+            ("http://example.com/a", InputKind::Url),
+            ("env:HOME", InputKind::Env),
+            ("base64:aGk=", InputKind::Base64),
+            ("some text", InputKind::Text),
+            ("\\@notafile", InputKind::Text),
+            ("\\-", InputKind::Text),
+        ];
+        #[cfg(feature = "zip")]
+        cases.push(("@archive.zip!inner/member.txt", InputKind::File));
+        #[cfg(feature = "tcp")]
+        cases.push(("tcp://localhost:8080", InputKind::Tcp));
+        #[cfg(feature = "clipboard")]
+        cases.push(("clipboard", InputKind::Clipboard));
+
+        for (spec, kind) in cases {
+            let i = cfg.parse(spec).unwrap();
+            assert_eq!(i.kind(), kind, "{}", spec);
+
+            let displayed = i.to_string();
+            assert_eq!(displayed, spec);
+            assert_eq!(cfg.parse(&displayed).unwrap(), i, "{}", displayed)
+        }
+
+        // Kinds which can't be parsed back as the same input
+        let files = Input::with_options(
+            InputType::Files(vec![FilePath::new("a".into()), FilePath::new("b".into())]),
+            Default::default(),
+        );
+        assert_eq!(files.to_string(), "@a @b");
+
+        let bytes = Input::from_bytes(&b"hi"[..]);
+        let parsed = cfg.parse(&bytes.to_string()).unwrap();
+        assert_eq!(parsed.kind(), InputKind::Base64);
+        assert_eq!(parsed.read_to_bytes().unwrap(), b"hi");
+
+        let called = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let lazy = Input::from_text_fn({
+            let called = called.clone();
+            move || {
+                called.store(true, std::sync::atomic::Ordering::SeqCst);
+                String::from("@lazy")
+            }
+        });
+        assert_eq!(lazy.to_string(), "<lazy text>");
+        assert!(!called.load(std::sync::atomic::Ordering::SeqCst));
+
+        let reader = Input::from_reader(io::empty());
+        assert_eq!(reader.to_string(), "<reader>")
+    }
+
+    #[test]
+    fn input_ordering() {
+        let cfg = Builder::new()
+            .with(|this| this.text().stdin().file().env())
+            .build();
+
+        let mut inputs: Vec<_> = ["-", "b text", "@b", "env:HOME", "a text", "@a"]
+            .iter()
+            .map(|s| cfg.parse(s).unwrap())
+            .collect();
+        inputs.sort();
+
+        let expected: Vec<_> = ["@a", "@b", "env:HOME", "a text", "b text", "-"]
+            .iter()
+            .map(|s| cfg.parse(s).unwrap())
+            .collect();
+
+        assert_eq!(inputs, expected);
+        assert_ne!(cfg.parse("@a").unwrap(), cfg.parse("@b").unwrap());
+
+        let cfg = Builder::new()
+            .with(|this| this.with_file(crate::parsers::File::new().with(|f| f.append_marker("+"))))
+            .build();
+        assert!(cfg.parse("@a").unwrap() < cfg.parse("+a").unwrap())
+    }
+
+    #[test]
+    fn input_file_chain() {
+        let path = |name: &str| FilePath::new(fixture(name));
+        let files = vec![path("lorem.txt"), path("empty.txt"), path("binary.bin")];
+
+        let i = Input::with_options(InputType::Files(files), ReadOptions::default());
+
+        let mut expected = Input::with_defaults(fixture_spec("lorem.txt"))
+            .unwrap()
+            .read_to_bytes()
+            .unwrap();
+        expected.extend(0..=255);
+
+        assert_eq!(i.read_to_bytes().unwrap(), expected)
+    }
+
+    #[test]
+    fn input_read_to_bytes_file() {
+        let i = Input::with_defaults(fixture_spec("binary.bin")).unwrap();
+
+        let output = i.read_to_bytes().unwrap();
+
+        assert_eq!(output, (0..=255).collect::<Vec<u8>>())
+    }
+
+    #[test]
+    fn input_read_to_bytes_text() {
+        let i = Input::with_defaults("wörld").unwrap();
+
+        assert_eq!(i.read_to_bytes().unwrap(), "wörld".as_bytes())
+    }
+
+    #[test]
+    fn input_read_to_bytes_stdin() {
+        let stdin = FakeStdin::new(vec![0xFF, 0x00, 0xFE]);
+        let i = Input::with_defaults("-").unwrap();
+
+        assert_eq!(bytes_from(&i, &stdin).unwrap(), [0xFF, 0x00, 0xFE])
+    }
+
+    #[test]
+    fn input_reader_lines_file() {
+        let i = Input::with_defaults(fixture_spec("lorem.txt")).unwrap();
+        let expected = std::fs::read_to_string(fixture("lorem.txt")).unwrap();
+
+        let lines = i
+            .access()
+            .unwrap()
+            .lines()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(lines.len(), 60);
+        assert_eq!(lines, expected.lines().collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn input_reader_lines_text() {
+        let i = Input::with_defaults("first\nsecond\r\nthird").unwrap();
+
+        let lines = i
+            .access()
+            .unwrap()
+            .lines()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(lines, ["first", "second", "third"])
+    }
+
+    #[test]
+    fn input_records_nul() {
+        let i = Input::with_defaults(fixture_spec("records.dat")).unwrap();
+
+        let records = i
+            .access_records(b'\0')
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(records, [&b"first"[..], b"second record", b"", b"last"])
+    }
+
+    #[test]
+    fn input_records_trailing_separator() {
+        let i = Input::with_defaults("a,b,").unwrap();
+
+        let records = i
+            .access_records(b',')
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(records, [b"a", b"b"])
+    }
+
+    #[test]
+    fn input_lines() {
+        let file = Input::with_defaults(fixture_spec("lines.txt")).unwrap();
+        let text = Input::with_defaults("one\ntwo\r\nthree").unwrap();
+
+        for i in [file, text] {
+            let lines = i.lines().unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+
+            assert_eq!(lines, ["one", "two", "three"])
+        }
+    }
+
+    #[test]
+    fn input_access_filtered() {
+        let i = Input::with_defaults(fixture_spec("lorem.txt")).unwrap();
+
+        let expected: Vec<_> = i
+            .read_to_string()
+            .unwrap()
+            .lines()
+            .filter(|line| line.contains("amet"))
+            .map(String::from)
+            .collect();
+
+        let lines = i
+            .access_filtered(|line| line.contains("amet"))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(lines.len(), 31);
+        assert_eq!(lines, expected)
+    }
+
+    #[test]
+    fn input_access_seek() {
+        use io::{Read, Seek, SeekFrom};
+
+        let file = Input::with_defaults(fixture_spec("lorem.txt")).unwrap();
+
+        for i in [file, Input::from_text("some text")] {
+            let mut reader = i.access_seek().unwrap();
+
+            let mut head = [0; 4];
+            reader.read_exact(&mut head).unwrap();
+
+            reader.seek(SeekFrom::Current(-2)).unwrap();
+            let mut rest = String::new();
+            reader.read_to_string(&mut rest).unwrap();
+
+            // Everything after the first two bytes, which were read again
+            assert_eq!(rest, i.read_to_string().unwrap()[2..])
+        }
+    }
+
+    #[test]
+    fn input_len() {
+        use crate::error::access::Kind;
+
+        let i = Input::with_defaults(fixture_spec("utf8.txt")).unwrap();
+        assert_eq!(i.len().unwrap(), Some(14));
+
+        assert_eq!(Input::from_text("wörld").len().unwrap(), Some(6));
+        assert_eq!(Input::with_defaults("-").unwrap().len().unwrap(), None);
+
+        let i = Input::with_defaults(fixture_spec("does_not_exist.txt")).unwrap();
+        assert_eq!(i.len().unwrap_err().kind(), Kind::File)
+    }
+
+    #[test]
+    fn input_access_seek_stdin() {
+        use crate::error::access::Kind;
+
+        let i = Input::with_defaults("-").unwrap();
+
+        assert_eq!(i.access_seek().err().unwrap().kind(), Kind::NotSeekable)
+    }
+
+    #[test]
+    fn input_lines_rev_file() {
+        let i = Input::with_defaults(fixture_spec("lorem.txt")).unwrap();
+
+        let mut expected: Vec<_> = i
+            .read_to_string()
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect();
+        expected.reverse();
+
+        let lines = i
+            .access_lines_rev()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(lines.len(), 60);
+        assert_eq!(lines, expected)
+    }
+
+    #[test]
+    fn input_lines_rev_direct_io() {
+        let cfg = Builder::new()
+            .with(|this| this.with_file(crate::parsers::File::new().with(|f| f.direct_io(true))))
+            .build();
+        let i = cfg.parse(&fixture_spec("lorem.txt")).unwrap();
+
+        let mut expected: Vec<_> = i
+            .read_to_string()
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect();
+        expected.reverse();
+
+        // Blocks are read at unaligned offsets into unaligned buffers
+        let lines = i
+            .access_lines_rev()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(lines, expected)
+    }
+
+    #[test]
+    fn input_lines_rev_text() {
+        for &(text, expected) in &[
+            (
+                "first\nsecond\r\nthird\n",
+                &["third", "second", "first"][..],
+            ),
+            ("\n\nlast", &["last", "", ""][..]),
+            ("", &[][..]),
+        ] {
+            let i = Input::with_defaults(text).unwrap();
+
+            let lines = i
+                .access_lines_rev()
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+
+            assert_eq!(lines, expected, "{:?}", text)
+        }
+    }
+
+    #[test]
+    fn input_lines_rev_long_lines() {
+        let dir = TempDir::new("rev-lines");
+
+        // Lines spanning several blocks, with a newline falling right on a block boundary
+        let long = "x".repeat(RevLines::BLOCK_SIZE as usize * 5 + 7);
+        let boundary = "y".repeat(RevLines::BLOCK_SIZE as usize - 1);
+        let path = dir.write(
+            "lines.txt",
+            format!("first\n{}\n\n{}\r\nlast", long, boundary),
+        );
+
+        let lines = Input::with_defaults(format!("@{}", path.display()))
+            .unwrap()
+            .access_lines_rev()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>();
+
+        assert_eq!(lines.unwrap(), ["last", &boundary, "", &long, "first"])
+    }
+
+    #[test]
+    fn input_lines_rev_empty_file() {
+        let i = Input::with_defaults(fixture_spec("empty.txt")).unwrap();
+
+        assert_eq!(i.access_lines_rev().unwrap().count(), 0)
+    }
+
+    #[test]
+    fn input_lines_rev_stdin() {
+        use crate::error::access::Kind;
+
+        let i = Input::with_defaults("-").unwrap();
+
+        let err = i.access_lines_rev().err().unwrap();
+
+        assert_eq!(err.kind(), Kind::Stdin)
+    }
+
+    #[test]
+    fn input_would_block() {
+        for input in &["@some/file", "some text"] {
+            assert!(
+                !Input::with_defaults(input).unwrap().would_block(),
+                "{}",
+                input
+            )
+        }
+    }
+
+    #[test]
+    fn input_would_block_piped() {
+        const CHILD: &str = "GRAB_TEST_WOULD_BLOCK_PIPED";
+
+        // Re-run just this test in a child process, with real piped stdin that stays empty
+        if std::env::var_os(CHILD).is_some() {
+            assert!(!Input::with_defaults("-").unwrap().would_block());
+            return;
+        }
+
+        use std::process::{Command, Stdio};
+
+        let mut child = Command::new(std::env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "input::tests::input_would_block_piped",
+                "--test-threads=1",
+            ])
+            .env(CHILD, "1")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        // Keep the pipe open, but empty, until the child is done
+        let _writer = child.stdin.take();
+
+        assert!(child.wait().unwrap().success())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn input_would_block_terminal() {
+        const CHILD: &str = "GRAB_TEST_WOULD_BLOCK_TERMINAL";
+
+        use std::{
+            io::{BufRead, Write},
+            os::unix::{ffi::OsStringExt, io::FromRawFd},
+            process::{Command, Stdio},
+        };
+
+        // Re-run just this test in a child process, with a pseudo terminal as stdin
+        if std::env::var_os(CHILD).is_some() {
+            let i = Input::with_defaults("-").unwrap();
+            assert!(i.would_block());
+
+            let mut stdout = io::stdout();
+            stdout.write_all(b"\nready\n").unwrap();
+            stdout.flush().unwrap();
+
+            // Wait for the line written by the parent to reach the terminal
+            let start = std::time::Instant::now();
+            while i.would_block() {
+                assert!(start.elapsed() < std::time::Duration::from_secs(10));
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            }
+
+            let mut line = String::new();
+            io::BufReader::new(i.access().unwrap())
+                .read_line(&mut line)
+                .unwrap();
+            assert_eq!(line, "abc\n");
+
+            // The line was consumed, so the terminal is idle again
+            assert!(i.would_block());
+            return;
+        }
+
+        // SAFETY: plain libc calls on the new master fd, and ptsname's result is copied
+        // before any other call could overwrite it
+        let (mut master, slave) = unsafe {
+            let master = libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY);
+            if master == -1 || libc::grantpt(master) != 0 || libc::unlockpt(master) != 0 {
+                // No pseudo terminals in this environment
+                return;
+            }
+            let slave = std::ffi::CStr::from_ptr(libc::ptsname(master)).to_owned();
+
+            (
+                std::fs::File::from_raw_fd(master),
+                std::ffi::OsString::from_vec(slave.into_bytes()),
+            )
+        };
+
+        let mut child = Command::new(std::env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "input::tests::input_would_block_terminal",
+                "--test-threads=1",
+            ])
+            .env(CHILD, "1")
+            .stdin(
+                std::fs::File::options()
+                    .read(true)
+                    .write(true)
+                    .open(slave)
+                    .unwrap(),
+            )
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let mut stdout = io::BufReader::new(child.stdout.take().unwrap());
+        let mut line = String::new();
+        while line != "ready\n" {
+            line.clear();
+            if stdout.read_line(&mut line).unwrap() == 0 {
+                break;
+            }
+        }
+
+        master.write_all(b"abc\n").unwrap();
+
+        assert!(child.wait().unwrap().success())
+    }
+
+    #[test]
+    fn input_would_block_buffered() {
+        let stdin = FakeStdin::new("buffered");
+        let i = Builder::new()
+            .with(|this| this.stdin().buffer_stdin(true))
+            .build()
+            .parse("-")
+            .unwrap();
+
+        read_from(&i, &stdin).unwrap();
+
+        assert!(i.stdin_buffer.get().is_some() && !i.would_block())
+    }
+
+    #[test]
+    fn input_strip_ansi() {
+        let colored = "\u{1b}[1;32mok\u{1b}[0m: \u{1b}[31mfailed\u{1b}[m";
+        let cfg = Builder::new()
+            .with(|this| this.text().stdin().strip_ansi(true))
+            .build();
+
+        let i = cfg.parse(colored).unwrap();
+        assert_eq!(i.read_to_string().unwrap(), "ok: failed");
+
+        let i = cfg.parse("-").unwrap();
+        assert_eq!(
+            read_from(&i, &FakeStdin::new(colored)).unwrap(),
+            "ok: failed"
+        );
+
+        // Raw reads are untouched
+        let i = cfg.parse(colored).unwrap();
+        assert_eq!(i.read_to_bytes().unwrap(), colored.as_bytes())
+    }
+
+    #[test]
+    fn input_buffer_stdin() {
+        let stdin = FakeStdin::new("read me twice");
+        let cfg = Builder::new()
+            .with(|this| this.stdin().buffer_stdin(true))
+            .build();
+        let i = cfg.parse("-").unwrap();
+
+        assert_eq!(read_from(&i, &stdin).unwrap(), "read me twice");
+        assert_eq!(read_from(&i, &stdin).unwrap(), "read me twice")
+    }
+
+    #[test]
+    fn input_unbuffered_stdin() {
+        let stdin = FakeStdin::new("read me once");
+        let i = Input::with_defaults("-").unwrap();
+
+        assert_eq!(read_from(&i, &stdin).unwrap(), "read me once");
+        assert_eq!(read_from(&i, &stdin).unwrap(), "")
+    }
+
+    #[test]
+    fn input_guard_stdin() {
+        use crate::error::access::Kind;
+
+        let stdin = FakeStdin::new("read me once");
+        let cfg = Builder::new()
+            .with(|this| this.stdin().guard_stdin(true))
+            .build();
+        let i = cfg.parse("-").unwrap();
+
+        assert_eq!(read_from(&i, &stdin).unwrap(), "read me once");
+        assert_eq!(
+            read_from(&i, &stdin).unwrap_err().kind(),
+            Kind::StdinAlreadyConsumed
+        );
+
+        // Other inputs are guarded too, and buffered stdin only counts as one access
+        let stdin = FakeStdin::new("read me twice");
+        let buffered = Builder::new()
+            .with(|this| this.stdin().guard_stdin(true).buffer_stdin(true))
+            .build()
+            .parse("-")
+            .unwrap();
+
+        assert_eq!(read_from(&buffered, &stdin).unwrap(), "read me twice");
+        assert_eq!(read_from(&buffered, &stdin).unwrap(), "read me twice");
+        assert!(cfg.parse("-").unwrap().access_from(&stdin).is_err())
+    }
+
+    #[test]
+    fn input_guard_process_stdin() {
+        use crate::error::access::Kind;
+
+        const CHILD: &str = "GRAB_TEST_GUARD_PROCESS_STDIN";
+
+        // Re-run just this test in a child process, as the guard is shared by the whole process
+        if std::env::var_os(CHILD).is_some() {
+            let cfg = Builder::new()
+                .with(|this| this.stdin().guard_stdin(true))
+                .build();
+            let i = cfg.parse("-").unwrap();
+
+            assert_eq!(i.read_to_string().unwrap(), "piped");
+            assert_eq!(i.access().unwrap_err().kind(), Kind::StdinAlreadyConsumed);
+
+            // Inputs accessed on other threads are guarded too
+            let other = cfg.parse("-").unwrap();
+            let err = std::thread::spawn(move || other.access().unwrap_err())
+                .join()
+                .unwrap();
+            assert_eq!(err.kind(), Kind::StdinAlreadyConsumed);
+
+            // Unguarded inputs can still access stdin
+            assert!(Input::with_defaults("-").unwrap().access().is_ok());
+            return;
+        }
+
+        use std::{
+            io::Write,
+            process::{Command, Stdio},
+        };
+
+        let mut child = Command::new(std::env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "input::tests::input_guard_process_stdin",
+                "--test-threads=1",
+            ])
+            .env(CHILD, "1")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        child.stdin.take().unwrap().write_all(b"piped").unwrap();
+
+        assert!(child.wait().unwrap().success())
+    }
+
+    #[test]
+    fn input_from_text_fn() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let i = Input::from_text_fn(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            String::from("lazy text")
+        });
+
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        let output = i.access().unwrap().read_to_string().unwrap();
+
+        assert_eq!(output, "lazy text");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        i.access().unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2)
+    }
+
+    #[test]
+    fn validate_nonempty_empty_file() {
+        use crate::error::access::Kind;
+
+        let i = Input::with_defaults(fixture_spec("empty.txt")).unwrap();
+
+        let err = i.validate_nonempty().unwrap_err();
+
+        assert_eq!(err.kind(), Kind::EmptyFile)
+    }
+
+    #[test]
+    fn validate_nonempty_file() {
+        let i = Input::with_defaults(fixture_spec("utf16le.txt")).unwrap();
+
+        assert!(i.validate_nonempty().is_ok())
+    }
+
+    #[test]
+    fn validate_nonempty_missing_file() {
+        use crate::error::access::Kind;
+
+        let i = Input::with_defaults(fixture_spec("does_not_exist.txt")).unwrap();
+
+        let err = i.validate_nonempty().unwrap_err();
+
+        assert_eq!(err.kind(), Kind::File)
+    }
+
+    #[test]
+    fn validate_nonempty_files() {
+        use crate::error::access::Kind;
+
+        let files = |names: &[&str]| {
+            let paths = names
+                .iter()
+                .map(|name| FilePath::new(fixture(name)))
+                .collect();
+
+            Input::with_options(InputType::Files(paths), Default::default())
+        };
+
+        assert!(files(&["lorem.txt", "utf8.txt"])
+            .validate_nonempty()
+            .is_ok());
+
+        let err = files(&["lorem.txt", "empty.txt"])
+            .validate_nonempty()
+            .unwrap_err();
+        assert_eq!(err.kind(), Kind::EmptyFile);
+        assert!(err.to_string().contains("empty.txt"))
+    }
+
+    #[test]
+    fn validate_nonempty_open_file() {
+        use crate::error::access::Kind;
+
+        let open = |name: &str| std::fs::File::open(fixture(name)).unwrap();
+
+        let i = Input::from_open_file(open("empty.txt"));
+        assert_eq!(i.validate_nonempty().unwrap_err().kind(), Kind::EmptyFile);
+
+        let i = Input::from_open_file(open("lorem.txt"));
+        assert!(i.validate_nonempty().is_ok())
+    }
+
+    #[cfg(feature = "zip")]
+    #[test]
+    fn validate_nonempty_archive_member() {
+        use crate::parsers::File;
+
+        let cfg = Builder::new()
+            .with(|this| this.with_file(File::new().with(|f| f.archive_separator("!"))))
+            .build();
+        let i = cfg
+            .parse(&format!("{}!no/such/member", fixture_spec("archive.zip")))
+            .unwrap();
+
+        // The archive itself isn't empty, and the member isn't looked up
+        assert!(i.validate_nonempty().is_ok())
+    }
+
+    #[test]
+    fn validate_nonempty_text() {
+        let i = Input::with_defaults("").unwrap();
+
+        assert!(i.validate_nonempty().is_ok())
+    }
+
+    /// XORs every byte it reads with a fixed key, so that applying it twice is a no-op
+    struct Xor<R>(R);
+
+    impl<R: io::Read> io::Read for Xor<R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.0.read(buf)?;
+            buf[..n].iter_mut().for_each(|b| *b ^= 0x5A);
+
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn with_decoder_xor() {
+        let plain = "some secret text";
+        let mut cipher = Vec::new();
+        io::Read::read_to_end(&mut Xor(plain.as_bytes()), &mut cipher).unwrap();
+
+        let cfg = Builder::new()
+            .with(|this| {
+                this.text()
+                    .stdin()
+                    .with_decoder(Arc::new(|source| Box::new(Xor(source))))
+                    .wrap_content("<", ">")
+            })
+            .build();
+        let i = cfg.parse("-").unwrap();
+
+        let stdin = FakeStdin::new(cipher);
+        assert_eq!(read_from(&i, &stdin).unwrap(), "<some secret text>");
+
+        // The raw source is left untouched
+        let i = cfg.parse(plain).unwrap();
+        let mut raw = String::new();
+        io::Read::read_to_string(&mut i.access_enum().unwrap(), &mut raw).unwrap();
+        assert_eq!(raw, plain)
+    }
+
+    #[test]
+    fn with_decoder_lines_rev() {
+        let dir = TempDir::new("decoder-rev");
+
+        let mut cipher = Vec::new();
+        io::Read::read_to_end(&mut Xor(&b"one\ntwo\nthree\n"[..]), &mut cipher).unwrap();
+        let path = dir.write("cipher", cipher);
+
+        let cfg = Builder::new()
+            .with(|this| {
+                this.file()
+                    .with_decoder(Arc::new(|source| Box::new(Xor(source))))
+            })
+            .build();
+        let lines = cfg
+            .parse(&format!("@{}", path.display()))
+            .unwrap()
+            .access_lines_rev()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>();
+
+        assert_eq!(lines.unwrap(), ["three", "two", "one"])
+    }
+
+    fn wrap_config() -> Config {
+        Builder::new()
+            .with(|this| this.text().stdin().file().wrap_content("<<", ">>"))
+            .build()
+    }
+
+    #[test]
+    fn wrap_content_text() {
+        let i = wrap_config().parse("some text").unwrap();
+
+        let output = i.access().unwrap().read_to_string().unwrap();
+
+        assert_eq!(output, "<<some text>>")
+    }
+
+    #[test]
+    fn wrap_content_file() {
+        use crate::parsers::File;
+
+        let cfg = Builder::new()
+            .with(|this| {
+                this.with_file(File::new().with(|f| f.utf16(Endianness::Auto)))
+                    .wrap_content("<<", ">>")
+            })
+            .build();
+        let i = cfg.parse(&fixture_spec("utf16le.txt")).unwrap();
+
+        let output = i.access().unwrap().read_to_string().unwrap();
+
+        assert_eq!(output, "<<hello, wörld\n>>")
+    }
+
+    #[test]
+    fn wrap_content_file_bytes() {
+        let i = wrap_config().parse(&fixture_spec("empty.txt")).unwrap();
+
+        let mut output = Vec::new();
+        io::Read::read_to_end(&mut i.access().unwrap(), &mut output).unwrap();
+
+        assert_eq!(output, b"<<>>")
+    }
+
+    #[test]
+    fn wrap_content_stdin() {
+        let i = wrap_config().parse("-").unwrap();
+        assert!(i.access().unwrap().wrap.is_some());
+
+        // Stand in for stdin, rather than blocking on the real thing
+        let mut stdin = io::Cursor::new("piped\n");
+        let mut wrap = Wrap::new("<<", ">>");
+        let mut output = Vec::new();
+        let mut buf = [0; 3];
+
+        loop {
+            match wrap.read(&mut stdin, &mut buf).unwrap() {
+                0 => break,
+                n => output.extend_from_slice(&buf[..n]),
+            }
+        }
+
+        assert_eq!(output, b"<<piped\n>>")
+    }
+
+    fn limit_config(max: usize) -> Config {
+        Builder::new()
+            .with(|this| this.text().file().max_bytes(max))
+            .build()
+    }
+
+    #[test]
+    fn max_bytes_under_limit() {
+        let i = limit_config(256)
+            .parse(&fixture_spec("binary.bin"))
+            .unwrap();
+        assert_eq!(i.read_to_bytes().unwrap().len(), 256);
+
+        let i = limit_config(5).parse("12345").unwrap();
+        assert_eq!(i.read_to_string().unwrap(), "12345")
+    }
+
+    #[test]
+    fn max_bytes_over_limit() {
+        use crate::error::access::Kind;
+
+        let i = limit_config(255)
+            .parse(&fixture_spec("binary.bin"))
+            .unwrap();
+        assert_eq!(i.read_to_bytes().unwrap_err().kind(), Kind::LimitExceeded);
+
+        let i = limit_config(4).parse("12345").unwrap();
+        let err = i.read_to_string().unwrap_err();
+        assert_eq!(err.kind(), Kind::LimitExceeded);
+        assert!(err.to_string().contains("larger than 4 bytes"));
+
+        // Raw reads through access() are limited too
+        let mut buf = [0u8; 16];
+        let mut reader = i.access().unwrap();
+        assert_eq!(io::Read::read(&mut reader, &mut buf).unwrap(), 4);
+        assert!(io::Read::read(&mut reader, &mut buf).is_err())
+    }
+
+    #[test]
+    fn access_with_limit_over() {
+        use crate::error::access::Kind;
+
+        let i = Input::with_defaults(fixture_spec("lorem.txt")).unwrap();
+        let mut buf = Vec::new();
+
+        let err = io::Read::read_to_end(&mut i.access_with_limit(64).unwrap(), &mut buf)
+            .map_err(AccessError::read)
+            .unwrap_err();
+
+        assert_eq!(err.kind(), Kind::LimitExceeded);
+        assert_eq!(buf.len(), 64)
+    }
+
+    #[test]
+    fn access_with_limit_keeps_lower() {
+        let i = limit_config(4).parse("12345").unwrap();
+        let mut buf = Vec::new();
+
+        let result = io::Read::read_to_end(&mut i.access_with_limit(16).unwrap(), &mut buf);
+
+        assert!(result.is_err());
+        assert_eq!(buf, b"1234")
+    }
+
+    #[test]
+    fn max_bytes_buffered_stdin() {
+        use crate::error::access::Kind;
+
+        let cfg = Builder::new()
+            .with(|this| this.stdin().buffer_stdin(true).max_bytes(3))
+            .build();
+        let stdin = FakeStdin::new("too long");
+
+        let i = cfg.parse("-").unwrap();
+
+        assert_eq!(
+            i.access_from(&stdin).unwrap_err().kind(),
+            Kind::LimitExceeded
+        )
+    }
+
+    #[cfg(feature = "zip")]
+    #[test]
+    fn input_archive_member() {
+        use crate::{error::access::Kind, parsers::File};
+
+        let cfg = Builder::new()
+            .with(|this| this.with_file(File::new().with(|f| f.archive_separator("!"))))
+            .build();
+
+        let i = cfg
+            .parse(&format!("{}!lorem.txt", fixture_spec("archive.zip")))
+            .unwrap();
+        let expected = Input::with_defaults(fixture_spec("lorem.txt")).unwrap();
+
+        assert_eq!(
+            i.read_to_bytes().unwrap(),
+            expected.read_to_bytes().unwrap()
+        );
+
+        let i = cfg
+            .parse(&format!("{}!missing.txt", fixture_spec("archive.zip")))
+            .unwrap();
+
+        assert_eq!(i.access().unwrap_err().kind(), Kind::ArchiveMember)
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn input_decompress_zstd() {
+        use crate::parsers::File;
+
+        let cfg = Builder::new()
+            .with(|this| this.with_file(File::new().with(|f| f.decompress_zstd(true))))
+            .build();
+        let expected = Input::with_defaults(fixture_spec("lorem.txt"))
+            .unwrap()
+            .read_to_bytes()
+            .unwrap();
+
+        let i = cfg.parse(&fixture_spec("compressed.txt.zst")).unwrap();
+        assert_eq!(i.read_to_bytes().unwrap(), expected);
+        assert_eq!(i.len().unwrap(), None);
+
+        // Files without a .zst extension are read as is
+        let i = cfg.parse(&fixture_spec("lorem.txt")).unwrap();
+        assert_eq!(i.read_to_bytes().unwrap(), expected);
+
+        // ...as are .zst files when the option is unset
+        let i = Input::with_defaults(fixture_spec("compressed.txt.zst")).unwrap();
+        assert_ne!(i.read_to_bytes().unwrap(), expected)
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn input_decompress_gzip() {
+        use crate::parsers::File;
+
+        let cfg = Builder::new()
+            .with(|this| this.with_file(File::new().with(|f| f.decompress_gzip(true))))
+            .build();
+        let expected = Input::with_defaults(fixture_spec("lorem.txt"))
+            .unwrap()
+            .read_to_bytes()
+            .unwrap();
+
+        let i = cfg.parse(&fixture_spec("compressed.txt.gz")).unwrap();
+        assert_eq!(i.read_to_bytes().unwrap(), expected);
+
+        // Files without a .gz extension are read as is
+        let i = cfg.parse(&fixture_spec("lorem.txt")).unwrap();
+        assert_eq!(i.read_to_bytes().unwrap(), expected);
+
+        // ...as are .gz files when the option is unset
+        let i = Input::with_defaults(fixture_spec("compressed.txt.gz")).unwrap();
+        assert_ne!(i.read_to_bytes().unwrap(), expected)
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn input_decompress_gzip_limit() {
+        use crate::{compression::gzip::test_data, error::access::Kind, parsers::File};
+
+        let cfg = Builder::new()
+            .with(|this| {
+                this.with_file(File::new().with(|f| f.decompress_gzip(true)))
+                    .max_bytes(1024 * 1024)
+            })
+            .build();
+        let dir = TempDir::new("zeros");
+
+        // 256MiB once decompressed, which is only ever decompressed up to the limit
+        let path = dir.write("zeros.gz", test_data::zeros(256 * 1024 * 1024, false));
+
+        let i = cfg.parse(&format!("@{}", path.display())).unwrap();
+        let err = i.read_to_bytes().unwrap_err();
+
+        assert_eq!(err.kind(), Kind::LimitExceeded)
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn input_mmap() {
+        use crate::parsers::File;
+
+        let cfg = Builder::new()
+            .with(|this| this.with_file(File::new().with(|f| f.mmap(true))))
+            .build();
+        let expected = Input::with_defaults(fixture_spec("lorem.txt"))
+            .unwrap()
+            .read_to_bytes()
+            .unwrap();
+
+        let i = cfg.parse(&fixture_spec("lorem.txt")).unwrap();
+        assert!(matches!(i.access_enum(), Ok(RawReader::Mapped(_))));
+        assert_eq!(i.read_to_bytes().unwrap(), expected);
+
+        // Empty files can't be mapped, and are read as normal
+        let i = cfg.parse(&fixture_spec("empty.txt")).unwrap();
+        assert!(matches!(i.access_enum(), Ok(RawReader::File(_))));
+        assert_eq!(i.read_to_bytes().unwrap(), b"")
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn input_stdin_auto_decompress() {
+        use crate::parsers::Stdin;
+
+        let cfg = Builder::new()
+            .with(|this| this.with_stdin(Stdin::new().with(|s| s.auto_decompress(true))))
+            .build();
+        let compressed = std::fs::read(fixture("compressed.txt.gz")).unwrap();
+        let expected = Input::with_defaults(fixture_spec("lorem.txt"))
+            .unwrap()
+            .read_to_bytes()
+            .unwrap();
+
+        let i = cfg.parse("-").unwrap();
+        assert_eq!(
+            bytes_from(&i, &FakeStdin::new(compressed)).unwrap(),
+            expected
+        );
+
+        // Plain data passes through untouched, including the bytes peeked at
+        let i = cfg.parse("-").unwrap();
+        assert_eq!(
+            read_from(&i, &FakeStdin::new("plain text")).unwrap(),
+            "plain text"
+        );
+
+        let i = cfg.parse("-").unwrap();
+        let mut raw = RawReader::from(i.source_from(&FakeStdin::new("p")).unwrap());
+        assert!(matches!(raw, RawReader::Peeked(_)));
+        let mut buf = String::new();
+        io::Read::read_to_string(&mut raw, &mut buf).unwrap();
+        assert_eq!(buf, "p")
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn input_stdin_auto_decompress_limit() {
+        use crate::{compression::gzip::test_data, error::access::Kind, parsers::Stdin};
+
+        let cfg = Builder::new()
+            .with(|this| {
+                this.with_stdin(Stdin::new().with(|s| s.auto_decompress(true)))
+                    .max_bytes(1024 * 1024)
+            })
+            .build();
+        // 256MiB once decompressed
+        let compressed = test_data::zeros(256 * 1024 * 1024, false);
+        let len = compressed.len();
+
+        let i = cfg.parse("-").unwrap();
+        assert!(matches!(
+            i.source_from(&FakeStdin::new(compressed)),
+            Ok(Read::Decompressed(_))
+        ));
+
+        let stdin = FakeStdin::new(test_data::zeros(256 * 1024 * 1024, false));
+        let i = cfg.parse("-").unwrap();
+        assert_eq!(
+            bytes_from(&i, &stdin).unwrap_err().kind(),
+            Kind::LimitExceeded
+        );
+
+        // Reading stopped at the limit, leaving most of stdin unread
+        let mut rest = Vec::new();
+        io::Read::read_to_end(&mut stdin.open(), &mut rest).unwrap();
+        assert!(
+            rest.len() > len / 2,
+            "{} of {} bytes read",
+            len - rest.len(),
+            len
+        )
+    }
+
+    #[test]
+    fn input_stdin_require_nonempty() {
+        use crate::{error::access::Kind, parsers::Stdin};
+
+        let cfg = Builder::new()
+            .with(|this| this.with_stdin(Stdin::new().with(|s| s.require_nonempty(true))))
+            .build();
+
+        let i = cfg.parse("-").unwrap();
+        assert_eq!(
+            i.access_from(&FakeStdin::new("")).unwrap_err().kind(),
+            Kind::EmptyStdin
+        );
+
+        let i = cfg.parse("-").unwrap();
+        assert_eq!(
+            read_from(&i, &FakeStdin::new("piped text")).unwrap(),
+            "piped text"
+        )
+    }
+
+    #[test]
+    fn input_eof_on_broken_pipe() {
+        let cfg = Builder::new()
+            .with(|this| this.stdin().eof_on_broken_pipe(true))
+            .build();
+        let stdin = FakeStdin::failing("partial", io::ErrorKind::BrokenPipe);
+
+        let i = cfg.parse("-").unwrap();
+
+        assert_eq!(read_from(&i, &stdin).unwrap(), "partial")
+    }
+
+    #[test]
+    fn input_broken_pipe_is_error_by_default() {
+        use crate::error::access::Kind;
+
+        let stdin = FakeStdin::failing("partial", io::ErrorKind::BrokenPipe);
+
+        let i = Input::with_defaults("-").unwrap();
+
+        assert_eq!(read_from(&i, &stdin).unwrap_err().kind(), Kind::Read)
+    }
+
+    #[test]
+    fn input_decompressed_size_hint() {
+        let i = Input::with_defaults(fixture_spec("compressed.txt.gz")).unwrap();
+
+        // Without decompression enabled, the file is read as is
+        assert_eq!(i.decompressed_size_hint(), None);
+
+        #[cfg(feature = "gzip")]
+        {
+            use crate::parsers::File;
+
+            let cfg = Builder::new()
+                .with(|this| this.with_file(File::new().with(|f| f.decompress_gzip(true))))
+                .build();
+            let expected = std::fs::metadata(fixture("lorem.txt")).unwrap().len();
+
+            let i = cfg.parse(&fixture_spec("compressed.txt.gz")).unwrap();
+            assert_eq!(i.decompressed_size_hint(), Some(expected));
+
+            let i = cfg.parse(&fixture_spec("lorem.txt")).unwrap();
+            assert_eq!(i.decompressed_size_hint(), None)
+        }
+
+        #[cfg(feature = "zstd")]
+        {
+            use crate::parsers::File;
+
+            let cfg = Builder::new()
+                .with(|this| this.with_file(File::new().with(|f| f.decompress_zstd(true))))
+                .build();
+            let expected = std::fs::metadata(fixture("lorem.txt")).unwrap().len();
+
+            let i = cfg.parse(&fixture_spec("compressed.txt.zst")).unwrap();
+            assert_eq!(i.decompressed_size_hint(), Some(expected));
+
+            // Without decompression enabled, the file is read as is
+            let i = Input::with_defaults(fixture_spec("compressed.txt.zst")).unwrap();
+            assert_eq!(i.decompressed_size_hint(), None)
+        }
+    }
+
+    #[test]
+    fn input_env() {
+        use crate::error::access::Kind;
+
+        let cfg = Builder::new().with(|this| this.env()).build();
+
+        // Set by cargo for every test it runs
+        let i = cfg.parse("env:CARGO_MANIFEST_DIR").unwrap();
+        assert_eq!(i.read_to_string().unwrap(), env!("CARGO_MANIFEST_DIR"));
+
+        let i = cfg.parse("env:GRAB_TEST_INPUT_ENV_UNSET").unwrap();
+        assert_eq!(i.access().unwrap_err().kind(), Kind::Env)
+    }
+
+    #[cfg(not(feature = "http"))]
+    #[test]
+    fn input_url_requires_feature() {
+        use crate::error::access::Kind;
+
+        let cfg = Builder::new().with(|this| this.url()).build();
+
+        let i = cfg.parse("http://127.0.0.1/").unwrap();
+
+        assert_eq!(i.access().unwrap_err().kind(), Kind::Url)
+    }
+
+    #[cfg(feature = "tcp")]
+    #[test]
+    fn input_tcp() {
+        use crate::error::access::Kind;
+        use std::{io::Write, net::TcpListener};
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            stream.write_all(b"hello over tcp").unwrap();
+        });
+
+        let cfg = Builder::new().with(|this| this.tcp()).build();
+        let i = cfg.parse(&format!("tcp://{}", addr)).unwrap();
+
+        assert_eq!(i.kind(), InputKind::Tcp);
+        assert_eq!(i.to_string(), format!("tcp://{}", addr));
+        assert_eq!(i.read_to_string().unwrap(), "hello over tcp");
+        server.join().unwrap();
+
+        // Nothing listens on the port any more
+        assert_eq!(i.access().unwrap_err().kind(), Kind::Tcp)
+    }
+
+    #[cfg(grab_nightly)]
+    #[test]
+    fn input_reader_read_buf() {
+        use std::mem::MaybeUninit;
+
+        let i = Input::with_defaults(fixture_spec("lorem.txt")).unwrap();
+        let expected = i.read_to_bytes().unwrap();
+
+        let mut reader = i.access().unwrap();
+        let mut output = Vec::new();
+        let mut storage = [MaybeUninit::uninit(); 100];
+
+        loop {
+            let mut buf = io::BorrowedBuf::from(&mut storage[..]);
+            io::Read::read_buf(&mut reader, buf.unfilled()).unwrap();
+
+            match buf.filled() {
+                [] => break,
+                filled => output.extend_from_slice(filled),
+            }
+        }
+
+        assert_eq!(output, expected)
+    }
+
+    #[test]
+    fn input_reader_fallback_encoding() {
+        use crate::parsers::File;
+
+        let latin1 = Encoding::for_label(b"latin1").unwrap();
+        let cfg = Builder::new()
+            .with(|this| this.with_file(File::new().with(|f| f.fallback_encoding(latin1))))
+            .build();
+
+        for name in &["latin1.txt", "utf8.txt"] {
+            let i = cfg.parse(&fixture_spec(name)).unwrap();
+
+            assert_eq!(i.read_to_string().unwrap(), "hello, wörld\n", "{}", name)
+        }
+
+        // Without a fallback, latin-1 isn't valid UTF-8
+        let i = Input::with_defaults(fixture_spec("latin1.txt")).unwrap();
+        assert!(i.read_to_string().is_err())
+    }
+
+    fn utf16_config(endianness: Endianness) -> Config {
+        use crate::parsers::File;
+
+        Builder::new()
+            .with(|this| this.with_file(File::new().with(|f| f.utf16(endianness))))
+            .build()
+    }
+
+    #[test]
+    fn input_reader_utf16le_bom() {
+        let cfg = utf16_config(Endianness::Auto);
+        let i = cfg.parse(&fixture_spec("utf16le.txt")).unwrap();
+
+        let output = i.access().unwrap().read_to_string().unwrap();
+
+        assert_eq!(output, "hello, wörld\n")
+    }
+
+    #[test]
+    fn input_reader_utf16be_bom() {
+        let cfg = utf16_config(Endianness::Auto);
+        let i = cfg.parse(&fixture_spec("utf16be.txt")).unwrap();
+
+        let output = i.access().unwrap().read_to_string().unwrap();
+
+        assert_eq!(output, "hello, wörld\n")
+    }
+
+    #[test]
+    fn input_reader_utf16_configured_endianness() {
+        let i = utf16_config(Endianness::Big)
+            .parse(&fixture_spec("utf16be.txt"))
+            .unwrap();
+
+        let output = i.access().unwrap().read_to_string().unwrap();
+
+        assert_eq!(output, "hello, wörld\n")
+    }
+
+    #[test]
+    fn decode_utf16_no_bom() {
+        let le: Vec<u8> = "hi".encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let be: Vec<u8> = "hi".encode_utf16().flat_map(u16::to_be_bytes).collect();
+
+        assert_eq!(decode_utf16(&le, Endianness::Auto).unwrap(), "hi");
+        assert_eq!(decode_utf16(&le, Endianness::Little).unwrap(), "hi");
+        assert_eq!(decode_utf16(&be, Endianness::Big).unwrap(), "hi");
+    }
+
+    #[test]
+    fn decode_utf16_odd_length() {
+        let err = decode_utf16(&[0xFF, 0xFE, 0x68], Endianness::Auto).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData)
     }
 }
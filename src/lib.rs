@@ -1,4 +1,5 @@
 #![warn(missing_docs)]
+#![cfg_attr(grab_nightly, feature(read_buf, core_io_borrowed_buf))]
 //! This library contains types for supercharging and streamlining grabbing input from the
 //! command line. Gone are the days needing to messily handle whether the user wants you to read
 //! their input from stdin, or a file, or directly from the argument. Welcome to the future.
@@ -104,12 +105,49 @@
 //!
 //! There we have it. A custom parser which you can use however you like (you monster)!
 
+mod ansi;
+#[cfg(feature = "argfile")]
+mod argfile;
 mod builder;
+#[cfg(feature = "clipboard")]
+mod clipboard;
+#[cfg(any(feature = "zip", feature = "gzip", feature = "zstd"))]
+mod compression;
+#[cfg(feature = "glob")]
+mod glob;
+#[cfg(feature = "http")]
+mod http;
 mod input;
+#[cfg(feature = "clap")]
+mod matches;
+#[cfg(feature = "parallel")]
+mod parallel;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "tcp")]
+mod tcp;
+#[cfg(test)]
+mod test_support;
 
+#[cfg(feature = "clap")]
+pub mod clap;
 pub mod error;
 pub mod parsers;
 
-pub use input::{Input, InputReader};
+pub use input::{FileChain, Input, InputKind, InputReader, RawReader, ReaderHandle};
 
-pub use builder::{Builder, Config};
+#[cfg(feature = "mmap")]
+pub use input::MappedFile;
+
+#[cfg(feature = "http")]
+pub use input::UrlBody;
+
+pub use input::PeekedStdin;
+
+pub use builder::{Builder, Config, Interpretation, Order};
+
+#[cfg(feature = "parallel")]
+pub use parallel::open_all_parallel;
+
+#[cfg(feature = "clap")]
+pub use matches::from_matches;
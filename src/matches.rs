@@ -0,0 +1,131 @@
+//! Parsing arguments already matched by [clap], see [from_matches] and [Config::parse_matches].
+
+use crate::{
+    error::input::{EKind, InputError},
+    Config, Input,
+};
+
+/// Parse the value of the argument with the given id from clap's matches with the
+/// [default][Config::default] config. See [Config::parse_matches] to use a custom config.
+///
+/// ```
+/// use clap::{Arg, Command};
+///
+/// let matches = Command::new("cli")
+///     .arg(Arg::new("input"))
+///     .get_matches_from(["cli", "-"]);
+///
+/// let input = grab::from_matches(&matches, "input").unwrap();
+///
+/// assert!(input.is_stdin());
+/// ```
+pub fn from_matches(m: &::clap::ArgMatches, id: &str) -> Result<Input, InputError> {
+    Config::default().parse_matches(m, id)
+}
+
+impl Config {
+    /// Parse the value of the argument with the given id from clap's matches with this config.
+    /// The raw [OsStr][std::ffi::OsStr] value is parsed with [parse_os][Config::parse_os], so
+    /// arguments which aren't valid UTF-8 can still name a file.
+    ///
+    /// This is an alternative to parsing arguments as they are matched, for extracting them
+    /// afterwards instead. If the argument wasn't given, this fails with an error containing
+    /// [EKind::MISSING_VALUE], as it does if clap doesn't know an argument with the id.
+    ///
+    /// ```
+    /// use clap::{Arg, Command};
+    /// use grab::Config;
+    ///
+    /// let matches = Command::new("cli")
+    ///     .arg(Arg::new("input"))
+    ///     .get_matches_from(["cli", "@notes.txt"]);
+    ///
+    /// let input = Config::default().parse_matches(&matches, "input").unwrap();
+    ///
+    /// assert!(input.is_file());
+    /// ```
+    pub fn parse_matches(&self, m: &::clap::ArgMatches, id: &str) -> Result<Input, InputError> {
+        let value = m
+            .try_get_raw(id)
+            .ok()
+            .flatten()
+            .and_then(|mut values| values.next());
+        let value = value.ok_or_else(|| {
+            InputError::new(EKind::MISSING_VALUE)
+                .with(|this| this.add_context(format!("no value given for {}", id)))
+        })?;
+
+        self.parse_os(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ::clap::{Arg, ArgMatches, Command};
+
+    fn matches(args: &[&str]) -> ArgMatches {
+        Command::new("cli")
+            .arg(Arg::new("input"))
+            .get_matches_from(args)
+    }
+
+    #[test]
+    fn parse_matches() {
+        let m = matches(&["cli", "@some/file"]);
+
+        let input = Config::default().parse_matches(&m, "input").unwrap();
+
+        assert_eq!(input.path(), Some(std::path::Path::new("some/file")))
+    }
+
+    #[test]
+    fn from_matches_default() {
+        let m = matches(&["cli", "@some/file"]);
+
+        let input = from_matches(&m, "input").unwrap();
+        assert_eq!(input.path(), Some(std::path::Path::new("some/file")));
+
+        let err = from_matches(&matches(&["cli"]), "input").unwrap_err();
+        assert!(err.contains(EKind::MISSING_VALUE))
+    }
+
+    #[test]
+    fn parse_matches_config() {
+        let m = matches(&["cli", "some text"]);
+
+        let cfg = crate::Builder::new().with(|this| this.file()).build();
+        let err = cfg.parse_matches(&m, "input").unwrap_err();
+
+        assert!(err.contains(EKind::FILE))
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parse_matches_non_utf8() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let m = Command::new("cli")
+            .arg(Arg::new("input").value_parser(::clap::value_parser!(std::ffi::OsString)))
+            .get_matches_from([OsStr::new("cli"), OsStr::from_bytes(b"@caf\xe9")]);
+
+        let input = Config::default().parse_matches(&m, "input").unwrap();
+
+        assert_eq!(
+            input.path().unwrap().as_os_str(),
+            OsStr::from_bytes(b"caf\xe9")
+        )
+    }
+
+    #[test]
+    fn parse_matches_missing() {
+        let m = matches(&["cli"]);
+
+        let err = Config::default().parse_matches(&m, "input").unwrap_err();
+        assert!(err.contains(EKind::MISSING_VALUE));
+
+        let err = Config::default().parse_matches(&m, "unknown").unwrap_err();
+        assert!(err.contains(EKind::MISSING_VALUE))
+    }
+}
@@ -0,0 +1,113 @@
+//! Opening several inputs at once, see [open_all_parallel].
+
+use crate::{
+    error::access::AccessError,
+    input::{ProcessStdin, StdinSource},
+    Input, InputReader,
+};
+
+use std::thread;
+
+/// Access every input concurrently, returning their readers (or the error accessing each) in the
+/// same order as the inputs. This is mostly useful for programs given many file arguments, where
+/// opening (or for URLs, fetching) each in turn is slow.
+///
+/// Inputs are split across up to [available_parallelism][thread::available_parallelism]
+/// threads. Stdin inputs are always accessed on the current thread, after the others, as stdin
+/// can only be consumed once and there is nothing to gain from accessing it concurrently.
+pub fn open_all_parallel(inputs: Vec<Input>) -> Vec<Result<InputReader, AccessError>> {
+    open_all_from(inputs, &ProcessStdin)
+}
+
+/// Like [open_all_parallel], but stdin inputs read from the given stdin
+fn open_all_from(
+    inputs: Vec<Input>,
+    stdin: &dyn StdinSource,
+) -> Vec<Result<InputReader, AccessError>> {
+    let mut results: Vec<Option<Result<InputReader, AccessError>>> =
+        inputs.iter().map(|_| None).collect();
+
+    let concurrent: Vec<_> = inputs
+        .iter()
+        .zip(results.iter_mut())
+        .filter(|(input, _)| !input.is_stdin())
+        .collect();
+
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let per_thread = concurrent.len().div_ceil(threads).max(1);
+
+    thread::scope(|scope| {
+        let mut pending = concurrent;
+
+        while !pending.is_empty() {
+            let rest = pending.split_off(per_thread.min(pending.len()));
+            let chunk = std::mem::replace(&mut pending, rest);
+
+            scope.spawn(move || {
+                for (input, result) in chunk {
+                    *result = Some(input.access());
+                }
+            });
+        }
+    });
+
+    inputs
+        .iter()
+        .zip(results)
+        .map(|(input, result)| result.unwrap_or_else(|| input.access_from(stdin)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        error::access::Kind,
+        test_support::{self, FakeStdin},
+    };
+
+    fn fixture(name: &str) -> Input {
+        Input::with_defaults(format!("@{}", test_support::fixture(name).display())).unwrap()
+    }
+
+    #[test]
+    fn open_many_files() {
+        let names = ["lorem.txt", "empty.txt", "records.dat"];
+        let inputs: Vec<_> = names.iter().cycle().take(100).map(|n| fixture(n)).collect();
+
+        let expected: Vec<_> = inputs.iter().map(|i| i.read_to_bytes().unwrap()).collect();
+
+        let readers = open_all_parallel(inputs);
+        assert_eq!(readers.len(), 100);
+
+        for (reader, expected) in readers.into_iter().zip(expected) {
+            let mut buf = Vec::new();
+            std::io::Read::read_to_end(&mut reader.unwrap(), &mut buf).unwrap();
+
+            assert_eq!(buf, expected)
+        }
+    }
+
+    #[test]
+    fn open_mixed_inputs() {
+        let inputs = vec![
+            fixture("empty.txt"),
+            Input::with_defaults("-").unwrap(),
+            fixture("no_such_file"),
+            Input::with_defaults("some text").unwrap(),
+        ];
+
+        let mut results = open_all_from(inputs, &FakeStdin::new("piped")).into_iter();
+
+        assert!(results.next().unwrap().is_ok());
+        // Stdin is read from the given stdin
+        let mut stdin = results.next().unwrap().unwrap();
+        assert_eq!(stdin.read_to_string().unwrap(), "piped");
+        assert_eq!(results.next().unwrap().err().unwrap().kind(), Kind::File);
+        assert_eq!(
+            results.next().unwrap().unwrap().read_to_string().unwrap(),
+            "some text"
+        );
+    }
+}
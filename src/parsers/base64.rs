@@ -0,0 +1,380 @@
+use super::{
+    failed_at,
+    nom::{self, Finish},
+    EKind, InputError, InputType, Named, NomError, Parser, ParserName, Weight,
+};
+
+use std::fmt;
+
+/// Function signature of the parser Base64 calls for processing input
+pub type Base64Parser = for<'a, 'b> fn(&'a str, &'b str) -> nom::IResult<&'a str, String>;
+
+/// Construct for treating the given input as base64 encoded data, e.g `base64:aGVsbG8=`. The
+/// payload is decoded when the input is parsed, so malformed base64 is reported as an
+/// [InputError], and reading the input produces the decoded bytes.
+///
+/// Both padded and unpadded payloads are accepted. By default the standard alphabet is used,
+/// see [url_safe][Base64::url_safe] for the URL and filename safe alphabet.
+#[derive(Clone, Default)]
+pub struct Base64 {
+    marker: Option<String>,
+    parser: Option<Base64Parser>,
+    weight: Option<u8>,
+    label: Option<String>,
+    url_safe: bool,
+}
+
+impl Base64 {
+    /// The default weighting for [Base64]
+    pub const DEFAULT_WEIGHT: u8 = 150;
+    /// Default marker for [Base64]
+    pub const DEFAULT_MARKER: &str = "base64:";
+    /// Default parser implementation for [Base64]
+    pub const DEFAULT_PARSER: Base64Parser = default_base64_parser;
+
+    /// Instantiate a new Base64 parser with sensible defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convenience function for modifying the semantics of
+    /// this parser
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use grab::parsers::Base64;
+    ///
+    /// // Use a shorter marker, e.g b64:aGVsbG8=
+    /// let base64 = Base64::new().with(|this| this.marker("b64:"));
+    /// ```
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnMut(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        let mut actions = f;
+
+        actions(&mut this);
+
+        this
+    }
+
+    /// Modify the marker string for triggering this Base64 parser.
+    /// This marker is passed to the parser function as the second &str
+    /// argument.
+    pub fn marker(&mut self, marker: impl AsRef<str>) -> &mut Self {
+        self.marker = Some(marker.as_ref().to_string());
+
+        self
+    }
+
+    /// Replace the parser for this Base64 with a different one. Expects a
+    /// _function_ (not closure) with the following arguments + return:
+    ///
+    /// fn my_parser<'a>(input: &'a str, marker: &str) -> crate::nom::IResult<&'a str, String>
+    /// {
+    ///     /* ... */
+    /// }
+    pub fn parser(&mut self, parser: Base64Parser) -> &mut Self {
+        self.parser = Some(parser);
+
+        self
+    }
+
+    /// Set this parser's weight. Lower numbers will be ran before greater.
+    pub fn weight(&mut self, weight: u8) -> &mut Self {
+        self.weight = Some(weight);
+
+        self
+    }
+
+    /// Attach a human readable label to this parser, which will be included in any
+    /// [InputError] it produces.
+    pub fn label(&mut self, label: impl AsRef<str>) -> &mut Self {
+        self.label = Some(label.as_ref().to_string());
+
+        self
+    }
+
+    /// Decode the payload with the URL and filename safe alphabet, which uses `-` and `_` in
+    /// place of `+` and `/`.
+    pub fn url_safe(&mut self, url_safe: bool) -> &mut Self {
+        self.url_safe = url_safe;
+
+        self
+    }
+
+    fn get_weight(&self) -> u8 {
+        self.weight.unwrap_or(Self::DEFAULT_WEIGHT)
+    }
+
+    fn get_marker(&self) -> &str {
+        self.marker.as_deref().unwrap_or(Self::DEFAULT_MARKER)
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<String, NomError<&'a str>> {
+        let marker = self.get_marker();
+
+        let (_, payload) = self
+            .parser
+            .map(|p| p(input, marker))
+            .unwrap_or_else(|| Self::DEFAULT_PARSER(input, marker))
+            .finish()?;
+
+        Ok(payload)
+    }
+
+    fn decode(&self, payload: &str) -> Result<Vec<u8>, InputError> {
+        let alphabet = match self.url_safe {
+            true => URL_SAFE,
+            false => STANDARD,
+        };
+
+        decode(payload, alphabet).ok_or_else(|| {
+            self.error()
+                .with(|this| this.add_context("payload is not valid base64"))
+        })
+    }
+
+    fn new_error(&self, p_error: NomError<&str>) -> InputError {
+        self.error()
+            .with(|this| this.add_context(failed_at(&p_error)))
+    }
+
+    fn error(&self) -> InputError {
+        let mut error = InputError::new(EKind::BASE64);
+
+        if let Some(ref label) = self.label {
+            error.add_label(label);
+        }
+
+        error
+    }
+}
+
+impl Parser for Base64 {
+    fn parse_str(&self, s: &str) -> Result<InputType, InputError> {
+        let payload = self.parse(s).map_err(|e| self.new_error(e))?;
+
+        self.decode(&payload).map(InputType::Base64)
+    }
+}
+
+impl Weight for Base64 {
+    fn weight(&self) -> u8 {
+        self.get_weight()
+    }
+}
+
+impl Named for Base64 {
+    fn name(&self) -> ParserName {
+        ParserName::Base64
+    }
+
+    fn marker(&self) -> &str {
+        self.get_marker()
+    }
+}
+
+impl fmt::Debug for Base64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Base64")
+            .field("marker", &self.get_marker())
+            .field(
+                "parser",
+                &self
+                    .parser
+                    .map_or("Default Base64Parser", |_| "Custom Base64Parser"),
+            )
+            .finish()
+    }
+}
+
+/// The two characters that differ between base64 alphabets, for values 62 and 63
+const STANDARD: [u8; 2] = [b'+', b'/'];
+const URL_SAFE: [u8; 2] = [b'-', b'_'];
+
+/// Encode bytes as padded base64 with the standard alphabet
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let mut acc = [0u8; 3];
+        acc[..chunk.len()].copy_from_slice(chunk);
+        let acc = u32::from_be_bytes([0, acc[0], acc[1], acc[2]]);
+
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => out.push(char::from(ALPHABET[(acc >> (18 - 6 * i) & 0x3f) as usize])),
+                false => out.push('='),
+            }
+        }
+    }
+
+    out
+}
+
+/// Decode base64, with or without padding, returning None if the input is malformed
+#[allow(clippy::manual_is_multiple_of)]
+fn decode(input: &str, alphabet: [u8; 2]) -> Option<Vec<u8>> {
+    let bytes = input.as_bytes();
+    let unpadded = input.trim_end_matches('=').as_bytes();
+    let padding = bytes.len() - unpadded.len();
+
+    // Padding, if present, must complete the final quantum
+    if padding > 2 || (padding > 0 && bytes.len() % 4 != 0) || unpadded.len() % 4 == 1 {
+        return None;
+    }
+
+    let value = |c: u8| match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        c if c == alphabet[0] => Some(62),
+        c if c == alphabet[1] => Some(63),
+        _ => None,
+    };
+
+    let mut out = Vec::with_capacity(unpadded.len() * 3 / 4);
+
+    for chunk in unpadded.chunks(4) {
+        let mut acc = 0u32;
+        for &c in chunk {
+            acc = (acc << 6) | u32::from(value(c)?);
+        }
+        acc <<= 6 * (4 - chunk.len() as u32);
+
+        let decoded = acc.to_be_bytes();
+        let len = chunk.len() - 1;
+
+        // Reject non-zero bits after the final byte, as they can't have come from an encoder
+        if decoded[1 + len..].iter().any(|&b| b != 0) {
+            return None;
+        }
+
+        out.extend_from_slice(&decoded[1..=len]);
+    }
+
+    Some(out)
+}
+
+/// Default parser for base64. It expects input starting with the 'marker', returning the rest of
+/// the input as the (still encoded) payload.
+pub fn default_base64_parser<'a>(input: &'a str, marker: &str) -> nom::IResult<&'a str, String> {
+    nom::context("BASE64", nom::tag(marker))(input).map(|(payload, _)| ("", String::from(payload)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BAD_INPUT: &str = "invalid base64 input";
+
+    #[test]
+    fn defaults_success() {
+        let cases: &[(&str, &[u8])] = &[
+            ("base64:aGVsbG8=", b"hello"),
+            ("base64:aGVsbG8", b"hello"),
+            ("base64:aGVsbG8h", b"hello!"),
+            ("base64:aGk=", b"hi"),
+            ("base64:aA==", b"h"),
+            ("base64:aA", b"h"),
+            ("base64:", b""),
+            ("base64:+/8=", &[0xfb, 0xff]),
+        ];
+
+        for (input, output) in cases {
+            let parser = Base64::new();
+
+            let result = parser.parse_str(input);
+
+            assert_eq!(result, Ok(InputType::Base64(output.to_vec())), "{}", input)
+        }
+    }
+
+    #[test]
+    fn defaults_failure() {
+        let cases = [
+            BAD_INPUT,
+            "base64:a",
+            "base64:aGVsbG8===",
+            "base64:aGk==",
+            "base64:a=GVsbG8",
+            "base64:-_8=",
+            "base64:aR==",
+        ];
+
+        for input in &cases[..] {
+            let parser = Base64::new();
+
+            let result = parser.parse_str(input);
+
+            assert!(result.unwrap_err().contains(EKind::BASE64), "{}", input)
+        }
+    }
+
+    #[test]
+    fn encode_round_trip() {
+        let cases: &[&[u8]] = &[b"", b"h", b"hi", b"hello", b"hello!", &[0xfb, 0xff]];
+
+        for bytes in cases {
+            assert_eq!(decode(&encode(bytes), STANDARD).as_deref(), Some(*bytes))
+        }
+
+        assert_eq!(encode(b"hello"), "aGVsbG8=")
+    }
+
+    #[test]
+    fn c_url_safe() {
+        let input = "base64:-_8";
+        let output = vec![0xfb, 0xff];
+
+        let parser = Base64::new().with(|this| this.url_safe(true));
+
+        let result = parser.parse_str(input);
+        assert_eq!(result, Ok(InputType::Base64(output)));
+
+        let result = parser.parse_str("base64:+/8=");
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn c_marker_success() {
+        let input = "b64:aGk=";
+
+        let parser = Base64::new().with(|this| this.marker("b64:"));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Ok(InputType::Base64(b"hi".to_vec())))
+    }
+
+    #[test]
+    fn c_marker_failure() {
+        let input = "base64:aGk=";
+
+        let parser = Base64::new().with(|this| this.marker("b64:"));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Err(EKind::BASE64.into()))
+    }
+
+    #[test]
+    fn c_parser_success() {
+        let input = "aGk=";
+
+        let parser = Base64::new().with(|this| this.parser(test_custom_parser));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Ok(InputType::Base64(b"hi".to_vec())))
+    }
+
+    fn test_custom_parser<'a>(input: &'a str, _: &str) -> nom::IResult<&'a str, String> {
+        Ok(("", input.to_string()))
+    }
+}
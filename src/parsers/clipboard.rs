@@ -0,0 +1,209 @@
+use super::{
+    failed_at,
+    nom::{self, Finish},
+    EKind, InputError, InputType, Named, NomError, Parser, ParserName, Weight,
+};
+
+use std::fmt;
+
+/// Function signature of the parser Clipboard calls for processing input
+pub type ClipboardParser = for<'a, 'b> fn(&'a str, &'b str) -> nom::IResult<&'a str, ()>;
+
+/// Construct for reading the text currently on the system clipboard in place of the given input.
+/// By default, this parser only accepts the input `clipboard`, with no other input.
+///
+/// The clipboard is read when the input is accessed, using the [arboard](::arboard) crate.
+/// Accessing the input fails if the clipboard can't be opened, e.g there is no display server,
+/// or if it doesn't hold any text.
+#[derive(Clone, Default)]
+pub struct Clipboard {
+    marker: Option<String>,
+    parser: Option<ClipboardParser>,
+    weight: Option<u8>,
+    label: Option<String>,
+}
+
+impl Clipboard {
+    /// The default weighting for [Clipboard], which sits between [Env][super::Env] and
+    /// [Base64][super::Base64]
+    pub const DEFAULT_WEIGHT: u8 = 147;
+    /// Default marker for [Clipboard]
+    pub const DEFAULT_MARKER: &str = "clipboard";
+    /// Default parser implementation for [Clipboard]
+    pub const DEFAULT_PARSER: ClipboardParser = default_clipboard_parser;
+
+    /// Instantiate a new Clipboard parser with sensible defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convenience function for modifying the semantics of
+    /// this parser
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use grab::parsers::Clipboard;
+    ///
+    /// // Read the clipboard with a shorter input
+    /// let clipboard = Clipboard::new().with(|this| this.marker("%"));
+    /// ```
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnMut(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        let mut actions = f;
+
+        actions(&mut this);
+
+        this
+    }
+
+    /// Modify the marker string for triggering this Clipboard parser.
+    /// This marker is passed to the parser function as the second &str
+    /// argument.
+    pub fn marker(&mut self, marker: impl AsRef<str>) -> &mut Self {
+        self.marker = Some(marker.as_ref().to_string());
+
+        self
+    }
+
+    /// Replace the parser for this Clipboard with a different one. Expects a
+    /// _function_ (not closure) with the following arguments + return:
+    ///
+    /// fn my_parser<'a>(input: &'a str, marker: &str) -> crate::nom::IResult<&'a str, ()>
+    /// {
+    ///     /* ... */
+    /// }
+    pub fn parser(&mut self, parser: ClipboardParser) -> &mut Self {
+        self.parser = Some(parser);
+
+        self
+    }
+
+    /// Set this parser's weight. Lower numbers will be ran before greater.
+    pub fn weight(&mut self, weight: u8) -> &mut Self {
+        self.weight = Some(weight);
+
+        self
+    }
+
+    /// Attach a human readable label to this parser, which will be included in any
+    /// [InputError] it produces.
+    pub fn label(&mut self, label: impl AsRef<str>) -> &mut Self {
+        self.label = Some(label.as_ref().to_string());
+
+        self
+    }
+
+    fn get_weight(&self) -> u8 {
+        self.weight.unwrap_or(Self::DEFAULT_WEIGHT)
+    }
+
+    fn get_marker(&self) -> &str {
+        self.marker.as_deref().unwrap_or(Self::DEFAULT_MARKER)
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<(), NomError<&'a str>> {
+        let marker = self.get_marker();
+
+        self.parser
+            .map(|p| p(input, marker))
+            .unwrap_or_else(|| Self::DEFAULT_PARSER(input, marker))
+            .finish()?;
+
+        Ok(())
+    }
+
+    fn new_error(&self, p_error: NomError<&str>) -> InputError {
+        let mut error = InputError::new(EKind::CLIPBOARD);
+
+        if let Some(ref label) = self.label {
+            error.add_label(label);
+        }
+
+        error.add_context(failed_at(&p_error));
+
+        error
+    }
+}
+
+impl Parser for Clipboard {
+    fn parse_str(&self, s: &str) -> Result<InputType, InputError> {
+        self.parse(s)
+            .map(|_| InputType::Clipboard)
+            .map_err(|e| self.new_error(e))
+    }
+}
+
+impl Weight for Clipboard {
+    fn weight(&self) -> u8 {
+        self.get_weight()
+    }
+}
+
+impl Named for Clipboard {
+    fn name(&self) -> ParserName {
+        ParserName::Clipboard
+    }
+
+    fn marker(&self) -> &str {
+        self.get_marker()
+    }
+}
+
+impl fmt::Debug for Clipboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Clipboard")
+            .field("marker", &self.get_marker())
+            .field(
+                "parser",
+                &self
+                    .parser
+                    .map_or("Default ClipboardParser", |_| "Custom ClipboardParser"),
+            )
+            .finish()
+    }
+}
+
+/// Default parser for the clipboard. It expects the input to be exactly the 'marker', with no
+/// other input.
+pub fn default_clipboard_parser<'a>(input: &'a str, marker: &str) -> nom::IResult<&'a str, ()> {
+    let child = nom::context("CLIPBOARD", nom::all_consuming(nom::tag(marker)));
+
+    nom::value((), child)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_success() {
+        let parser = Clipboard::new();
+
+        let result = parser.parse_str("clipboard");
+
+        assert_eq!(result, Ok(InputType::Clipboard))
+    }
+
+    #[test]
+    fn defaults_failure() {
+        for input in &["", "clip", "clipboard2", " clipboard", "@clipboard"] {
+            let parser = Clipboard::new();
+
+            let result = parser.parse_str(input);
+
+            assert_eq!(result, Err(EKind::CLIPBOARD.into()), "{}", input)
+        }
+    }
+
+    #[test]
+    fn c_marker_success() {
+        let parser = Clipboard::new().with(|this| this.marker("%"));
+
+        assert_eq!(parser.parse_str("%"), Ok(InputType::Clipboard));
+        assert_eq!(parser.parse_str("clipboard"), Err(EKind::CLIPBOARD.into()))
+    }
+}
@@ -0,0 +1,248 @@
+use super::{
+    failed_at,
+    nom::{self, Finish},
+    EKind, InputError, InputType, Named, NomError, Parser, ParserName, Weight,
+};
+
+use std::fmt;
+
+/// Function signature of the parser Env calls for processing input
+pub type EnvParser = for<'a, 'b> fn(&'a str, &'b str) -> nom::IResult<&'a str, String>;
+
+/// Construct for treating the given input as the name of an environment variable, which is read
+/// when the input is accessed. By default, this parser accepts input like `env:VAR_NAME`.
+#[derive(Clone, Default)]
+pub struct Env {
+    marker: Option<String>,
+    parser: Option<EnvParser>,
+    weight: Option<u8>,
+    label: Option<String>,
+}
+
+impl Env {
+    /// The default weighting for [Env]
+    pub const DEFAULT_WEIGHT: u8 = 145;
+    /// Default marker for [Env]
+    pub const DEFAULT_MARKER: &str = "env:";
+    /// Default parser implementation for [Env]
+    pub const DEFAULT_PARSER: EnvParser = default_env_parser;
+
+    /// Instantiate a new Env parser with sensible defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convenience function for modifying the semantics of
+    /// this parser
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use grab::parsers::Env;
+    ///
+    /// // Use a shell styled marker, e.g $HOME
+    /// let env = Env::new().with(|this| this.marker("$"));
+    /// ```
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnMut(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        let mut actions = f;
+
+        actions(&mut this);
+
+        this
+    }
+
+    /// Modify the marker string for triggering this Env parser.
+    /// This marker is passed to the parser function as the second &str
+    /// argument.
+    pub fn marker(&mut self, marker: impl AsRef<str>) -> &mut Self {
+        self.marker = Some(marker.as_ref().to_string());
+
+        self
+    }
+
+    /// Replace the parser for this Env with a different one. Expects a
+    /// _function_ (not closure) with the following arguments + return:
+    ///
+    /// fn my_parser<'a>(input: &'a str, marker: &str) -> crate::nom::IResult<&'a str, String>
+    /// {
+    ///     /* ... */
+    /// }
+    pub fn parser(&mut self, parser: EnvParser) -> &mut Self {
+        self.parser = Some(parser);
+
+        self
+    }
+
+    /// Set this parser's weight. Lower numbers will be ran before greater.
+    pub fn weight(&mut self, weight: u8) -> &mut Self {
+        self.weight = Some(weight);
+
+        self
+    }
+
+    /// Attach a human readable label to this parser, which will be included in any
+    /// [InputError] it produces.
+    pub fn label(&mut self, label: impl AsRef<str>) -> &mut Self {
+        self.label = Some(label.as_ref().to_string());
+
+        self
+    }
+
+    fn get_weight(&self) -> u8 {
+        self.weight.unwrap_or(Self::DEFAULT_WEIGHT)
+    }
+
+    fn get_marker(&self) -> &str {
+        self.marker.as_deref().unwrap_or(Self::DEFAULT_MARKER)
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<String, NomError<&'a str>> {
+        let marker = self.get_marker();
+
+        let (_, name) = self
+            .parser
+            .map(|p| p(input, marker))
+            .unwrap_or_else(|| Self::DEFAULT_PARSER(input, marker))
+            .finish()?;
+
+        Ok(name)
+    }
+
+    fn new_error(&self, p_error: NomError<&str>) -> InputError {
+        let mut error = InputError::new(EKind::ENV);
+
+        if let Some(ref label) = self.label {
+            error.add_label(label);
+        }
+
+        error.add_context(failed_at(&p_error));
+
+        error
+    }
+}
+
+impl Parser for Env {
+    fn parse_str(&self, s: &str) -> Result<InputType, InputError> {
+        self.parse(s)
+            .map(InputType::Env)
+            .map_err(|e| self.new_error(e))
+    }
+}
+
+impl Weight for Env {
+    fn weight(&self) -> u8 {
+        self.get_weight()
+    }
+}
+
+impl Named for Env {
+    fn name(&self) -> ParserName {
+        ParserName::Env
+    }
+
+    fn marker(&self) -> &str {
+        self.get_marker()
+    }
+}
+
+impl fmt::Debug for Env {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Env")
+            .field("marker", &self.get_marker())
+            .field(
+                "parser",
+                &self
+                    .parser
+                    .map_or("Default EnvParser", |_| "Custom EnvParser"),
+            )
+            .finish()
+    }
+}
+
+/// Default parser for environment variables. It expects input starting with the 'marker',
+/// followed by the variable's name, returning the name. Names which are empty or contain `=` or
+/// a NUL byte are rejected, as no environment variable can have them.
+pub fn default_env_parser<'a>(input: &'a str, marker: &str) -> nom::IResult<&'a str, String> {
+    let (name, _) = nom::context("ENV", nom::tag(marker))(input)?;
+
+    if name.is_empty() || name.contains(['=', '\0']) {
+        use nom::{make_error, ErrorKind};
+
+        return Err(nom::Err::Error(make_error(name, ErrorKind::Verify)));
+    }
+
+    Ok(("", String::from(name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BAD_INPUT: &str = "invalid env input";
+
+    #[test]
+    fn defaults_success() {
+        let input = "env:HOME";
+        let output = String::from("HOME");
+
+        let parser = Env::new();
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Ok(InputType::Env(output)))
+    }
+
+    #[test]
+    fn defaults_failure() {
+        for input in &[BAD_INPUT, "env:", "env:A=B", "HOME"] {
+            let parser = Env::new();
+
+            let result = parser.parse_str(input);
+
+            assert_eq!(result, Err(EKind::ENV.into()))
+        }
+    }
+
+    #[test]
+    fn c_marker_success() {
+        let input = "$HOME";
+        let output = String::from("HOME");
+
+        let parser = Env::new().with(|this| this.marker("$"));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Ok(InputType::Env(output)))
+    }
+
+    #[test]
+    fn c_marker_failure() {
+        let input = "env:HOME";
+
+        let parser = Env::new().with(|this| this.marker("$"));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Err(EKind::ENV.into()))
+    }
+
+    #[test]
+    fn c_parser_success() {
+        let input = "home";
+        let output = String::from("HOME");
+
+        let parser = Env::new().with(|this| this.parser(test_custom_parser));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Ok(InputType::Env(output)))
+    }
+
+    fn test_custom_parser<'a>(input: &'a str, _: &str) -> nom::IResult<&'a str, String> {
+        Ok(("", input.to_uppercase()))
+    }
+}
@@ -1,24 +1,47 @@
 use super::{
+    failed_at,
     nom::{self, Finish},
-    EKind, InputError, InputType, NomError, Parser, Weight,
+    EKind, Encoding, InputError, InputType, Named, NomError, Parser, ParserName, Weight,
+};
+use std::{
+    ffi::OsStr,
+    fmt, io,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
 };
-use std::{fmt, path::PathBuf};
 
 pub type FileParser = for<'a, 'b> fn(&'a str, &'b str) -> nom::IResult<&'a str, PathBuf>;
 
+/// Closure equivalent of [FileParser], see [File::parser_fn]
+pub type FileParserFn =
+    dyn for<'a, 'b> Fn(&'a str, &'b str) -> nom::IResult<&'a str, PathBuf> + Send + Sync;
+
 /// Parser
 #[derive(Clone, Default)]
 pub struct File {
-    marker: Option<String>,
+    markers: Vec<String>,
+    marker_position: MarkerPosition,
     parser: Option<FileParser>,
+    parser_fn: Option<Arc<FileParserFn>>,
     weight: Option<u8>,
+    label: Option<String>,
+    require_path_prefix: bool,
+    split_on_marker: bool,
+    expand_home: bool,
+    file_uri: bool,
+    case_insensitive: bool,
+    must_exist: bool,
+    append_marker: Option<String>,
+    #[cfg(feature = "zip")]
+    archive_separator: Option<String>,
+    options: FileOptions,
 }
 
 impl File {
     /// The default weighting for [File]
     pub const DEFAULT_WEIGHT: u8 = 130;
     /// Default marker for [File]
-    pub const DEFAULT_MARKER: &'static str = "@";
+    pub const DEFAULT_MARKER: &str = "@";
     /// Default parser implementation for [File]
     pub const DEFAULT_PARSER: FileParser = default_file_parser;
 
@@ -54,7 +77,43 @@ impl File {
     /// This marker is passed to the parser function as the second &str
     /// argument.
     pub fn marker(&mut self, marker: impl AsRef<str>) -> &mut Self {
-        self.marker = Some(marker.as_ref().to_string());
+        self.markers(Some(marker))
+    }
+
+    /// Accept any of the given markers, e.g both `@path` and `file://path`. Each marker is tried
+    /// in order, and the first which the parser accepts is used. Setting a single
+    /// [marker][File::marker] is equivalent to a list of one.
+    ///
+    /// Each marker is in turn passed to the parser function as the second &str argument. An
+    /// empty list restores the default marker.
+    pub fn markers<I, S>(&mut self, markers: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.markers = markers
+            .into_iter()
+            .map(|m| m.as_ref().to_string())
+            .collect();
+
+        self
+    }
+
+    /// Set where the marker appears in the input. With [MarkerPosition::Suffix], the default
+    /// parser accepts input like `notes.txt@`, stripping the marker from the end and treating
+    /// the rest as the path. Custom [parser][File::parser] functions are unaffected.
+    pub fn marker_position(&mut self, position: MarkerPosition) -> &mut Self {
+        self.marker_position = position;
+
+        self
+    }
+
+    /// Mark files given with this marker, e.g `+out.log` for a marker of `+`, as opened for
+    /// appending. This is intended for tools which write to their inputs as well as reading
+    /// them, see [Input::is_append][crate::Input::is_append]. The rest of the input is taken as
+    /// the path, and other options like [expand_home][File::expand_home] still apply.
+    pub fn append_marker(&mut self, marker: impl AsRef<str>) -> &mut Self {
+        self.append_marker = Some(marker.as_ref().to_string());
 
         self
     }
@@ -62,7 +121,7 @@ impl File {
     /// Replace the parser for this File with a different one. Expects a
     /// _function_ (not closure) with the following arguments + return:
     ///
-    /// fn my_parser<'a, 'b>(input: &'a str, marker: &'b str) -> crate::nom::IResult<&'a str, PathBuf>
+    /// fn my_parser<'a>(input: &'a str, marker: &str) -> crate::nom::IResult<&'a str, PathBuf>
     /// {
     ///     /* ... */
     /// }
@@ -72,6 +131,20 @@ impl File {
         self
     }
 
+    /// Like [parser][File::parser], but accepts a closure which may capture state. If set, this
+    /// is used in preference to any function given to [parser][File::parser].
+    pub fn parser_fn<F>(&mut self, parser: F) -> &mut Self
+    where
+        F: for<'a, 'b> Fn(&'a str, &'b str) -> nom::IResult<&'a str, PathBuf>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.parser_fn = Some(Arc::new(parser));
+
+        self
+    }
+
     /// Set this parser's weight. Lower numbers will be ran before greater.
     pub fn weight(&mut self, weight: u8) -> &mut Self {
         self.weight = Some(weight);
@@ -79,37 +152,489 @@ impl File {
         self
     }
 
+    /// Attach a human readable label to this parser, which will be included in any
+    /// [InputError] it produces.
+    pub fn label(&mut self, label: impl AsRef<str>) -> &mut Self {
+        self.label = Some(label.as_ref().to_string());
+
+        self
+    }
+
+    /// Require the path following the marker to be explicitly relative or absolute, that is, it
+    /// must start with one of `/`, `./`, `../` or `~`. Bare names like `@notes.txt` will then fail
+    /// to parse, as they might not have been intended as a path.
+    pub fn require_path_prefix(&mut self, require: bool) -> &mut Self {
+        self.require_path_prefix = require;
+
+        self
+    }
+
+    /// Treat repeated markers as separating several file paths, e.g `@a@b@c` refers to the
+    /// three files `a`, `b` and `c`. Empty paths, like in `@a@@b` or `@a@`, are rejected. Use
+    /// [Config::parse_many][crate::Config::parse_many] to get an input per file, otherwise the
+    /// files are read one after another.
+    pub fn split_on_marker(&mut self, split: bool) -> &mut Self {
+        self.split_on_marker = split;
+
+        self
+    }
+
+    /// Expand a leading `~` in the path to the current user's home directory, so `@~/notes.txt`
+    /// refers to `notes.txt` in the home directory. Only a bare `~` or a leading `~/` is
+    /// expanded, `~user` and `~` anywhere else in the path are left untouched. The home directory
+    /// is taken from `HOME`, or `USERPROFILE` on Windows, and the path is also left untouched if
+    /// neither is set.
+    pub fn expand_home(&mut self, expand: bool) -> &mut Self {
+        self.expand_home = expand;
+
+        self
+    }
+
+    /// Additionally accept [RFC 8089](https://www.rfc-editor.org/rfc/rfc8089) `file:` URIs,
+    /// e.g `file:///tmp/x` or `file://localhost/tmp/x`, which are percent-decoded into an absolute
+    /// path. URIs with any authority other than an empty one or `localhost` are rejected, as they
+    /// refer to files on another host. Input which isn't a `file:` URI falls back to the normal
+    /// marker based parsing.
+    pub fn file_uri(&mut self, enable: bool) -> &mut Self {
+        self.file_uri = enable;
+
+        self
+    }
+
+    /// Match the marker ignoring ASCII case, so `file://x` also accepts `FILE://x`. By default, markers are
+    /// case-sensitive. This only applies to the default parser, custom [parsers][File::parser]
+    /// are given the marker unchanged.
+    pub fn case_insensitive(&mut self, insensitive: bool) -> &mut Self {
+        self.case_insensitive = insensitive;
+
+        self
+    }
+
+    /// Check that the file exists while parsing, rather than only failing once the input is
+    /// accessed. Missing files are rejected with an [InputError] containing
+    /// [EKind::FILE_NOT_FOUND], so that a bad path is reported before any work starts. For
+    /// members of an archive, only the archive itself is checked.
+    ///
+    /// Files whose existence can't be determined, e.g due to missing permissions, are still
+    /// accepted, and fail when accessed instead.
+    pub fn must_exist(&mut self, must_exist: bool) -> &mut Self {
+        self.must_exist = must_exist;
+
+        self
+    }
+
+    /// Allow selecting a member of a zip archive, by separating the archive's path and the
+    /// member's name with the given separator, e.g `@path.zip!inner/path` with a separator of
+    /// `!`. The input is split on the first occurrence of the separator, and the member is read
+    /// from the archive when the input is accessed.
+    ///
+    /// Only stored and deflated members are supported.
+    #[cfg(feature = "zip")]
+    pub fn archive_separator(&mut self, separator: impl AsRef<str>) -> &mut Self {
+        self.archive_separator = Some(separator.as_ref().to_string());
+
+        self
+    }
+
+    /// Treat the file's contents as UTF-16 encoded text, transcoding to UTF-8 when it is read
+    /// via [read_to_string][crate::InputReader::read_to_string]. Raw reads via [io::Read] are
+    /// left untouched.
+    ///
+    /// [io::Read]: std::io::Read
+    pub fn utf16(&mut self, endianness: Endianness) -> &mut Self {
+        self.options.utf16 = Some(endianness);
+
+        self
+    }
+
+    /// Decode the file's contents with the given encoding if they aren't valid UTF-8, when it is
+    /// read via [read_to_string][crate::InputReader::read_to_string], rather than failing. Files
+    /// which are valid UTF-8 are read as normal. This has no effect if [utf16][File::utf16] is
+    /// also set.
+    ///
+    /// ```
+    /// use grab::parsers::{Encoding, File};
+    ///
+    /// let latin1 = Encoding::for_label(b"latin1").unwrap();
+    /// let file = File::new().with(|this| this.fallback_encoding(latin1));
+    /// ```
+    pub fn fallback_encoding(&mut self, encoding: &'static Encoding) -> &mut Self {
+        self.options.fallback_encoding = Some(encoding);
+
+        self
+    }
+
+    /// Open the file with direct I/O, bypassing the OS page cache. This is useful for
+    /// benchmarking or large sequential scans that would otherwise evict the cache.
+    ///
+    /// This uses `O_DIRECT` on Linux, Android and FreeBSD, `F_NOCACHE` on macOS/iOS and
+    /// `FILE_FLAG_NO_BUFFERING` on Windows, and is ignored on other platforms. Be aware that
+    /// direct I/O usually requires the buffers passed to [read][std::io::Read::read] to be
+    /// aligned to the underlying device's block size, and that some filesystems (e.g tmpfs) do
+    /// not support it at all. If the file can't be opened with direct I/O, or the filesystem
+    /// refuses unaligned reads from it, it is opened normally instead.
+    pub fn direct_io(&mut self, direct: bool) -> &mut Self {
+        self.options.direct_io = direct;
+
+        self
+    }
+
+    /// Hint to the OS that the file will be read sequentially once opened, which may allow it to
+    /// read ahead more aggressively and improve throughput for large files.
+    ///
+    /// This uses `posix_fadvise(POSIX_FADV_SEQUENTIAL)` on Linux, Android and FreeBSD, and is a
+    /// no-op on other platforms. The hint is purely advisory, so a file is still read as normal
+    /// if the OS rejects it.
+    pub fn sequential(&mut self, sequential: bool) -> &mut Self {
+        self.options.sequential = sequential;
+
+        self
+    }
+
+    /// Transparently decompress files whose path ends in `.gz` when they are accessed, so that
+    /// reading the input yields the decompressed bytes. Other files are read as normal.
+    ///
+    /// The file is decompressed as it is read, so limits like
+    /// [max_bytes][crate::Builder::max_bytes] apply to the decompressed data without all of it
+    /// being held in memory. Corrupt data is only reported once it is reached by a read.
+    #[cfg(feature = "gzip")]
+    pub fn decompress_gzip(&mut self, decompress: bool) -> &mut Self {
+        self.options.decompress_gzip = decompress;
+
+        self
+    }
+
+    /// Like [decompress_gzip][File::decompress_gzip], but for zstd files, whose path ends in
+    /// `.zst`
+    #[cfg(feature = "zstd")]
+    pub fn decompress_zstd(&mut self, decompress: bool) -> &mut Self {
+        self.options.decompress_zstd = decompress;
+
+        self
+    }
+
+    /// Memory map the file when it is accessed rather than reading it through [io::Read] calls,
+    /// which avoids copying its contents through the kernel for large files that are only
+    /// scanned. Files which can't be mapped, like empty files or pipes, are read as normal.
+    ///
+    /// Only enable this for files which are known not to change while they are read, e.g ones
+    /// owned by the program. If another process truncates a mapped file, reading the now missing
+    /// part of it crashes the process with `SIGBUS`.
+    ///
+    /// [io::Read]: std::io::Read
+    #[cfg(feature = "mmap")]
+    pub fn mmap(&mut self, mmap: bool) -> &mut Self {
+        self.options.mmap = mmap;
+
+        self
+    }
+
+    /// Whether to open files which are named pipes (FIFOs) on unix. Opening a FIFO blocks until
+    /// another process opens it for writing, which can be surprising for a path that looks like
+    /// a regular file. When unset, accessing a FIFO fails with
+    /// [Kind::File][crate::error::access::Kind::File] instead of blocking. By default, FIFOs are
+    /// opened like any other file, use [Input::is_fifo][crate::Input::is_fifo] to detect them.
+    pub fn follow_fifo(&mut self, follow: bool) -> &mut Self {
+        self.options.reject_fifo = !follow;
+
+        self
+    }
+
+    /// Whether this parser accepts `file:` URIs, see [file_uri][File::file_uri]
+    pub(crate) fn accepts_file_uri(&self) -> bool {
+        self.file_uri
+    }
+
     fn get_weight(&self) -> u8 {
         self.weight.unwrap_or(Self::DEFAULT_WEIGHT)
     }
 
     fn get_marker(&self) -> &str {
-        self.marker.as_deref().unwrap_or(Self::DEFAULT_MARKER)
+        self.get_markers().next().unwrap_or(Self::DEFAULT_MARKER)
+    }
+
+    fn get_markers(&self) -> impl Iterator<Item = &str> {
+        let default = match self.markers.is_empty() {
+            true => Some(Self::DEFAULT_MARKER),
+            false => None,
+        };
+
+        self.markers.iter().map(String::as_str).chain(default)
+    }
+
+    /// Run the parser with each marker in turn, returning the first success along with the
+    /// marker that was used
+    fn run_parser<'a>(&self, input: &'a str) -> Result<(&str, PathBuf), NomError<&'a str>> {
+        let mut error = None;
+
+        for marker in self.get_markers() {
+            let result = match (&self.parser_fn, self.parser) {
+                (Some(f), _) => f(input, marker).finish().map(|(_, path)| path),
+                (None, Some(p)) => p(input, marker).finish().map(|(_, path)| path),
+                (None, None) => self
+                    .strip_marker(input.as_bytes(), marker)
+                    .and_then(|path| std::str::from_utf8(path).ok())
+                    .map(PathBuf::from)
+                    .ok_or_else(|| nom::make_error(input, nom::ErrorKind::Tag)),
+            };
+
+            match result {
+                Ok(path) => return Ok((marker, path)),
+                Err(e) => error = Some(e),
+            }
+        }
+
+        Err(error.unwrap_or_else(|| nom::make_error(input, nom::ErrorKind::Tag)))
+    }
+
+    /// Strip the marker from the input, at the [marker_position][File::marker_position] and
+    /// respecting [case_insensitive][File::case_insensitive]. This is the marker handling of
+    /// the default parser, for both UTF-8 and non-UTF-8 input.
+    fn strip_marker<'a>(&self, input: &'a [u8], marker: &str) -> Option<&'a [u8]> {
+        let marker = marker.as_bytes();
+        let split = match self.marker_position {
+            MarkerPosition::Prefix => marker.len(),
+            MarkerPosition::Suffix => input.len().checked_sub(marker.len())?,
+        };
+        let (head, tail) = (input.get(..split)?, input.get(split..)?);
+
+        match self.marker_position {
+            MarkerPosition::Prefix => self.marker_eq(head, marker).then_some(tail),
+            MarkerPosition::Suffix => self.marker_eq(tail, marker).then_some(head),
+        }
+    }
+
+    /// Split a path on every occurrence of a non-empty marker, see
+    /// [split_on_marker][File::split_on_marker]
+    fn split_marker<'a>(&self, path: &'a [u8], marker: &str) -> Vec<&'a [u8]> {
+        let marker = marker.as_bytes();
+        let mut segments = Vec::new();
+        let (mut start, mut i) = (0, 0);
+
+        while i + marker.len() <= path.len() {
+            match self.marker_eq(&path[i..i + marker.len()], marker) {
+                true => {
+                    segments.push(&path[start..i]);
+                    i += marker.len();
+                    start = i;
+                }
+                false => i += 1,
+            }
+        }
+        segments.push(&path[start..]);
+
+        segments
+    }
+
+    fn marker_eq(&self, a: &[u8], marker: &[u8]) -> bool {
+        match self.case_insensitive {
+            true => a.eq_ignore_ascii_case(marker),
+            false => a == marker,
+        }
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<InputType, NomError<&'a str>> {
+        let verify = || nom::make_error(input, nom::ErrorKind::Verify);
+
+        if let Some(path) = self.file_uri.then(|| parse_file_uri(input)).flatten() {
+            return self.file_path(path).map(InputType::File).ok_or_else(verify);
+        }
+
+        let (marker, path) = self.run_parser(input)?;
+
+        self.files(marker, path).ok_or_else(verify)
+    }
+
+    /// Parse input that isn't valid UTF-8. On unix, paths are arbitrary bytes so the marker is
+    /// stripped at the byte level, though this is only possible with the default parser.
+    #[cfg(unix)]
+    fn parse_os(&self, input: &OsStr) -> Result<InputType, InputError> {
+        use std::os::unix::ffi::OsStrExt;
+
+        let bytes = input.as_bytes();
+
+        if let Some(result) = self.parse_append(bytes) {
+            return result;
+        }
+
+        if self.parser.is_some() || self.parser_fn.is_some() {
+            return Err(EKind::REQUIRES_UTF8.into());
+        }
+
+        self.get_markers()
+            .find_map(|marker| {
+                let path = self.strip_marker(bytes, marker)?;
+
+                self.files(marker, PathBuf::from(OsStr::from_bytes(path)))
+            })
+            .ok_or_else(|| self.error())
+    }
+
+    #[cfg(not(unix))]
+    fn parse_os(&self, _input: &OsStr) -> Result<InputType, InputError> {
+        Err(EKind::REQUIRES_UTF8.into())
+    }
+
+    /// Parse input starting with the [append_marker][File::append_marker], returning None if it
+    /// doesn't
+    fn parse_append(&self, input: &[u8]) -> Option<Result<InputType, InputError>> {
+        let marker = self.append_marker.as_deref().filter(|m| !m.is_empty())?;
+        let path = input.strip_prefix(marker.as_bytes())?;
+
+        let file = bytes_to_path(path.to_vec()).and_then(|path| self.append_path(path));
+
+        Some(file.map(InputType::File).ok_or_else(|| self.error()))
     }
 
-    fn parse<'a>(&self, input: &'a str) -> Result<FilePath, NomError<&'a str>> {
-        let marker = self.get_marker();
+    /// Turn the path parsed with the given marker into a file, or several if
+    /// [split_on_marker][File::split_on_marker] is set, returning None if any are rejected
+    fn files(&self, marker: &str, path: PathBuf) -> Option<InputType> {
+        if !self.split_on_marker || marker.is_empty() {
+            return self.file_path(path).map(InputType::File);
+        }
 
-        let (_, path) = self
-            .parser
-            .map(|p| p(input, marker))
-            .unwrap_or_else(|| Self::DEFAULT_PARSER(input, marker))
-            .finish()?;
+        let mut files = self
+            .split_marker(path_bytes(&path)?, marker)
+            .into_iter()
+            .map(|segment| match segment.is_empty() {
+                true => None,
+                false => bytes_to_path(segment.to_vec()).and_then(|path| self.file_path(path)),
+            })
+            .collect::<Option<Vec<_>>>()?;
 
-        Ok(FilePath::new(path))
+        match files.len() {
+            1 => Some(InputType::File(files.remove(0))),
+            _ => Some(InputType::Files(files)),
+        }
     }
 
-    // TODO: Allow potentially passing contextual data to InputErrors
-    fn new_error(&self, _p_error: NomError<&str>) -> InputError {
-        InputError::new(EKind::FILE)
+    /// Like [file_path][File::file_path], but marks the file as opened for appending
+    fn append_path(&self, path: PathBuf) -> Option<FilePath> {
+        self.file_path(path).map(|mut file| {
+            file.options.append = self.append_marker.clone();
+
+            file
+        })
+    }
+
+    /// Apply any checks and transformations to a parsed path, returning None if it is rejected
+    fn file_path(&self, path: PathBuf) -> Option<FilePath> {
+        if self.require_path_prefix && !has_path_prefix(&path) {
+            return None;
+        }
+
+        let path = match self.expand_home {
+            true => expand_home(path),
+            false => path,
+        };
+
+        let file = FilePath::with_options(path, self.options.clone());
+
+        #[cfg(feature = "zip")]
+        let file = match self.archive_separator {
+            Some(ref separator) => file.split_archive(separator)?,
+            None => file,
+        };
+
+        Some(file)
+    }
+
+    fn new_error(&self, p_error: NomError<&str>) -> InputError {
+        self.error()
+            .with(|this| this.add_context(failed_at(&p_error)))
+    }
+
+    fn error(&self) -> InputError {
+        let mut error = InputError::new(EKind::FILE);
+
+        if let Some(ref label) = self.label {
+            error.add_label(label);
+        }
+
+        error
     }
 }
 
 impl Parser for File {
     fn parse_str(&self, s: &str) -> Result<InputType, InputError> {
-        self.parse(s)
-            .map(InputType::File)
-            .map_err(|e| self.new_error(e))
+        self.parse_input(s)
+            .and_then(|kind| self.reject_nul(kind))
+            .and_then(|kind| self.check_exists(kind))
+    }
+
+    fn parse_os_str(&self, input: &OsStr) -> Result<InputType, InputError> {
+        match input.to_str() {
+            Some(s) => self.parse_str(s),
+            None => self
+                .parse_os(input)
+                .and_then(|kind| self.reject_nul(kind))
+                .and_then(|kind| self.check_exists(kind)),
+        }
+    }
+}
+
+impl File {
+    /// Parse a bare path, given without any marker, applying this parser's options. This is how
+    /// a [marker_chain][crate::Builder::marker_chain] reads its value as a file
+    pub(crate) fn parse_path(&self, path: &str) -> Result<InputType, InputError> {
+        self.parse_append(path.as_bytes())
+            .unwrap_or_else(|| {
+                self.files("", PathBuf::from(path))
+                    .ok_or_else(|| self.error())
+            })
+            .and_then(|kind| self.reject_nul(kind))
+            .and_then(|kind| self.check_exists(kind))
+    }
+
+    fn parse_input(&self, s: &str) -> Result<InputType, InputError> {
+        if let Some(result) = self.parse_append(s.as_bytes()) {
+            return result;
+        }
+
+        self.parse(s).map_err(|e| self.new_error(e))
+    }
+
+    /// Paths can't contain interior NUL bytes, so catch them here rather than leaving the OS to
+    /// fail with a less helpful error when the file is opened
+    fn reject_nul(&self, kind: InputType) -> Result<InputType, InputError> {
+        let has_nul = |file: &FilePath| file.path.to_string_lossy().contains('\0');
+
+        let nul = match kind {
+            InputType::File(ref file) => has_nul(file),
+            InputType::Files(ref files) => files.iter().any(has_nul),
+            _ => false,
+        };
+
+        match nul {
+            true => Err(self
+                .error()
+                .with(|this| this.add_context("path contains a NUL byte"))),
+            false => Ok(kind),
+        }
+    }
+
+    /// Reject files which are known not to exist, if [must_exist][File::must_exist] is set
+    fn check_exists(&self, kind: InputType) -> Result<InputType, InputError> {
+        if !self.must_exist {
+            return Ok(kind);
+        }
+
+        let files = match kind {
+            InputType::File(ref file) => std::slice::from_ref(file),
+            InputType::Files(ref files) => files.as_slice(),
+            _ => &[],
+        };
+        let missing = files
+            .iter()
+            .find(|file| matches!(file.path.try_exists(), Ok(false)));
+
+        match missing {
+            Some(file) => Err(self.error().with(|this| {
+                this.insert(EKind::FILE_NOT_FOUND)
+                    .add_context(format!("no such file: {}", file.path.display()))
+            })),
+            None => Ok(kind),
+        }
     }
 }
 
@@ -119,45 +644,432 @@ impl Weight for File {
     }
 }
 
+impl Named for File {
+    fn name(&self) -> ParserName {
+        ParserName::File
+    }
+
+    fn marker(&self) -> &str {
+        self.get_marker()
+    }
+
+    fn markers(&self) -> Vec<&str> {
+        self.get_markers()
+            .chain(self.append_marker.as_deref().filter(|m| !m.is_empty()))
+            .collect()
+    }
+}
+
 impl fmt::Debug for File {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("File")
-            .field("marker", &self.get_marker())
+            .field("markers", &self.get_markers().collect::<Vec<_>>())
             .field(
                 "parser",
-                &self
-                    .parser
-                    .map_or("Default FileParser", |_| "Custom FileParser"),
+                &match self.parser.is_some() || self.parser_fn.is_some() {
+                    true => "Custom FileParser",
+                    false => "Default FileParser",
+                },
             )
             .finish()
     }
 }
 
+/// Parse a local `file:` URI into the absolute path it refers to, returning None if the input
+/// isn't one
+fn parse_file_uri(input: &str) -> Option<PathBuf> {
+    let scheme = input.get(..5)?;
+    if !scheme.eq_ignore_ascii_case("file:") {
+        return None;
+    }
+
+    let rest = &input[5..];
+    // Ignore any query or fragment
+    let rest = &rest[..rest.find(['?', '#']).unwrap_or(rest.len())];
+
+    let path = match rest.strip_prefix("//") {
+        Some(hier) => {
+            let (authority, path) = hier.split_at(hier.find('/')?);
+
+            if !(authority.is_empty() || authority.eq_ignore_ascii_case("localhost")) {
+                return None;
+            }
+
+            path
+        }
+        // The minimal form, file:/path
+        None if rest.starts_with('/') => rest,
+        None => return None,
+    };
+
+    let decoded = percent_decode(path)?;
+
+    // Windows drive letters are written as file:///C:/path
+    #[cfg(windows)]
+    let decoded = match decoded.as_slice() {
+        [b'/', drive, b':', ..] if drive.is_ascii_alphabetic() => decoded[1..].to_vec(),
+        _ => decoded,
+    };
+
+    bytes_to_path(decoded)
+}
+
+/// Format an absolute path as a `file:` URI, percent-encoding every byte which isn't unreserved,
+/// the inverse of [parse_file_uri]. Returns None for relative paths.
+#[cfg(unix)]
+pub(crate) fn file_uri(path: &Path) -> Option<String> {
+    use std::fmt::Write;
+
+    if !path.is_absolute() {
+        return None;
+    }
+
+    let mut uri = String::from("file://");
+    for &b in path_bytes(path)? {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                uri.push(b as char)
+            }
+            _ => write!(uri, "%{:02X}", b).ok()?,
+        }
+    }
+
+    Some(uri)
+}
+
+/// Decode %XX escapes, returning None for malformed escapes
+fn percent_decode(input: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(input.len());
+    let mut bytes = input.bytes();
+
+    while let Some(b) = bytes.next() {
+        if b == b'%' {
+            let hex = [bytes.next()?, bytes.next()?];
+            let hex = std::str::from_utf8(&hex).ok()?;
+
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            out.push(b);
+        }
+    }
+
+    Some(out)
+}
+
+#[cfg(unix)]
+fn bytes_to_path(bytes: Vec<u8>) -> Option<PathBuf> {
+    use std::{ffi::OsString, os::unix::ffi::OsStringExt};
+
+    Some(PathBuf::from(OsString::from_vec(bytes)))
+}
+
+#[cfg(not(unix))]
+fn bytes_to_path(bytes: Vec<u8>) -> Option<PathBuf> {
+    String::from_utf8(bytes).ok().map(PathBuf::from)
+}
+
+#[cfg(unix)]
+fn path_bytes(path: &Path) -> Option<&[u8]> {
+    use std::os::unix::ffi::OsStrExt;
+
+    Some(path.as_os_str().as_bytes())
+}
+
+#[cfg(not(unix))]
+fn path_bytes(path: &Path) -> Option<&[u8]> {
+    path.to_str().map(str::as_bytes)
+}
+
+/// Checks if the given path is explicitly relative or absolute, e.g `./x`, `../x`, `/x` or `~/x`
+fn has_path_prefix(path: &Path) -> bool {
+    match path.components().next() {
+        Some(Component::Prefix(_)) | Some(Component::RootDir) => true,
+        Some(Component::CurDir) | Some(Component::ParentDir) => true,
+        Some(Component::Normal(first)) => first.to_str().is_some_and(|s| s.starts_with('~')),
+        None => false,
+    }
+}
+
+/// Replace a leading `~` component with the user's home directory, if it can be found
+fn expand_home(path: PathBuf) -> PathBuf {
+    let mut components = path.components();
+
+    match (components.next(), home_dir()) {
+        (Some(Component::Normal(first)), Some(home)) if first == "~" => {
+            home.join(components.as_path())
+        }
+        _ => path,
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME");
+
+    #[cfg(windows)]
+    let home = home.or_else(|| std::env::var_os("USERPROFILE"));
+
+    home.filter(|h| !h.is_empty()).map(PathBuf::from)
+}
+
+/// Where a [File] parser's marker appears in the input, see [File::marker_position]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarkerPosition {
+    /// The marker comes before the path, e.g `@notes.txt`. This is the default
+    #[default]
+    Prefix,
+    /// The marker comes after the path, e.g `notes.txt@`
+    Suffix,
+}
+
+/// The byte order used when decoding a UTF-16 file, see [File::utf16].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endianness {
+    /// Detect the byte order from the file's BOM, falling back to little endian if the file
+    /// doesn't start with one
+    Auto,
+    /// Little endian, i.e UTF-16LE
+    Little,
+    /// Big endian, i.e UTF-16BE
+    Big,
+}
+
+/// Settings given to a [File] parser which only take effect once the input is accessed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct FileOptions {
+    pub utf16: Option<Endianness>,
+    pub fallback_encoding: Option<&'static Encoding>,
+    pub direct_io: bool,
+    pub sequential: bool,
+    #[cfg(feature = "gzip")]
+    pub decompress_gzip: bool,
+    #[cfg(feature = "zstd")]
+    pub decompress_zstd: bool,
+    #[cfg(feature = "mmap")]
+    pub mmap: bool,
+    pub reject_fifo: bool,
+    /// The [append marker][File::append_marker] the file was given with, if any
+    pub append: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct FilePath {
     pub path: PathBuf,
+    /// The member of the zip archive at path to read, if any
+    #[cfg(feature = "zip")]
+    pub member: Option<String>,
+    pub options: FileOptions,
 }
 
 impl FilePath {
-    fn new(path: PathBuf) -> Self {
-        Self { path }
+    #[cfg_attr(not(any(test, feature = "glob")), allow(dead_code))]
+    pub fn new(path: PathBuf) -> Self {
+        Self::with_options(path, FileOptions::default())
+    }
+
+    fn with_options(path: PathBuf, options: FileOptions) -> Self {
+        Self {
+            path,
+            #[cfg(feature = "zip")]
+            member: None,
+            options,
+        }
+    }
+
+    /// Split this path into an archive path and a member name on the first occurrence of
+    /// separator, failing if either side is empty. Paths without the separator are returned
+    /// unchanged.
+    #[cfg(feature = "zip")]
+    fn split_archive(self, separator: &str) -> Option<Self> {
+        let split = self
+            .path
+            .to_str()
+            .and_then(|s| s.split_once(separator))
+            .map(|(archive, member)| (PathBuf::from(archive), member.to_string()));
+
+        match split {
+            Some((archive, member)) if archive.as_os_str().is_empty() || member.is_empty() => None,
+            Some((archive, member)) => Some(Self {
+                path: archive,
+                member: Some(member),
+                ..self
+            }),
+            None => Some(self),
+        }
+    }
+
+    /// Open the file at this path, respecting any options set
+    pub fn open(&self) -> io::Result<std::fs::File> {
+        if self.options.reject_fifo && self.is_fifo() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "file is a FIFO, which follow_fifo is unset for",
+            ));
+        }
+
+        let file = match self.options.direct_io {
+            true => direct::open(&self.path).or_else(|_| std::fs::File::open(&self.path))?,
+            false => std::fs::File::open(&self.path)?,
+        };
+
+        if self.options.sequential {
+            advise::sequential(&file);
+        }
+
+        Ok(file)
+    }
+
+    /// Check whether this path is a named pipe, without opening it
+    pub fn is_fifo(&self) -> bool {
+        std::fs::metadata(&self.path).is_ok_and(|meta| is_fifo(&meta.file_type()))
     }
 }
 
+#[cfg(unix)]
+pub(crate) fn is_fifo(file_type: &std::fs::FileType) -> bool {
+    std::os::unix::fs::FileTypeExt::is_fifo(file_type)
+}
+
+#[cfg(not(unix))]
+pub(crate) fn is_fifo(_file_type: &std::fs::FileType) -> bool {
+    false
+}
+
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+mod direct {
+    use std::{
+        fs, io,
+        os::unix::fs::{FileExt, OpenOptionsExt},
+        path::Path,
+    };
+
+    pub fn open(path: &Path) -> io::Result<fs::File> {
+        let file = fs::OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_DIRECT)
+            .open(path)?;
+
+        // Many filesystems accept O_DIRECT, but then refuse any read whose buffer, offset or
+        // length isn't block aligned, which is every read made through std::io::Read. Probe
+        // with such a read so these files are read normally, instead of failing part way
+        let mut probe = [0; 1];
+        match file.read_at(&mut probe, 0) {
+            Err(e) if e.raw_os_error() == Some(libc::EINVAL) => Err(e),
+            _ => Ok(file),
+        }
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+mod direct {
+    use std::{fs, io, os::unix::io::AsRawFd, path::Path};
+
+    pub fn open(path: &Path) -> io::Result<fs::File> {
+        let file = fs::File::open(path)?;
+
+        // SAFETY: the fd is valid for the lifetime of file
+        match unsafe { libc::fcntl(file.as_raw_fd(), libc::F_NOCACHE, 1) } {
+            -1 => Err(io::Error::last_os_error()),
+            _ => Ok(file),
+        }
+    }
+}
+
+#[cfg(windows)]
+mod direct {
+    use std::{
+        fs,
+        io::{self, Seek},
+        os::windows::fs::{FileExt, OpenOptionsExt},
+        path::Path,
+    };
+
+    const FILE_FLAG_NO_BUFFERING: u32 = 0x2000_0000;
+    const ERROR_INVALID_PARAMETER: i32 = 87;
+
+    pub fn open(path: &Path) -> io::Result<fs::File> {
+        let mut file = fs::OpenOptions::new()
+            .read(true)
+            .custom_flags(FILE_FLAG_NO_BUFFERING)
+            .open(path)?;
+
+        // Unbuffered reads must be sector aligned in buffer, offset and length, which no read
+        // made through std::io::Read is. Probe with such a read so these files are read
+        // normally, instead of failing part way
+        let mut probe = [0; 1];
+        match file.seek_read(&mut probe, 0) {
+            Err(e) if e.raw_os_error() == Some(ERROR_INVALID_PARAMETER) => Err(e),
+            _ => file.rewind().map(|_| file),
+        }
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "macos",
+    target_os = "ios",
+    windows
+)))]
+mod direct {
+    use std::{fs, io, path::Path};
+
+    pub fn open(path: &Path) -> io::Result<fs::File> {
+        fs::File::open(path)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+mod advise {
+    use std::{fs, os::unix::io::AsRawFd};
+
+    pub fn sequential(file: &fs::File) {
+        // SAFETY: the fd is valid for the lifetime of file. The hint is advisory, so any
+        // failure is ignored
+        unsafe { libc::posix_fadvise(file.as_raw_fd(), 0, 0, libc::POSIX_FADV_SEQUENTIAL) };
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "freebsd")))]
+mod advise {
+    pub fn sequential(_file: &std::fs::File) {}
+}
+
 /// Default parser for files. It expects input starting with the 'marker' and
 /// takes the rest of the input as a file path.
-pub fn default_file_parser<'a, 'b>(
+pub fn default_file_parser<'a>(input: &'a str, marker: &str) -> nom::IResult<&'a str, PathBuf> {
+    nom::context("FILE", nom::tag(marker))(input).map(|(path, _)| ("", PathBuf::from(path)))
+}
+
+/// Parser used by [Config::platform_default][crate::Config::platform_default] on Windows, which
+/// additionally accepts absolute paths starting with a drive letter without any marker
+#[cfg(windows)]
+pub(crate) fn drive_path_parser<'a>(
     input: &'a str,
-    marker: &'b str,
+    marker: &str,
 ) -> nom::IResult<&'a str, PathBuf> {
-    nom::context("FILE", nom::tag(marker))(input).map(|(path, _)| ("", PathBuf::from(path)))
+    match input.as_bytes() {
+        [drive, b':', b'\\' | b'/', ..] if drive.is_ascii_alphabetic() => {
+            Ok(("", PathBuf::from(input)))
+        }
+        _ => default_file_parser(input, marker),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const BAD_INPUT: &'static str = "invalid file input";
+    use crate::test_support::fixture;
+
+    const BAD_INPUT: &str = "invalid file input";
+
+    fn parse_file(parser: &File, input: &str) -> FilePath {
+        match parser.parse_str(input) {
+            Ok(InputType::File(f)) => f,
+            bad => panic!("expected File, got: {:?}", bad),
+        }
+    }
 
     #[test]
     fn defaults_success() {
@@ -182,6 +1094,37 @@ mod tests {
         assert_eq!(result, Err(EKind::FILE.into()))
     }
 
+    #[test]
+    fn c_case_insensitive() {
+        let output = InputType::File(FilePath::new(PathBuf::from("x")));
+
+        let parser = File::new().with(|this| this.marker("file://").case_insensitive(true));
+
+        assert_eq!(parser.parse_str("FILE://x"), Ok(output.clone()));
+        assert_eq!(parser.parse_str("file://x"), Ok(output));
+
+        let parser = File::new().with(|this| this.marker("file://"));
+
+        assert_eq!(parser.parse_str("FILE://x"), Err(EKind::FILE.into()))
+    }
+
+    #[test]
+    fn c_case_insensitive_suffix() {
+        let parser = File::new().with(|this| {
+            this.marker(".IN")
+                .marker_position(MarkerPosition::Suffix)
+                .case_insensitive(true)
+        });
+
+        let result = parser.parse_str("x.in");
+
+        assert_eq!(
+            result,
+            Ok(InputType::File(FilePath::new(PathBuf::from("x"))))
+        );
+        assert_eq!(parser.parse_str("in"), Err(EKind::FILE.into()))
+    }
+
     #[test]
     fn c_marker_success() {
         let mkr = "+";
@@ -209,6 +1152,148 @@ mod tests {
         assert_eq!(result, Err(EKind::FILE.into()))
     }
 
+    #[test]
+    fn c_markers_success() {
+        let parser = File::new().with(|this| this.markers(["@", "file://"]));
+
+        for input in &["@foo/bar", "file://foo/bar"] {
+            let result = parser.parse_str(input);
+
+            assert_eq!(
+                result,
+                Ok(InputType::File(FilePath::new(PathBuf::from("foo/bar")))),
+                "{}",
+                input
+            )
+        }
+    }
+
+    #[test]
+    fn c_markers_failure() {
+        let parser = File::new().with(|this| this.markers(["@", "file://"]));
+
+        let result = parser.parse_str("+foo/bar");
+
+        assert_eq!(result, Err(EKind::FILE.into()))
+    }
+
+    #[test]
+    fn defaults_nul_failure() {
+        let parser = File::new();
+
+        let err = parser.parse_str("@some/fi\0le").unwrap_err();
+
+        assert!(err.contains(EKind::FILE));
+        assert!(err.to_string().contains("NUL byte"))
+    }
+
+    #[test]
+    fn c_must_exist() {
+        let parser = File::new().with(|this| this.must_exist(true));
+
+        let existing = format!("@{}", fixture("lorem.txt").display());
+        assert!(parser.parse_str(&existing).is_ok());
+
+        let err = parser.parse_str("@does/not/exist.txt").unwrap_err();
+        assert!(err.contains(EKind::FILE) && err.contains(EKind::FILE_NOT_FOUND));
+        assert!(err.to_string().contains("no such file: does/not/exist.txt"));
+
+        // Without the check, missing files only fail once accessed
+        assert!(File::new().parse_str("@does/not/exist.txt").is_ok())
+    }
+
+    #[test]
+    fn c_markers_custom_parser() {
+        let parser = File::new().with(|this| this.markers(["<", "in:"]).parser(test_marker_parser));
+
+        let result = parser.parse_str("in:foo");
+
+        assert_eq!(
+            result,
+            Ok(InputType::File(FilePath::new(PathBuf::from("foo"))))
+        )
+    }
+
+    #[test]
+    fn c_marker_position_prefix() {
+        let parser = File::new().with(|this| this.marker_position(MarkerPosition::Prefix));
+
+        let result = parser.parse_str("@foo/bar");
+        assert_eq!(
+            result,
+            Ok(InputType::File(FilePath::new(PathBuf::from("foo/bar"))))
+        );
+
+        let result = parser.parse_str("foo/bar@");
+        assert_eq!(result, Err(EKind::FILE.into()))
+    }
+
+    #[test]
+    fn c_marker_position_suffix() {
+        let parser = File::new().with(|this| this.marker_position(MarkerPosition::Suffix));
+
+        let result = parser.parse_str("foo/bar@");
+        assert_eq!(
+            result,
+            Ok(InputType::File(FilePath::new(PathBuf::from("foo/bar"))))
+        );
+
+        let result = parser.parse_str("@foo/bar");
+        assert_eq!(result, Err(EKind::FILE.into()))
+    }
+
+    #[test]
+    fn append_marker() {
+        let parser = File::new().with(|this| this.append_marker("+"));
+
+        match parser.parse_str("+out.log") {
+            Ok(InputType::File(f)) => {
+                assert_eq!(f.path, PathBuf::from("out.log"));
+                assert_eq!(f.options.append.as_deref(), Some("+"))
+            }
+            bad => panic!("expected File, got: {:?}", bad),
+        }
+
+        match parser.parse_str("@out.log") {
+            Ok(InputType::File(f)) => assert_eq!(f.options.append, None),
+            bad => panic!("expected File, got: {:?}", bad),
+        }
+
+        assert_eq!(Named::markers(&parser), ["@", "+"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn append_marker_os() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let parser = File::new().with(|this| this.append_marker("+"));
+
+        match parser.parse_os_str(OsStr::from_bytes(b"+out\xff.log")) {
+            Ok(InputType::File(f)) => {
+                assert_eq!(f.path.as_os_str().as_bytes(), b"out\xff.log");
+                assert_eq!(f.options.append.as_deref(), Some("+"))
+            }
+            bad => panic!("expected File, got: {:?}", bad),
+        }
+    }
+
+    #[test]
+    fn failure_context() {
+        let parser = File::new().with(|this| this.label("config file"));
+
+        let err = parser.parse_str(BAD_INPUT).unwrap_err();
+        let message = err.to_string();
+
+        assert!(message.contains("config file"), "{}", message);
+        assert!(
+            message.contains(&format!("failed at {:?}", BAD_INPUT)),
+            "{}",
+            message
+        );
+        assert_eq!(err.context().count(), 1)
+    }
+
     #[test]
     fn c_parser_success() {
         let input = "file://foo/bar/baz";
@@ -232,7 +1317,387 @@ mod tests {
         assert_eq!(result, Err(EKind::FILE.into()))
     }
 
-    fn test_custom_parser<'a, 'b>(input: &'a str, _: &'b str) -> nom::IResult<&'a str, PathBuf> {
+    #[test]
+    fn c_parser_fn_success() {
+        let marker = String::from("in:");
+
+        // Closures are given each marker in turn, like fn pointers are
+        let parser = File::new().with(|this| {
+            let marker = marker.clone();
+
+            this.markers(["<", "@"]).parser_fn(move |input, m| {
+                let (path, _) = nom::tag(m)(input)?;
+
+                nom::tag(marker.as_str())(path).map(|(path, _)| ("", PathBuf::from(path)))
+            })
+        });
+
+        for input in &["<in:foo/bar", "@in:foo/bar"] {
+            assert_eq!(
+                parser.parse_str(input),
+                Ok(InputType::File(FilePath::new(PathBuf::from("foo/bar")))),
+                "{}",
+                input
+            )
+        }
+        assert_eq!(parser.parse_str("@foo/bar"), Err(EKind::FILE.into()))
+    }
+
+    #[test]
+    fn require_path_prefix_success() {
+        let parser = File::new().with(|this| this.require_path_prefix(true));
+
+        for input in &[
+            "@/abs/path",
+            "@./rel/path",
+            "@../parent/path",
+            "@~/home/path",
+            "@~",
+        ] {
+            let output = FilePath::new(PathBuf::from(&input[1..]));
+
+            let result = parser.parse_str(input);
+
+            assert_eq!(result, Ok(InputType::File(output)), "input: {}", input)
+        }
+    }
+
+    #[test]
+    fn require_path_prefix_failure() {
+        let input = "@bare/name";
+
+        let parser = File::new().with(|this| this.require_path_prefix(true));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Err(EKind::FILE.into()))
+    }
+
+    #[cfg(feature = "zip")]
+    #[test]
+    fn archive_separator_success() {
+        let input = "@some/archive.zip!inner/file.txt";
+        let mut output = FilePath::new(PathBuf::from("some/archive.zip"));
+        output.member = Some(String::from("inner/file.txt"));
+
+        let parser = File::new().with(|this| this.archive_separator("!"));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Ok(InputType::File(output)))
+    }
+
+    #[cfg(feature = "zip")]
+    #[test]
+    fn archive_separator_no_member() {
+        let input = "@some/file.txt";
+        let output = FilePath::new(PathBuf::from("some/file.txt"));
+
+        let parser = File::new().with(|this| this.archive_separator("!"));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Ok(InputType::File(output)))
+    }
+
+    #[cfg(feature = "zip")]
+    #[test]
+    fn archive_separator_empty_member() {
+        let input = "@some/archive.zip!";
+
+        let parser = File::new().with(|this| this.archive_separator("!"));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Err(EKind::FILE.into()))
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_path() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let input = OsStr::from_bytes(b"@/tmp/caf\xe9");
+        let output = FilePath::new(PathBuf::from(OsStr::from_bytes(b"/tmp/caf\xe9")));
+
+        let parser = File::new();
+
+        let result = parser.parse_os_str(input);
+
+        assert_eq!(result, Ok(InputType::File(output)))
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_case_insensitive() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let parser = File::new().with(|this| this.marker("file://").case_insensitive(true));
+
+        match parser.parse_os_str(OsStr::from_bytes(b"FILE:///tmp/caf\xe9")) {
+            Ok(InputType::File(f)) => assert_eq!(f.path.as_os_str().as_bytes(), b"/tmp/caf\xe9"),
+            bad => panic!("expected File, got: {:?}", bad),
+        }
+
+        let parser = File::new().with(|this| {
+            this.marker(".IN")
+                .marker_position(MarkerPosition::Suffix)
+                .case_insensitive(true)
+        });
+
+        match parser.parse_os_str(OsStr::from_bytes(b"caf\xe9.in")) {
+            Ok(InputType::File(f)) => assert_eq!(f.path.as_os_str().as_bytes(), b"caf\xe9"),
+            bad => panic!("expected File, got: {:?}", bad),
+        }
+
+        let result = File::new()
+            .with(|this| this.marker("file://"))
+            .parse_os_str(OsStr::from_bytes(b"FILE:///tmp/caf\xe9"));
+        assert_eq!(result, Err(EKind::FILE.into()))
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_split_on_marker() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let parser = File::new().with(|this| this.split_on_marker(true));
+
+        match parser.parse_os_str(OsStr::from_bytes(b"@a\xff@b")) {
+            Ok(InputType::Files(files)) => {
+                let paths: Vec<_> = files
+                    .iter()
+                    .map(|f| f.path.as_os_str().as_bytes())
+                    .collect();
+                assert_eq!(paths, [&b"a\xff"[..], b"b"])
+            }
+            bad => panic!("expected Files, got: {:?}", bad),
+        }
+
+        let result = parser.parse_os_str(OsStr::from_bytes(b"@a\xff@@b"));
+        assert_eq!(result, Err(EKind::FILE.into()))
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_suffix() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let parser = File::new().with(|this| this.marker_position(MarkerPosition::Suffix));
+
+        match parser.parse_os_str(OsStr::from_bytes(b"caf\xe9@")) {
+            Ok(InputType::File(f)) => assert_eq!(f.path.as_os_str().as_bytes(), b"caf\xe9"),
+            bad => panic!("expected File, got: {:?}", bad),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_path_options() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let parser = File::new().with(|this| this.require_path_prefix(true));
+
+        let result = parser.parse_os_str(OsStr::from_bytes(b"@caf\xe9"));
+        assert_eq!(result, Err(EKind::FILE.into()));
+        assert!(parser
+            .parse_os_str(OsStr::from_bytes(b"@./caf\xe9"))
+            .is_ok());
+
+        let home = home_dir().expect("a home directory");
+        let parser = File::new().with(|this| this.expand_home(true));
+
+        match parser.parse_os_str(OsStr::from_bytes(b"@~/caf\xe9")) {
+            Ok(InputType::File(f)) => assert_eq!(f.path, home.join(OsStr::from_bytes(b"caf\xe9"))),
+            bad => panic!("expected File, got: {:?}", bad),
+        }
+    }
+
+    #[test]
+    fn split_on_marker_case_insensitive() {
+        let parser = File::new().with(|this| {
+            this.marker("file:")
+                .split_on_marker(true)
+                .case_insensitive(true)
+        });
+
+        match parser.parse_str("FILE:a File:b") {
+            Ok(InputType::Files(files)) => {
+                let paths: Vec<_> = files.iter().map(|f| f.path.clone()).collect();
+                assert_eq!(paths, [PathBuf::from("a "), PathBuf::from("b")])
+            }
+            bad => panic!("expected Files, got: {:?}", bad),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_failure() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let input = OsStr::from_bytes(b"caf\xe9");
+
+        let result = File::new().parse_os_str(input);
+        assert_eq!(result, Err(EKind::FILE.into()));
+
+        let result = File::new()
+            .with(|this| this.parser(test_custom_parser))
+            .parse_os_str(input);
+        assert_eq!(result, Err(EKind::REQUIRES_UTF8.into()))
+    }
+
+    #[test]
+    fn file_uri_success() {
+        let parser = File::new().with(|this| this.file_uri(true));
+
+        let cases = [
+            ("file:///tmp/x", "/tmp/x"),
+            ("file://localhost/tmp/x", "/tmp/x"),
+            ("FILE://LOCALHOST/tmp/x", "/tmp/x"),
+            ("file:/tmp/x", "/tmp/x"),
+            (
+                "file:///tmp/with%20space/caf%C3%A9.txt",
+                "/tmp/with space/café.txt",
+            ),
+            ("file:///tmp/x?query#fragment", "/tmp/x"),
+        ];
+
+        for (input, path) in &cases {
+            let output = FilePath::new(PathBuf::from(path));
+
+            let result = parser.parse_str(input);
+
+            assert_eq!(result, Ok(InputType::File(output)), "input: {}", input)
+        }
+    }
+
+    #[test]
+    fn file_uri_failure() {
+        let parser = File::new().with(|this| this.file_uri(true));
+
+        for input in &["file://otherhost/tmp/x", "file:///tmp/%zz", "file:relative"] {
+            let result = parser.parse_str(input);
+
+            assert_eq!(result, Err(EKind::FILE.into()), "input: {}", input)
+        }
+    }
+
+    #[test]
+    fn c_split_on_marker() {
+        let parser = File::new().with(|this| this.split_on_marker(true));
+
+        let result = parser.parse_str("@a@b");
+        let output = vec![
+            FilePath::new(PathBuf::from("a")),
+            FilePath::new(PathBuf::from("b")),
+        ];
+        assert_eq!(result, Ok(InputType::Files(output)));
+
+        let result = parser.parse_str("@a");
+        let output = FilePath::new(PathBuf::from("a"));
+        assert_eq!(result, Ok(InputType::File(output)));
+
+        for input in &["@a@", "@@a", "@a@@b", "@"] {
+            assert_eq!(parser.parse_str(input), Err(EKind::FILE.into()))
+        }
+    }
+
+    #[test]
+    fn c_expand_home() {
+        let home = home_dir().expect("a home directory");
+        let parser = File::new().with(|this| this.expand_home(true));
+
+        let cases = [
+            ("@~", home.clone()),
+            ("@~/sub/file", home.join("sub/file")),
+            ("@some/~/file", PathBuf::from("some/~/file")),
+            ("@~user/file", PathBuf::from("~user/file")),
+        ];
+
+        for (input, path) in cases {
+            let result = parser.parse_str(input);
+
+            assert_eq!(result, Ok(InputType::File(FilePath::new(path))))
+        }
+    }
+
+    #[test]
+    fn defaults_no_expand_home() {
+        let input = "@~/sub/file";
+        let output = FilePath::new(PathBuf::from("~/sub/file"));
+
+        let result = File::new().parse_str(input);
+
+        assert_eq!(result, Ok(InputType::File(output)))
+    }
+
+    #[test]
+    fn file_uri_marker_fallback() {
+        let input = "@some/file/here";
+        let output = FilePath::new(PathBuf::from("some/file/here"));
+
+        let parser = File::new().with(|this| this.file_uri(true));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Ok(InputType::File(output)))
+    }
+
+    #[test]
+    fn direct_io_option() {
+        let input = "@some/file/here";
+
+        let parser = File::new().with(|this| this.direct_io(true));
+
+        match parser.parse_str(input) {
+            Ok(InputType::File(f)) => assert!(f.options.direct_io),
+            bad => panic!("expected File, got: {:?}", bad),
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+    #[test]
+    fn direct_io_open() {
+        use std::io::Read;
+
+        let path = fixture("utf16le.txt");
+        let expected = std::fs::read(&path).unwrap();
+
+        let parser = File::new().with(|this| this.direct_io(true));
+        let mut file = parse_file(&parser, &format!("@{}", path.display()))
+            .open()
+            .unwrap();
+
+        // Unaligned reads either work with direct I/O, or the file was opened normally
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).unwrap();
+
+        assert_eq!(buf, expected)
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+    #[test]
+    fn sequential_open() {
+        use std::io::Read;
+
+        let path = fixture("lorem.txt");
+        let expected = std::fs::read(&path).unwrap();
+
+        let parser = File::new().with(|this| this.sequential(true));
+        let parsed = parse_file(&parser, &format!("@{}", path.display()));
+        assert!(parsed.options.sequential);
+
+        let mut buf = Vec::new();
+        parsed.open().unwrap().read_to_end(&mut buf).unwrap();
+
+        assert_eq!(buf, expected)
+    }
+
+    fn test_marker_parser<'a>(input: &'a str, marker: &str) -> nom::IResult<&'a str, PathBuf> {
+        nom::tag(marker)(input).map(|(path, _)| ("", PathBuf::from(path)))
+    }
+
+    fn test_custom_parser<'a>(input: &'a str, _: &str) -> nom::IResult<&'a str, PathBuf> {
         nom::context("FILE", nom::tag("file://"))(input).map(|(path, _)| ("", PathBuf::from(path)))
     }
 }
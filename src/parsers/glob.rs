@@ -0,0 +1,325 @@
+use super::{
+    failed_at,
+    file::FilePath,
+    nom::{self, Finish},
+    EKind, InputError, InputType, Named, NomError, Parser, ParserName, Weight,
+};
+
+use std::fmt;
+
+/// Function signature of the parser Glob calls for processing input
+pub type GlobParser = for<'a, 'b> fn(&'a str, &'b str) -> nom::IResult<&'a str, String>;
+
+/// Construct for expanding the given input as a glob pattern into every file it matches, e.g
+/// `@'logs/*.txt'`. Input which doesn't contain any glob syntax, or a pattern which doesn't match
+/// any files, is rejected so that it can fall through to the [File][super::File] parser.
+///
+/// Relative patterns are expanded against the current directory with the [glob](::glob) crate.
+/// Supported syntax is `*`, `?`, `[a-z]` / `[!a-z]` classes and `**` for matching any number of
+/// directories. Names which aren't valid UTF-8 are never matched by a wildcard.
+///
+/// Input naming a file or directory which exists is also rejected, even if it contains glob
+/// syntax, so that `@file[1].txt` reads that file rather than `file1.txt`.
+///
+/// A [Config][crate::Config] will read every matched file one after another when the produced
+/// input is accessed, use [parse_many][crate::Config::parse_many] to get an input per file
+/// instead.
+#[derive(Clone, Default)]
+pub struct Glob {
+    marker: Option<String>,
+    parser: Option<GlobParser>,
+    weight: Option<u8>,
+    label: Option<String>,
+    /// Set by [Builder::max_expanded][crate::Builder::max_expanded]
+    limit: Option<usize>,
+}
+
+impl Glob {
+    /// The default weighting for [Glob], which runs just before [File][super::File]
+    pub const DEFAULT_WEIGHT: u8 = 125;
+    /// Default marker for [Glob], the same as [File][super::File]'s
+    pub const DEFAULT_MARKER: &str = "@";
+    /// Default parser implementation for [Glob]
+    pub const DEFAULT_PARSER: GlobParser = default_glob_parser;
+
+    /// Instantiate a new Glob parser with sensible defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convenience function for modifying the semantics of
+    /// this parser
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use grab::parsers::Glob;
+    ///
+    /// // Require globs to be explicitly marked, e.g glob:*.txt
+    /// let glob = Glob::new().with(|this| this.marker("glob:"));
+    /// ```
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnMut(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        let mut actions = f;
+
+        actions(&mut this);
+
+        this
+    }
+
+    /// Modify the marker string for triggering this Glob parser.
+    /// This marker is passed to the parser function as the second &str
+    /// argument.
+    pub fn marker(&mut self, marker: impl AsRef<str>) -> &mut Self {
+        self.marker = Some(marker.as_ref().to_string());
+
+        self
+    }
+
+    /// Replace the parser for this Glob with a different one. Expects a
+    /// _function_ (not closure) with the following arguments + return:
+    ///
+    /// fn my_parser<'a>(input: &'a str, marker: &str) -> crate::nom::IResult<&'a str, String>
+    /// {
+    ///     /* ... */
+    /// }
+    pub fn parser(&mut self, parser: GlobParser) -> &mut Self {
+        self.parser = Some(parser);
+
+        self
+    }
+
+    /// Set this parser's weight. Lower numbers will be ran before greater.
+    pub fn weight(&mut self, weight: u8) -> &mut Self {
+        self.weight = Some(weight);
+
+        self
+    }
+
+    /// Attach a human readable label to this parser, which will be included in any
+    /// [InputError] it produces.
+    pub fn label(&mut self, label: impl AsRef<str>) -> &mut Self {
+        self.label = Some(label.as_ref().to_string());
+
+        self
+    }
+
+    /// Stop expanding once more than limit files have matched
+    pub(crate) fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+    }
+
+    fn get_weight(&self) -> u8 {
+        self.weight.unwrap_or(Self::DEFAULT_WEIGHT)
+    }
+
+    fn get_marker(&self) -> &str {
+        self.marker.as_deref().unwrap_or(Self::DEFAULT_MARKER)
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<String, NomError<&'a str>> {
+        let marker = self.get_marker();
+
+        let (_, pattern) = self
+            .parser
+            .map(|p| p(input, marker))
+            .unwrap_or_else(|| Self::DEFAULT_PARSER(input, marker))
+            .finish()?;
+
+        Ok(pattern)
+    }
+
+    fn expand(&self, pattern: &str) -> Result<Vec<FilePath>, InputError> {
+        let found = crate::glob::expand(pattern, self.limit).map_err(|e| {
+            self.error()
+                .with(|this| this.add_context(format!("unable to expand {}: {}", pattern, e)))
+        })?;
+
+        if found.is_empty() {
+            return Err(self
+                .error()
+                .with(|this| this.add_context(format!("no files match {}", pattern))));
+        }
+
+        Ok(found.into_iter().map(FilePath::new).collect())
+    }
+
+    fn new_error(&self, p_error: NomError<&str>) -> InputError {
+        self.error()
+            .with(|this| this.add_context(failed_at(&p_error)))
+    }
+
+    fn error(&self) -> InputError {
+        let mut error = InputError::new(EKind::GLOB);
+
+        if let Some(ref label) = self.label {
+            error.add_label(label);
+        }
+
+        error
+    }
+}
+
+impl Parser for Glob {
+    fn parse_str(&self, s: &str) -> Result<InputType, InputError> {
+        let pattern = self.parse(s).map_err(|e| self.new_error(e))?;
+
+        // A path like file[1].txt may name a file, rather than a pattern matching file1.txt
+        if std::path::Path::new(&pattern).exists() {
+            return Err(self.error().with(|this| {
+                this.add_context(format!("{} is an existing path, not a pattern", pattern))
+            }));
+        }
+
+        self.expand(&pattern).map(InputType::Files)
+    }
+}
+
+impl Weight for Glob {
+    fn weight(&self) -> u8 {
+        self.get_weight()
+    }
+}
+
+impl Named for Glob {
+    fn name(&self) -> ParserName {
+        ParserName::Glob
+    }
+
+    fn marker(&self) -> &str {
+        self.get_marker()
+    }
+}
+
+impl fmt::Debug for Glob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Glob")
+            .field("marker", &self.get_marker())
+            .field(
+                "parser",
+                &self
+                    .parser
+                    .map_or("Default GlobParser", |_| "Custom GlobParser"),
+            )
+            .finish()
+    }
+}
+
+/// Default parser for globs. It expects input starting with the 'marker', followed by a pattern
+/// containing at least one of `*`, `?` or `[`, returning the pattern.
+pub fn default_glob_parser<'a>(input: &'a str, marker: &str) -> nom::IResult<&'a str, String> {
+    let (pattern, _) = nom::context("GLOB", nom::tag(marker))(input)?;
+
+    if !crate::glob::is_pattern(pattern) {
+        use nom::{make_error, ErrorKind};
+
+        return Err(nom::Err::Error(make_error(pattern, ErrorKind::Verify)));
+    }
+
+    Ok(("", String::from(pattern)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{fixture, TempDir};
+
+    fn files(names: &[&str]) -> InputType {
+        let paths = names.iter().map(|name| fixture(name));
+
+        InputType::Files(paths.map(FilePath::new).collect())
+    }
+
+    #[test]
+    fn defaults_success() {
+        let input = format!("@{}", fixture("utf16*.txt").display());
+
+        let parser = Glob::new();
+
+        let result = parser.parse_str(&input);
+
+        assert_eq!(result, Ok(files(&["utf16be.txt", "utf16le.txt"])))
+    }
+
+    #[test]
+    fn defaults_failure() {
+        for input in &["invalid glob input", "@not/a/pattern", "*.txt"] {
+            let parser = Glob::new();
+
+            let result = parser.parse_str(input);
+
+            assert_eq!(result, Err(EKind::GLOB.into()))
+        }
+    }
+
+    #[test]
+    fn defaults_no_matches() {
+        let input = format!("@{}", fixture("*.nothing").display());
+
+        let parser = Glob::new();
+
+        let err = parser.parse_str(&input).unwrap_err();
+
+        assert!(err.contains(EKind::GLOB));
+        assert!(err.to_string().contains("no files match"))
+    }
+
+    #[test]
+    fn defaults_existing_path() {
+        let dir = TempDir::new("glob-literal");
+        dir.write("file[1].txt", "literal");
+        dir.write("file1.txt", "matched");
+
+        let parser = Glob::new();
+
+        let literal = parser.parse_str(&format!("@{}", dir.join("file[1].txt").display()));
+        let pattern = parser.parse_str(&format!("@{}", dir.join("file[0-9].txt").display()));
+
+        assert_eq!(literal, Err(EKind::GLOB.into()));
+        assert_eq!(
+            pattern,
+            Ok(InputType::Files(vec![FilePath::new(dir.join("file1.txt"))]))
+        )
+    }
+
+    #[test]
+    fn c_marker_success() {
+        let input = format!("glob:{}", fixture("*.bin").display());
+
+        let parser = Glob::new().with(|this| this.marker("glob:"));
+
+        let result = parser.parse_str(&input);
+
+        assert_eq!(result, Ok(files(&["binary.bin"])))
+    }
+
+    #[test]
+    fn c_marker_failure() {
+        let input = format!("@{}", fixture("*.bin").display());
+
+        let parser = Glob::new().with(|this| this.marker("glob:"));
+
+        let result = parser.parse_str(&input);
+
+        assert_eq!(result, Err(EKind::GLOB.into()))
+    }
+
+    #[test]
+    fn c_parser_success() {
+        let input = "lorem";
+
+        let parser = Glob::new().with(|this| this.parser(test_custom_parser));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Ok(files(&["lorem.txt"])))
+    }
+
+    fn test_custom_parser<'a>(input: &'a str, _: &str) -> nom::IResult<&'a str, String> {
+        Ok(("", fixture(&format!("{}*", input)).display().to_string()))
+    }
+}
@@ -10,17 +10,59 @@
 //! use grab::parsers::reexport::nom;
 //! ```
 
+mod base64;
+#[cfg(feature = "clipboard")]
+mod clipboard;
+mod env;
 mod file;
+#[cfg(feature = "glob")]
+mod glob;
 mod stdin;
+#[cfg(feature = "tcp")]
+mod tcp;
 mod text;
+mod url;
 
-use std::ffi::OsStr;
+use std::{
+    ffi::OsStr,
+    fmt, io,
+    sync::{Arc, Mutex, PoisonError},
+};
 
 use crate::error::input::{EKind, InputError};
 
 use self::nom::NomError;
 
-pub use {file::File, stdin::Stdin, text::Text};
+pub use encoding_rs::Encoding;
+
+pub use {
+    base64::Base64,
+    env::Env,
+    file::{Endianness, File, MarkerPosition},
+    stdin::Stdin,
+    text::Text,
+    url::Url,
+};
+
+#[cfg(feature = "glob")]
+pub use glob::Glob;
+
+#[cfg(feature = "tcp")]
+pub use tcp::Tcp;
+
+#[cfg(feature = "clipboard")]
+pub use clipboard::Clipboard;
+
+pub(crate) use {
+    base64::encode as encode_base64,
+    file::{is_fifo, FilePath},
+};
+
+#[cfg(windows)]
+pub(crate) use file::drive_path_parser;
+
+#[cfg(unix)]
+pub(crate) use file::file_uri;
 
 /// Private trait that describes the conversion of some input into a reference to some kind of
 /// input type.
@@ -33,6 +75,7 @@ pub(crate) trait Parser {
         self.parse_str(input)
     }
 
+    #[allow(dead_code)]
     fn parse_bytes(&self, input: &[u8]) -> Result<InputType, InputError> {
         let input = std::str::from_utf8(input).map_err(|_| EKind::REQUIRES_UTF8)?;
 
@@ -46,16 +89,208 @@ pub(crate) trait Weight {
     fn weight(&self) -> u8;
 }
 
-/// Glue trait for creating trait objects with both Parser and Weight methods
-pub(crate) trait WeightedParser: Parser + Weight {}
+/// Describes which of this crate's parsers an implementor is, and the marker it is triggered by
+pub(crate) trait Named {
+    fn name(&self) -> ParserName;
+
+    fn marker(&self) -> &str;
+
+    /// Every marker this parser accepts, for parsers which accept more than one
+    fn markers(&self) -> Vec<&str> {
+        vec![self.marker()]
+    }
+}
+
+/// Glue trait for creating trait objects with Parser, Weight and Named methods
+pub(crate) trait WeightedParser: Parser + Weight + Named {}
+
+impl<T> WeightedParser for T where T: Parser + Weight + Named {}
+
+/// Identifies one of the parsers that a [Config][crate::Config] may contain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParserName {
+    /// The [Text] parser
+    Text,
+    /// The [Stdin] parser
+    Stdin,
+    /// The [File] parser
+    File,
+    /// The [Url] parser
+    Url,
+    /// The [Env] parser
+    Env,
+    /// The [Base64] parser
+    Base64,
+    /// The [Glob] parser
+    #[cfg(feature = "glob")]
+    Glob,
+    /// The [Tcp] parser
+    #[cfg(feature = "tcp")]
+    Tcp,
+    /// The [Clipboard] parser
+    #[cfg(feature = "clipboard")]
+    Clipboard,
+}
+
+impl ParserName {
+    /// Returns the lowercase name of this parser, e.g "file"
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Stdin => "stdin",
+            Self::File => "file",
+            Self::Url => "url",
+            Self::Env => "env",
+            Self::Base64 => "base64",
+            #[cfg(feature = "glob")]
+            Self::Glob => "glob",
+            #[cfg(feature = "tcp")]
+            Self::Tcp => "tcp",
+            #[cfg(feature = "clipboard")]
+            Self::Clipboard => "clipboard",
+        }
+    }
+}
 
-impl<T> WeightedParser for T where T: Parser + Weight {}
+impl fmt::Display for ParserName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum InputType {
-    Stdin,
+    Stdin(stdin::StdinOptions),
     File(file::FilePath),
+    Url(String),
+    Env(String),
+    Base64(Vec<u8>),
+    Bytes(Vec<u8>),
+    Files(Vec<FilePath>),
     UTF8(String),
+    LazyText(LazyText),
+    OpenFile(OpenFile),
+    Reader(SharedReader),
+    /// The `host:port` address of a TCP server
+    #[cfg(feature = "tcp")]
+    Tcp(String),
+    #[cfg(feature = "clipboard")]
+    Clipboard,
+}
+
+/// Describe where a parser failed, for use as an [InputError]'s context
+pub(crate) fn failed_at(err: &NomError<&str>) -> String {
+    format!("{} failed at {:?}", err.code.description(), err.input)
+}
+
+/// Text which is only produced once the input is accessed
+#[derive(Clone)]
+pub(crate) struct LazyText {
+    f: Arc<dyn Fn() -> String + Send + Sync>,
+}
+
+impl LazyText {
+    pub fn new(f: impl Fn() -> String + Send + Sync + 'static) -> Self {
+        Self { f: Arc::new(f) }
+    }
+
+    pub fn call(&self) -> String {
+        (self.f)()
+    }
+
+    /// An identity for this closure, consistent with [PartialEq]
+    pub fn addr(&self) -> usize {
+        Arc::as_ptr(&self.f) as *const () as usize
+    }
+}
+
+impl PartialEq for LazyText {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.f, &other.f)
+    }
+}
+
+impl fmt::Debug for LazyText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LazyText")
+    }
+}
+
+/// A file opened by the caller rather than by a parser, see
+/// [Input::from_open_file][crate::Input::from_open_file]
+#[derive(Debug, Clone)]
+pub(crate) struct OpenFile {
+    file: Arc<std::fs::File>,
+}
+
+impl OpenFile {
+    pub fn new(file: std::fs::File) -> Self {
+        Self {
+            file: Arc::new(file),
+        }
+    }
+
+    /// A new handle to the file, sharing its position with every other handle
+    pub fn try_clone(&self) -> io::Result<std::fs::File> {
+        self.file.try_clone()
+    }
+
+    pub fn metadata(&self) -> io::Result<std::fs::Metadata> {
+        self.file.metadata()
+    }
+
+    /// An identity for this file, consistent with [PartialEq]
+    pub fn addr(&self) -> usize {
+        Arc::as_ptr(&self.file) as usize
+    }
+}
+
+impl PartialEq for OpenFile {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.file, &other.file)
+    }
+}
+
+/// A reader given by the caller, see [Input::from_reader][crate::Input::from_reader]. Every
+/// clone reads from the same underlying reader.
+#[derive(Clone)]
+pub(crate) struct SharedReader {
+    reader: Arc<Mutex<Box<dyn io::Read + Send>>>,
+}
+
+impl SharedReader {
+    pub fn new(reader: impl io::Read + Send + 'static) -> Self {
+        Self {
+            reader: Arc::new(Mutex::new(Box::new(reader))),
+        }
+    }
+
+    /// An identity for this reader, consistent with [PartialEq]
+    pub fn addr(&self) -> usize {
+        Arc::as_ptr(&self.reader) as *const () as usize
+    }
+}
+
+impl io::Read for SharedReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.reader
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .read(buf)
+    }
+}
+
+impl PartialEq for SharedReader {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.reader, &other.reader)
+    }
+}
+
+impl fmt::Debug for SharedReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedReader")
+    }
 }
 
 // Reexport nom parsers in a manner that doesn't
@@ -64,15 +299,15 @@ mod nom {
     pub type IResult<I, O, E = NomError<I>> = Result<(I, O), nom::Err<E>>;
     pub type NomError<I> = nom::error::Error<I>;
 
-    pub use nom::Finish;
+    pub use nom::{Err, Finish};
 
-    pub use nom::bytes::complete::tag;
+    pub use nom::branch::alt;
 
-    pub use nom::combinator::{all_consuming, into, map, value};
+    pub use nom::bytes::complete::{tag, tag_no_case};
 
-    pub use nom::branch::alt;
+    pub use nom::combinator::{all_consuming, value};
 
-    pub use nom::error::{context, ParseError};
+    pub use nom::error::{context, make_error, ErrorKind};
 }
 
 /// This is hidden by default to avoid cluttering this crate's docs. If you want to create custom
@@ -1,13 +1,24 @@
 use super::{
+    failed_at,
     nom::{self, Finish},
-    EKind, InputError, InputType, NomError, Parser, Weight,
+    EKind, InputError, InputType, Named, NomError, Parser, ParserName, Weight,
 };
 
-use std::fmt;
+use crate::error::access::AccessError;
+
+use std::{
+    fmt,
+    io::{self, IsTerminal},
+    sync::Arc,
+};
 
 /// Function signature of the parser Stdin calls for processing input
 pub type StdinParser = for<'a, 'b> fn(&'a str, &'b str) -> nom::IResult<&'a str, ()>;
 
+/// Closure equivalent of [StdinParser], see [Stdin::parser_fn]
+pub type StdinParserFn =
+    dyn for<'a, 'b> Fn(&'a str, &'b str) -> nom::IResult<&'a str, ()> + Send + Sync;
+
 /// A construct for handling the parsing of a given input string and determining
 /// if the program's stdin should be called in leu of. By default, it will only
 /// indicate stdin should be used if the given input is a single dash ('-'),
@@ -15,15 +26,20 @@ pub type StdinParser = for<'a, 'b> fn(&'a str, &'b str) -> nom::IResult<&'a str,
 #[derive(Clone, Default)]
 pub struct Stdin {
     marker: Option<String>,
+    extra_markers: Vec<String>,
     parser: Option<StdinParser>,
+    parser_fn: Option<Arc<StdinParserFn>>,
     weight: Option<u8>,
+    label: Option<String>,
+    case_insensitive: bool,
+    options: StdinOptions,
 }
 
 impl Stdin {
     /// The default weighting for [Stdin]
     pub const DEFAULT_WEIGHT: u8 = 140;
     /// Default marker for [Stdin]
-    pub const DEFAULT_MARKER: &'static str = "-";
+    pub const DEFAULT_MARKER: &str = "-";
     /// Default parser implementation for [Stdin]
     pub const DEFAULT_PARSER: StdinParser = default_stdin_parser;
 
@@ -64,10 +80,21 @@ impl Stdin {
         self
     }
 
+    /// Accept the given marker in addition to the [marker][Stdin::marker], e.g `--` as well as
+    /// `-`. Each marker is tried in turn, the main marker first, and must match the whole input
+    /// like the main marker does.
+    ///
+    /// Each marker is in turn passed to the parser function as the second &str argument.
+    pub fn add_marker(&mut self, marker: impl AsRef<str>) -> &mut Self {
+        self.extra_markers.push(marker.as_ref().to_string());
+
+        self
+    }
+
     /// Replace the parser for this Stdin with a different one. Expects a
     /// _function_ (not closure) with the following arguments + return:
     ///
-    /// fn my_parser<'a, 'b>(input: &'a str, marker: &'b str) -> crate::nom::IResult<&'a str, ()>
+    /// fn my_parser<'a>(input: &'a str, marker: &str) -> crate::nom::IResult<&'a str, ()>
     /// {
     ///     /* ... */
     /// }
@@ -77,6 +104,17 @@ impl Stdin {
         self
     }
 
+    /// Like [parser][Stdin::parser], but accepts a closure which may capture state. If set,
+    /// this is used in preference to any function given to [parser][Stdin::parser].
+    pub fn parser_fn<F>(&mut self, parser: F) -> &mut Self
+    where
+        F: for<'a, 'b> Fn(&'a str, &'b str) -> nom::IResult<&'a str, ()> + Send + Sync + 'static,
+    {
+        self.parser_fn = Some(Arc::new(parser));
+
+        self
+    }
+
     /// Set this parser's weight. Lower numbers will be ran before greater.
     pub fn weight(&mut self, weight: u8) -> &mut Self {
         self.weight = Some(weight);
@@ -84,6 +122,70 @@ impl Stdin {
         self
     }
 
+    /// Attach a human readable label to this parser, which will be included in any
+    /// [InputError] it produces.
+    pub fn label(&mut self, label: impl AsRef<str>) -> &mut Self {
+        self.label = Some(label.as_ref().to_string());
+
+        self
+    }
+
+    /// Match the marker ignoring ASCII case, so `-in` also accepts `-IN`. By default, markers are
+    /// case-sensitive. This only applies to the default parser, custom [parsers][Stdin::parser]
+    /// are given the marker unchanged.
+    pub fn case_insensitive(&mut self, insensitive: bool) -> &mut Self {
+        self.case_insensitive = insensitive;
+
+        self
+    }
+
+    /// Set the C runtime's translation mode for stdin (file descriptor 0) to binary when it is
+    /// accessed, via `_setmode(0, _O_BINARY)`. This only has an effect on Windows, and only on
+    /// code in the process which reads stdin through the C runtime, e.g C libraries using
+    /// `fread` on `stdin`.
+    ///
+    /// Reading the input itself is unaffected, as Rust's [io::Stdin] reads the stdin handle
+    /// directly and never translates line endings, whatever the C runtime's mode is.
+    pub fn binary(&mut self, binary: bool) -> &mut Self {
+        self.options.binary = binary;
+
+        self
+    }
+
+    /// Fail to access stdin if it is an interactive terminal, rather than blocking while waiting
+    /// for the user to type something. This catches forgetting to pipe input into a program,
+    /// and reports it as an [AccessError] of [Kind::Stdin][crate::error::access::Kind::Stdin].
+    pub fn require_piped(&mut self, require: bool) -> &mut Self {
+        self.options.require_piped = require;
+
+        self
+    }
+
+    /// Fail to access stdin if it contains no data, rather than silently reading nothing. This
+    /// catches piping in the output of a command which produced nothing, and reports it as an
+    /// [AccessError] of [Kind::EmptyStdin][crate::error::access::Kind::EmptyStdin].
+    ///
+    /// Checking requires reading from stdin, so accessing it blocks until the first data
+    /// arrives. The data read while checking is still returned by the input.
+    pub fn require_nonempty(&mut self, require: bool) -> &mut Self {
+        self.options.require_nonempty = require;
+
+        self
+    }
+
+    /// Transparently decompress stdin if it starts with the gzip magic bytes (`1f 8b`). Other
+    /// data is passed through unchanged.
+    ///
+    /// Only the first two bytes are read when the input is accessed. Gzip data is then
+    /// decompressed as it is read, so piped data keeps streaming and limits like
+    /// [max_bytes][crate::Builder::max_bytes] apply to the decompressed data.
+    #[cfg(feature = "gzip")]
+    pub fn auto_decompress(&mut self, decompress: bool) -> &mut Self {
+        self.options.auto_decompress = decompress;
+
+        self
+    }
+
     fn get_weight(&self) -> u8 {
         self.weight.unwrap_or(Self::DEFAULT_WEIGHT)
     }
@@ -92,26 +194,56 @@ impl Stdin {
         self.marker.as_deref().unwrap_or(Self::DEFAULT_MARKER)
     }
 
+    fn get_markers(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.get_marker()).chain(self.extra_markers.iter().map(String::as_str))
+    }
+
     fn parse<'a>(&self, input: &'a str) -> Result<(), NomError<&'a str>> {
-        let marker = self.get_marker();
+        let mut error = None;
+
+        for marker in self.get_markers() {
+            let result = match (&self.parser_fn, self.parser) {
+                (Some(f), _) => f(input, marker),
+                (None, Some(p)) => p(input, marker),
+                (None, None) if self.case_insensitive => {
+                    default_stdin_parser_no_case(input, marker)
+                }
+                (None, None) => Self::DEFAULT_PARSER(input, marker),
+            };
+
+            match result.finish() {
+                Ok(_) => return Ok(()),
+                Err(e) => error = error.or(Some(e)),
+            }
+        }
+
+        Err(error.unwrap_or_else(|| nom::make_error(input, nom::ErrorKind::Tag)))
+    }
 
-        self.parser
-            .map(|p| p(input, marker))
-            .unwrap_or_else(|| Self::DEFAULT_PARSER(input, marker))
-            .finish()?;
+    fn new_error(&self, p_error: NomError<&str>) -> InputError {
+        let mut error = InputError::new(EKind::STDIN);
 
-        Ok(())
+        if let Some(ref label) = self.label {
+            error.add_label(label);
+        }
+
+        error.add_context(failed_at(&p_error));
+
+        error
     }
+}
 
-    fn new_error(&self, _p_error: NomError<&str>) -> InputError {
-        InputError::new(EKind::STDIN)
+impl Stdin {
+    /// The options stdin is read with, once parsed
+    pub(crate) fn options(&self) -> StdinOptions {
+        self.options.clone()
     }
 }
 
 impl Parser for Stdin {
     fn parse_str(&self, s: &str) -> Result<InputType, InputError> {
         self.parse(s)
-            .map(|_| InputType::Stdin)
+            .map(|_| InputType::Stdin(self.options.clone()))
             .map_err(|e| self.new_error(e))
     }
 }
@@ -122,33 +254,139 @@ impl Weight for Stdin {
     }
 }
 
+impl Named for Stdin {
+    fn name(&self) -> ParserName {
+        ParserName::Stdin
+    }
+
+    fn marker(&self) -> &str {
+        self.get_marker()
+    }
+
+    fn markers(&self) -> Vec<&str> {
+        self.get_markers().collect()
+    }
+}
+
 impl fmt::Debug for Stdin {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Stdin")
             .field("marker", &self.get_marker())
             .field(
                 "parser",
-                &self
-                    .parser
-                    .map_or("Default StdinParser", |_| "Custom StdinParser"),
+                &match self.parser.is_some() || self.parser_fn.is_some() {
+                    true => "Custom StdinParser",
+                    false => "Default StdinParser",
+                },
             )
             .finish()
     }
 }
 
+/// Settings given to a [Stdin] parser which only take effect once the input is accessed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct StdinOptions {
+    pub binary: bool,
+    pub require_piped: bool,
+    pub require_nonempty: bool,
+    #[cfg(feature = "gzip")]
+    pub auto_decompress: bool,
+}
+
+impl StdinOptions {
+    /// Apply these options to the process's stdin, before it is read from
+    pub fn apply(&self) -> Result<(), AccessError> {
+        self.apply_with(&ProcessStdin)
+    }
+
+    fn apply_with(&self, stdin: &dyn Terminal) -> Result<(), AccessError> {
+        if self.require_piped && stdin.is_terminal() {
+            return Err(AccessError::stdin_is_terminal());
+        }
+
+        if self.binary {
+            binary::set_stdin_binary().map_err(AccessError::read)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Seam for checking whether stdin is attached to an interactive terminal
+trait Terminal {
+    fn is_terminal(&self) -> bool;
+}
+
+/// The process's real stdin
+struct ProcessStdin;
+
+impl Terminal for ProcessStdin {
+    fn is_terminal(&self) -> bool {
+        io::stdin().is_terminal()
+    }
+}
+
+#[cfg(windows)]
+mod binary {
+    use std::{io, os::raw::c_int};
+
+    const STDIN_FILENO: c_int = 0;
+    const O_BINARY: c_int = 0x8000;
+
+    extern "C" {
+        fn _setmode(fd: c_int, mode: c_int) -> c_int;
+        fn _errno() -> *mut c_int;
+    }
+
+    pub fn set_stdin_binary() -> io::Result<()> {
+        set_mode(STDIN_FILENO, O_BINARY).map(|_| ())
+    }
+
+    /// Sets the translation mode of the given CRT file descriptor, returning the previous mode
+    pub fn set_mode(fd: c_int, mode: c_int) -> io::Result<c_int> {
+        // SAFETY: _setmode validates fd, returning -1 for invalid descriptors
+        match unsafe { _setmode(fd, mode) } {
+            // SAFETY: _errno returns a valid pointer to the calling thread's errno. _setmode
+            // reports failures through errno, not GetLastError
+            -1 => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("_setmode failed with errno {}", unsafe { *_errno() }),
+            )),
+            prev => Ok(prev),
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod binary {
+    use std::io;
+
+    /// There is no C runtime translation mode outside of Windows
+    pub fn set_stdin_binary() -> io::Result<()> {
+        Ok(())
+    }
+}
+
 /// The default parser implementation for reading from stdin. It will only trigger on
 /// a singular '-', in the style of kubectl, e.g kubectl apply -f -
-pub fn default_stdin_parser<'a, 'b>(input: &'a str, marker: &'b str) -> nom::IResult<&'a str, ()> {
+pub fn default_stdin_parser<'a>(input: &'a str, marker: &str) -> nom::IResult<&'a str, ()> {
     let child = nom::context("STDIN", nom::all_consuming(nom::tag(marker)));
 
     nom::value((), child)(input)
 }
 
+/// Like [default_stdin_parser], but the marker is matched ignoring ASCII case
+fn default_stdin_parser_no_case<'a>(input: &'a str, marker: &str) -> nom::IResult<&'a str, ()> {
+    let child = nom::context("STDIN", nom::all_consuming(nom::tag_no_case(marker)));
+
+    nom::value((), child)(input)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const BAD_INPUT: &'static str = "invalid stdin input";
+    const BAD_INPUT: &str = "invalid stdin input";
 
     #[test]
     fn defaults_success() {
@@ -158,7 +396,22 @@ mod tests {
 
         let result = parser.parse_str(input);
 
-        assert_eq!(result, Ok(InputType::Stdin))
+        assert_eq!(result, Ok(InputType::Stdin(StdinOptions::default())))
+    }
+
+    #[test]
+    fn c_case_insensitive() {
+        let parser = Stdin::new().with(|this| this.marker("-in").case_insensitive(true));
+
+        let result = parser.parse_str("-IN");
+
+        assert_eq!(result, Ok(InputType::Stdin(StdinOptions::default())));
+        assert_eq!(
+            Stdin::new()
+                .with(|this| this.marker("-in"))
+                .parse_str("-IN"),
+            Err(EKind::STDIN.into())
+        )
     }
 
     #[test]
@@ -182,7 +435,7 @@ mod tests {
 
         let result = parser.parse_str(input);
 
-        assert_eq!(result, Ok(InputType::Stdin))
+        assert_eq!(result, Ok(InputType::Stdin(StdinOptions::default())))
     }
 
     #[test]
@@ -198,6 +451,34 @@ mod tests {
         assert_eq!(result, Err(EKind::STDIN.into()))
     }
 
+    #[test]
+    fn c_add_marker() {
+        let parser = Stdin::new().with(|this| this.add_marker("--"));
+
+        for input in &["-", "--"] {
+            let result = parser.parse_str(input);
+
+            assert_eq!(
+                result,
+                Ok(InputType::Stdin(StdinOptions::default())),
+                "{}",
+                input
+            )
+        }
+
+        for input in &["-x", "--x", "---", ""] {
+            assert_eq!(
+                parser.parse_str(input),
+                Err(EKind::STDIN.into()),
+                "{}",
+                input
+            )
+        }
+
+        assert_eq!(parser.markers(), vec!["-", "--"]);
+        assert_eq!(Stdin::new().parse_str("--"), Err(EKind::STDIN.into()))
+    }
+
     #[test]
     fn c_parser_success() {
         let input = "- extra stuff";
@@ -206,7 +487,7 @@ mod tests {
 
         let result = parser.parse_str(input);
 
-        assert_eq!(result, Ok(InputType::Stdin))
+        assert_eq!(result, Ok(InputType::Stdin(StdinOptions::default())))
     }
 
     #[test]
@@ -220,7 +501,86 @@ mod tests {
         assert_eq!(result, Err(EKind::STDIN.into()))
     }
 
-    fn test_custom_parser<'a, 'b>(input: &'a str, marker: &'b str) -> nom::IResult<&'a str, ()> {
+    #[test]
+    fn c_parser_fn_success() {
+        let marker = String::from("stdin");
+
+        let parser = Stdin::new().with(|this| {
+            let marker = marker.clone();
+
+            this.parser_fn(move |input, _| nom::value((), nom::tag(marker.as_str()))(input))
+        });
+
+        assert_eq!(
+            parser.parse_str("stdin"),
+            Ok(InputType::Stdin(StdinOptions::default()))
+        );
+        assert_eq!(parser.parse_str("-"), Err(EKind::STDIN.into()))
+    }
+
+    #[test]
+    fn c_binary() {
+        let input = Stdin::DEFAULT_MARKER;
+        let output = StdinOptions {
+            binary: true,
+            ..Default::default()
+        };
+
+        let parser = Stdin::new().with(|this| this.binary(true));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Ok(InputType::Stdin(output)))
+    }
+
+    #[test]
+    fn binary_options_apply() {
+        let options = StdinOptions {
+            binary: true,
+            ..Default::default()
+        };
+
+        assert!(options.apply_with(&FakeTerminal(false)).is_ok())
+    }
+
+    struct FakeTerminal(bool);
+
+    impl Terminal for FakeTerminal {
+        fn is_terminal(&self) -> bool {
+            self.0
+        }
+    }
+
+    #[test]
+    fn require_piped() {
+        use crate::error::access::Kind;
+
+        let options = StdinOptions {
+            require_piped: true,
+            ..Default::default()
+        };
+
+        let err = options.apply_with(&FakeTerminal(true)).unwrap_err();
+        assert_eq!(err.kind(), Kind::Stdin);
+
+        assert!(options.apply_with(&FakeTerminal(false)).is_ok());
+        assert!(StdinOptions::default()
+            .apply_with(&FakeTerminal(true))
+            .is_ok())
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn binary_set_mode() {
+        binary::set_stdin_binary().unwrap();
+
+        // Setting the mode again reports the previous, binary, mode
+        assert_eq!(binary::set_mode(0, 0x8000).unwrap(), 0x8000);
+        let err = binary::set_mode(-1, 0x8000).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput)
+    }
+
+    fn test_custom_parser<'a>(input: &'a str, marker: &str) -> nom::IResult<&'a str, ()> {
         let child = nom::context("STDIN", nom::tag(marker));
 
         nom::value((), child)(input)
@@ -0,0 +1,273 @@
+use super::{
+    failed_at,
+    nom::{self, Finish},
+    EKind, InputError, InputType, Named, NomError, Parser, ParserName, Weight,
+};
+
+use std::{fmt, net::SocketAddr};
+
+/// Function signature of the parser Tcp calls for processing input
+pub type TcpParser = for<'a, 'b> fn(&'a str, &'b str) -> nom::IResult<&'a str, String>;
+
+/// Construct for treating the given input as the address of a TCP server, e.g
+/// `tcp://localhost:8080`, which is connected to when the input is accessed. Reading the input
+/// yields every byte the server sends until it closes the connection.
+///
+/// The address must be a host name or IP address followed by a port, IPv6 addresses are
+/// written in brackets, as in `tcp://[::1]:8080`. Host names are only resolved once the input is
+/// accessed, and each address they resolve to is tried in turn. Connecting gives up on an
+/// address after 30 seconds, and reads fail once the server sends nothing for 30 seconds.
+#[derive(Clone, Default)]
+pub struct Tcp {
+    marker: Option<String>,
+    parser: Option<TcpParser>,
+    weight: Option<u8>,
+    label: Option<String>,
+}
+
+impl Tcp {
+    /// The default weighting for [Tcp], which runs just after [Url][super::Url]
+    pub const DEFAULT_WEIGHT: u8 = 137;
+    /// Default marker for [Tcp]
+    pub const DEFAULT_MARKER: &str = "tcp://";
+    /// Default parser implementation for [Tcp]
+    pub const DEFAULT_PARSER: TcpParser = default_tcp_parser;
+
+    /// Instantiate a new Tcp parser with sensible defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convenience function for modifying the semantics of
+    /// this parser
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use grab::parsers::Tcp;
+    ///
+    /// // Accept addresses written as tcp:localhost:8080
+    /// let tcp = Tcp::new().with(|this| this.marker("tcp:"));
+    /// ```
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnMut(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        let mut actions = f;
+
+        actions(&mut this);
+
+        this
+    }
+
+    /// Modify the marker string for triggering this Tcp parser.
+    /// This marker is passed to the parser function as the second &str
+    /// argument.
+    pub fn marker(&mut self, marker: impl AsRef<str>) -> &mut Self {
+        self.marker = Some(marker.as_ref().to_string());
+
+        self
+    }
+
+    /// Replace the parser for this Tcp with a different one. Expects a
+    /// _function_ (not closure) with the following arguments + return:
+    ///
+    /// fn my_parser<'a>(input: &'a str, marker: &str) -> crate::nom::IResult<&'a str, String>
+    /// {
+    ///     /* ... */
+    /// }
+    ///
+    /// The returned address is still checked to be a valid `host:port`.
+    pub fn parser(&mut self, parser: TcpParser) -> &mut Self {
+        self.parser = Some(parser);
+
+        self
+    }
+
+    /// Set this parser's weight. Lower numbers will be ran before greater.
+    pub fn weight(&mut self, weight: u8) -> &mut Self {
+        self.weight = Some(weight);
+
+        self
+    }
+
+    /// Attach a human readable label to this parser, which will be included in any
+    /// [InputError] it produces.
+    pub fn label(&mut self, label: impl AsRef<str>) -> &mut Self {
+        self.label = Some(label.as_ref().to_string());
+
+        self
+    }
+
+    fn get_weight(&self) -> u8 {
+        self.weight.unwrap_or(Self::DEFAULT_WEIGHT)
+    }
+
+    fn get_marker(&self) -> &str {
+        self.marker.as_deref().unwrap_or(Self::DEFAULT_MARKER)
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<String, NomError<&'a str>> {
+        let marker = self.get_marker();
+
+        let (_, addr) = self
+            .parser
+            .map(|p| p(input, marker))
+            .unwrap_or_else(|| Self::DEFAULT_PARSER(input, marker))
+            .finish()?;
+
+        Ok(addr)
+    }
+
+    fn new_error(&self, p_error: NomError<&str>) -> InputError {
+        self.error()
+            .with(|this| this.add_context(failed_at(&p_error)))
+    }
+
+    fn error(&self) -> InputError {
+        let mut error = InputError::new(EKind::TCP);
+
+        if let Some(ref label) = self.label {
+            error.add_label(label);
+        }
+
+        error
+    }
+}
+
+impl Parser for Tcp {
+    fn parse_str(&self, s: &str) -> Result<InputType, InputError> {
+        let addr = self.parse(s).map_err(|e| self.new_error(e))?;
+
+        match is_address(&addr) {
+            true => Ok(InputType::Tcp(addr)),
+            false => Err(self.error().with(|this| {
+                this.add_context(format!("{} is not a valid host:port address", addr))
+            })),
+        }
+    }
+}
+
+impl Weight for Tcp {
+    fn weight(&self) -> u8 {
+        self.get_weight()
+    }
+}
+
+impl Named for Tcp {
+    fn name(&self) -> ParserName {
+        ParserName::Tcp
+    }
+
+    fn marker(&self) -> &str {
+        self.get_marker()
+    }
+}
+
+impl fmt::Debug for Tcp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tcp")
+            .field("marker", &self.get_marker())
+            .field(
+                "parser",
+                &self
+                    .parser
+                    .map_or("Default TcpParser", |_| "Custom TcpParser"),
+            )
+            .finish()
+    }
+}
+
+/// Check that addr is an IP address and port, or a plausible host name and port
+fn is_address(addr: &str) -> bool {
+    if addr.parse::<SocketAddr>().is_ok() {
+        return true;
+    }
+
+    match addr.rsplit_once(':') {
+        Some((host, port)) => {
+            port.parse::<u16>().is_ok()
+                && !host.is_empty()
+                && host
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+        }
+        None => false,
+    }
+}
+
+/// Default parser for TCP addresses. It expects input starting with the 'marker', returning the
+/// rest of the input as the address.
+pub fn default_tcp_parser<'a>(input: &'a str, marker: &str) -> nom::IResult<&'a str, String> {
+    nom::context("TCP", nom::tag(marker))(input).map(|(addr, _)| ("", String::from(addr)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_success() {
+        for &(input, addr) in &[
+            ("tcp://localhost:8080", "localhost:8080"),
+            ("tcp://127.0.0.1:1", "127.0.0.1:1"),
+            ("tcp://[::1]:65535", "[::1]:65535"),
+            ("tcp://my-host.example.com:80", "my-host.example.com:80"),
+        ] {
+            let parser = Tcp::new();
+
+            let result = parser.parse_str(input);
+
+            assert_eq!(result, Ok(InputType::Tcp(addr.to_string())), "{}", input)
+        }
+    }
+
+    #[test]
+    fn defaults_failure() {
+        for input in &[
+            "localhost:8080",
+            "tcp://",
+            "tcp://localhost",
+            "tcp://localhost:",
+            "tcp://localhost:65536",
+            "tcp://:8080",
+            "tcp://::1:8080",
+            "tcp://local host:8080",
+        ] {
+            let parser = Tcp::new();
+
+            let result = parser.parse_str(input);
+
+            assert_eq!(result, Err(EKind::TCP.into()), "{}", input)
+        }
+    }
+
+    #[test]
+    fn c_marker_success() {
+        let input = "tcp:localhost:8080";
+        let output = String::from("localhost:8080");
+
+        let parser = Tcp::new().with(|this| this.marker("tcp:"));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Ok(InputType::Tcp(output)))
+    }
+
+    #[test]
+    fn c_parser_success() {
+        let input = "8080";
+        let output = String::from("localhost:8080");
+
+        let parser = Tcp::new().with(|this| this.parser(test_custom_parser));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Ok(InputType::Tcp(output)))
+    }
+
+    fn test_custom_parser<'a>(input: &'a str, _: &str) -> nom::IResult<&'a str, String> {
+        Ok(("", format!("localhost:{}", input)))
+    }
+}
@@ -1,12 +1,17 @@
 use super::{
+    failed_at,
     nom::{self, Finish},
-    EKind, InputError, InputType, NomError, Parser, Weight,
+    EKind, InputError, InputType, Named, NomError, Parser, ParserName, Weight,
 };
 
-use std::fmt;
+use std::{borrow::Cow, fmt, sync::Arc};
 
 pub type TextParser = for<'a, 'b> fn(&'a str, &'b str) -> nom::IResult<&'a str, String>;
 
+/// Closure equivalent of [TextParser], see [Text::parser_fn]
+pub type TextParserFn =
+    dyn for<'a, 'b> Fn(&'a str, &'b str) -> nom::IResult<&'a str, String> + Send + Sync;
+
 /// Construct for treating the given input to parse as a readable input source. By default, this
 /// parser will consume any valid utf8 strings and return it as an input source. Consequently, this
 /// parser by default has the lowest possible priority so it will always be the last parser run.
@@ -14,7 +19,15 @@ pub type TextParser = for<'a, 'b> fn(&'a str, &'b str) -> nom::IResult<&'a str,
 pub struct Text {
     marker: Option<String>,
     parser: Option<TextParser>,
+    parser_fn: Option<Arc<TextParserFn>>,
     weight: Option<u8>,
+    label: Option<String>,
+    require_marker: bool,
+    unquote: bool,
+    trim: bool,
+    max_len: Option<usize>,
+    case_insensitive: bool,
+    default_value: Option<String>,
 }
 
 impl Text {
@@ -22,7 +35,7 @@ impl Text {
     /// this parser will never fail for UTF8 input.
     pub const DEFAULT_WEIGHT: u8 = 255;
     /// Default marker for [Text]
-    pub const DEFAULT_MARKER: &'static str = "";
+    pub const DEFAULT_MARKER: &str = "";
     /// Default parser implementation for [Text]
     pub const DEFAULT_PARSER: TextParser = default_text_parser;
 
@@ -66,7 +79,7 @@ impl Text {
     /// Replace the parser for this File with a different one. Expects a
     /// _function_ (not closure) with the following arguments + return:
     ///
-    /// fn my_parser<'a, 'b>(input: &'a str, marker: &'b str) -> crate::nom::IResult<&'a str, String>
+    /// fn my_parser<'a>(input: &'a str, marker: &str) -> crate::nom::IResult<&'a str, String>
     /// {
     ///     /* ... */
     /// }
@@ -76,6 +89,20 @@ impl Text {
         self
     }
 
+    /// Like [parser][Text::parser], but accepts a closure which may capture state. If set, this
+    /// is used in preference to any function given to [parser][Text::parser].
+    pub fn parser_fn<F>(&mut self, parser: F) -> &mut Self
+    where
+        F: for<'a, 'b> Fn(&'a str, &'b str) -> nom::IResult<&'a str, String>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.parser_fn = Some(Arc::new(parser));
+
+        self
+    }
+
     /// Set this parser's weight. Lower numbers will be ran before greater.
     pub fn weight(&mut self, weight: u8) -> &mut Self {
         self.weight = Some(weight);
@@ -83,6 +110,70 @@ impl Text {
         self
     }
 
+    /// Attach a human readable label to this parser, which will be included in any
+    /// [InputError] it produces.
+    pub fn label(&mut self, label: impl AsRef<str>) -> &mut Self {
+        self.label = Some(label.as_ref().to_string());
+
+        self
+    }
+
+    /// Require that input starts with a non-empty marker for this parser to succeed. When set,
+    /// a Text parser with an empty marker (the default) will fail on every input rather than
+    /// accepting everything, so unrecognized input becomes an error instead of silently being
+    /// treated as text.
+    pub fn require_marker(&mut self, require: bool) -> &mut Self {
+        self.require_marker = require;
+
+        self
+    }
+
+    /// Remove one matching pair of single or double quotes surrounding the parsed text, so
+    /// `"some value"` becomes `some value`. Text without a matching pair of quotes, like
+    /// `"unterminated`, is left untouched.
+    pub fn unquote(&mut self, unquote: bool) -> &mut Self {
+        self.unquote = unquote;
+
+        self
+    }
+
+    /// Trim leading and trailing ASCII whitespace from the parsed text, e.g the trailing newline
+    /// left by a shell pipeline. This happens before any [unquote][Text::unquote], so quoted
+    /// whitespace is kept.
+    pub fn trim(&mut self, trim: bool) -> &mut Self {
+        self.trim = trim;
+
+        self
+    }
+
+    /// Reject text longer than the given number of characters, guarding against absurdly long
+    /// arguments. The length is checked after any [trim][Text::trim] or
+    /// [unquote][Text::unquote]. By default, text of any length is accepted.
+    pub fn max_len(&mut self, max: usize) -> &mut Self {
+        self.max_len = Some(max);
+
+        self
+    }
+
+    /// Match the marker ignoring ASCII case, so `txt:` also accepts `TXT:`. By default, markers are
+    /// case-sensitive. This only applies to the default parser, custom [parsers][Text::parser]
+    /// are given the marker unchanged.
+    pub fn case_insensitive(&mut self, insensitive: bool) -> &mut Self {
+        self.case_insensitive = insensitive;
+
+        self
+    }
+
+    /// Use the given text in place of empty text, e.g an empty argument, or one which is empty
+    /// once its marker and any [trim][Text::trim]med whitespace are removed. Text which is only
+    /// empty after being [unquote][Text::unquote]d, like `""`, is kept as is. By default, empty
+    /// text is accepted unchanged.
+    pub fn default_value(&mut self, default: impl Into<String>) -> &mut Self {
+        self.default_value = Some(default.into());
+
+        self
+    }
+
     fn get_marker(&self) -> &str {
         self.marker.as_deref().unwrap_or(Self::DEFAULT_MARKER)
     }
@@ -91,28 +182,72 @@ impl Text {
         self.weight.unwrap_or(Self::DEFAULT_WEIGHT)
     }
 
-    fn parse<'a>(&self, input: &'a str) -> Result<String, NomError<&'a str>> {
+    /// Parse the input, borrowing from it unless a custom parser was set, or the text was
+    /// replaced by the default value, so that the text is only allocated once by
+    /// [parse_str][Parser::parse_str]
+    fn parse<'a>(&self, input: &'a str) -> Result<Cow<'a, str>, NomError<&'a str>> {
         let marker = self.get_marker();
 
-        let (_, text) = self
-            .parser
-            .map(|p| p(input, marker))
-            .unwrap_or_else(|| Self::DEFAULT_PARSER(input, marker))
-            .finish()?;
+        let marked = match self.case_insensitive {
+            true => input
+                .get(..marker.len())
+                .is_some_and(|head| head.eq_ignore_ascii_case(marker)),
+            false => input.starts_with(marker),
+        };
 
-        Ok(text)
+        if self.require_marker && (marker.is_empty() || !marked) {
+            return Err(nom::make_error(input, nom::ErrorKind::Tag));
+        }
+
+        let (_, text) = match (&self.parser_fn, self.parser) {
+            (Some(f), _) => f(input, marker).map(|(rest, text)| (rest, Cow::Owned(text))),
+            (None, Some(p)) => p(input, marker).map(|(rest, text)| (rest, Cow::Owned(text))),
+            (None, None) => text_span(input, marker, self.case_insensitive)
+                .map(|(rest, text)| (rest, Cow::Borrowed(text))),
+        }
+        .finish()?;
+
+        let text = match self.trim {
+            true => narrow(text, |t| t.trim_matches(|c: char| c.is_ascii_whitespace())),
+            false => text,
+        };
+
+        if let (true, Some(default)) = (text.is_empty(), &self.default_value) {
+            return Ok(Cow::Owned(default.clone()));
+        }
+
+        match self.unquote {
+            true => Ok(narrow(text, unquote)),
+            false => Ok(text),
+        }
+    }
+
+    fn new_error(&self, p_error: NomError<&str>) -> InputError {
+        self.error()
+            .with(|this| this.add_context(failed_at(&p_error)))
     }
 
-    fn new_error(&self, _p_error: NomError<&str>) -> InputError {
-        InputError::new(EKind::TEXT)
+    fn error(&self) -> InputError {
+        let mut error = InputError::new(EKind::TEXT);
+
+        if let Some(ref label) = self.label {
+            error.add_label(label);
+        }
+
+        error
     }
 }
 
 impl Parser for Text {
     fn parse_str(&self, s: &str) -> Result<InputType, InputError> {
-        self.parse(s)
-            .map(InputType::UTF8)
-            .map_err(|e| self.new_error(e))
+        let text = self.parse(s).map_err(|e| self.new_error(e))?;
+
+        match self.max_len {
+            Some(max) if text.chars().count() > max => Err(self
+                .error()
+                .with(|this| this.add_context(format!("text is longer than {} characters", max)))),
+            _ => Ok(InputType::UTF8(text.into_owned())),
+        }
     }
 }
 
@@ -122,15 +257,26 @@ impl Weight for Text {
     }
 }
 
+impl Named for Text {
+    fn name(&self) -> ParserName {
+        ParserName::Text
+    }
+
+    fn marker(&self) -> &str {
+        self.get_marker()
+    }
+}
+
 impl fmt::Debug for Text {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Text")
             .field("marker", &self.get_marker())
             .field(
                 "parser",
-                &self
-                    .parser
-                    .map_or("Default TextParser", |_| "Custom TextParser"),
+                &match self.parser.is_some() || self.parser_fn.is_some() {
+                    true => "Custom TextParser",
+                    false => "Default TextParser",
+                },
             )
             .finish()
     }
@@ -139,23 +285,58 @@ impl fmt::Debug for Text {
 /// Default text parser, if the given marker is empty (i.e "") it returns
 /// the entire input unmodified, otherwise it will return everything after
 /// the given marker
-pub fn default_text_parser<'a, 'b>(
+pub fn default_text_parser<'a>(input: &'a str, marker: &str) -> nom::IResult<&'a str, String> {
+    text_span(input, marker, false).map(|(rest, text)| (rest, String::from(text)))
+}
+
+/// The borrowing equivalent of [default_text_parser], optionally matching the marker ignoring
+/// ASCII case
+fn text_span<'a>(
     input: &'a str,
-    marker: &'b str,
-) -> nom::IResult<&'a str, String> {
+    marker: &str,
+    case_insensitive: bool,
+) -> nom::IResult<&'a str, &'a str> {
     // If the marker is empty (the default) we just return everything
     if marker.is_empty() {
-        Ok(("", input.to_string()))
-    } else {
-        nom::context("TEXT", nom::tag(marker))(input).map(|(path, _)| ("", String::from(path)))
+        return Ok(("", input));
     }
+
+    let tagged = match case_insensitive {
+        true => nom::context("TEXT", nom::tag_no_case(marker))(input),
+        false => nom::context("TEXT", nom::tag(marker))(input),
+    };
+
+    tagged.map(|(text, _)| ("", text))
+}
+
+/// Replace the text with the slice of it returned by f, which only allocates if the text was
+/// already owned and f shortened it
+fn narrow<'a>(text: Cow<'a, str>, f: impl for<'s> Fn(&'s str) -> &'s str) -> Cow<'a, str> {
+    match text {
+        Cow::Borrowed(text) => Cow::Borrowed(f(text)),
+        Cow::Owned(text) => match f(&text) {
+            inner if inner.len() == text.len() => Cow::Owned(text),
+            inner => Cow::Owned(inner.to_string()),
+        },
+    }
+}
+
+/// Strip a matching pair of surrounding quotes from the text, if it has one
+fn unquote(text: &str) -> &str {
+    ['"', '\'']
+        .iter()
+        .find_map(|&quote| {
+            text.strip_prefix(quote)
+                .and_then(|rest| rest.strip_suffix(quote))
+        })
+        .unwrap_or(text)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &'static str = "some arbitrary text";
+    const INPUT: &str = "some arbitrary text";
 
     #[test]
     fn defaults_success() {
@@ -169,6 +350,25 @@ mod tests {
         assert_eq!(result, Ok(InputType::UTF8(output)))
     }
 
+    #[test]
+    fn c_case_insensitive() {
+        let parser = Text::new().with(|this| {
+            this.marker("txt:")
+                .require_marker(true)
+                .case_insensitive(true)
+        });
+
+        let result = parser.parse_str("TXT:Some Text");
+
+        assert_eq!(result, Ok(InputType::UTF8(String::from("Some Text"))));
+        assert_eq!(
+            Text::new()
+                .with(|this| this.marker("txt:"))
+                .parse_str("TXT:x"),
+            Err(EKind::TEXT.into())
+        )
+    }
+
     #[test]
     fn c_marker_success() {
         let mkr = "!!";
@@ -208,6 +408,106 @@ mod tests {
         assert_eq!(result, Ok(InputType::UTF8(output)))
     }
 
+    #[test]
+    fn c_parser_fn_success() {
+        let marker = String::from("txt:");
+
+        let parser = Text::new().with(|this| {
+            let marker = marker.clone();
+
+            // Preferred over the fn pointer
+            this.parser(test_custom_parser).parser_fn(move |input, _| {
+                nom::tag(marker.as_str())(input).map(|(text, _)| ("", text.to_uppercase()))
+            })
+        });
+
+        assert_eq!(
+            parser.parse_str("txt:some text"),
+            Ok(InputType::UTF8(String::from("SOME TEXT")))
+        );
+        assert_eq!(parser.parse_str(INPUT), Err(EKind::TEXT.into()))
+    }
+
+    #[test]
+    fn c_unquote() {
+        let parser = Text::new().with(|this| this.unquote(true));
+
+        for &(input, output) in &[
+            ("\"x\"", "x"),
+            ("'x'", "x"),
+            ("\"x", "\"x"),
+            ("'x\"", "'x\""),
+            ("\"", "\""),
+            ("x", "x"),
+        ] {
+            let result = parser.parse_str(input);
+
+            assert_eq!(result, Ok(InputType::UTF8(output.into())), "{}", input)
+        }
+    }
+
+    #[test]
+    fn c_trim() {
+        let input = "  hi \n";
+
+        let result = Text::new().with(|this| this.trim(true)).parse_str(input);
+        assert_eq!(result, Ok(InputType::UTF8("hi".into())));
+
+        let result = Text::new().parse_str(input);
+        assert_eq!(result, Ok(InputType::UTF8(input.into())));
+
+        let result = Text::new()
+            .with(|this| this.trim(true).unquote(true))
+            .parse_str(" ' hi '\n");
+        assert_eq!(result, Ok(InputType::UTF8(" hi ".into())))
+    }
+
+    #[test]
+    fn c_default_value() {
+        let result = Text::new()
+            .with(|this| this.default_value("fallback"))
+            .parse_str("");
+        assert_eq!(result, Ok(InputType::UTF8("fallback".into())));
+
+        let parser = Text::new().with(|this| this.marker("txt:").default_value("fallback"));
+
+        let result = parser.parse_str("txt:");
+        assert_eq!(result, Ok(InputType::UTF8("fallback".into())));
+
+        let result = parser.parse_str("txt:given");
+        assert_eq!(result, Ok(InputType::UTF8("given".into())));
+
+        let result = Text::new().parse_str("");
+        assert_eq!(result, Ok(InputType::UTF8("".into())))
+    }
+
+    #[test]
+    fn c_default_value_trim() {
+        let parser = Text::new().with(|this| this.trim(true).unquote(true).default_value("x"));
+
+        let result = parser.parse_str(" \n");
+        assert_eq!(result, Ok(InputType::UTF8("x".into())));
+
+        let result = parser.parse_str("\"\"");
+        assert_eq!(result, Ok(InputType::UTF8("".into())))
+    }
+
+    #[test]
+    fn c_max_len() {
+        let parser = Text::new().with(|this| this.max_len(3));
+
+        for input in &["", "ab", "abc", "äöü"] {
+            assert_eq!(
+                parser.parse_str(input),
+                Ok(InputType::UTF8(input.to_string()))
+            )
+        }
+
+        let err = parser.parse_str("abcd").unwrap_err();
+        assert!(err.contains(EKind::TEXT));
+        assert!(err.to_string().contains("longer than 3"))
+    }
+
     #[test]
     fn c_parser_failure() {
         let input = "";
@@ -219,10 +519,126 @@ mod tests {
         assert_eq!(result, Err(EKind::TEXT.into()))
     }
 
-    fn test_custom_parser<'a, 'b>(
-        input: &'a str,
-        marker: &'b str,
-    ) -> nom::IResult<&'a str, String> {
+    #[test]
+    fn require_marker_success() {
+        let input = "!!valid text";
+        let output = String::from("valid text");
+
+        let parser = Text::new().with(|this| this.marker("!!").require_marker(true));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Ok(InputType::UTF8(output)))
+    }
+
+    #[test]
+    fn require_marker_unmarked_failure() {
+        let input = "no marker";
+
+        let parser = Text::new().with(|this| this.marker("!!").require_marker(true));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Err(EKind::TEXT.into()))
+    }
+
+    #[test]
+    fn require_marker_empty_marker_failure() {
+        let input = INPUT;
+
+        let parser = Text::new().with(|this| this.require_marker(true));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Err(EKind::TEXT.into()))
+    }
+
+    #[test]
+    fn require_marker_c_parser_failure() {
+        let input = "unmarked";
+
+        let parser = Text::new().with(|this| {
+            this.marker("!!")
+                .parser(test_custom_parser)
+                .require_marker(true)
+        });
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Err(EKind::TEXT.into()))
+    }
+
+    #[test]
+    fn borrowed_owned_equivalence() {
+        let inputs = [
+            "",
+            " ",
+            "txt:",
+            "TXT:",
+            "txt:x",
+            "  txt:x  ",
+            "txt: x ",
+            "'x'",
+            "\"x\"",
+            "\"x'",
+            "txt:\" x \"",
+            " \"\" ",
+            "ünïcödé",
+            "txt:ünïcödé ",
+        ];
+
+        for &marker in &["", "txt:"] {
+            for options in 0..16 {
+                let configured = || {
+                    Text::new().with(|this| {
+                        this.marker(marker)
+                            .trim(options & 1 != 0)
+                            .unquote(options & 2 != 0)
+                            .case_insensitive(options & 4 != 0);
+
+                        if options & 8 != 0 {
+                            this.default_value("default");
+                        }
+
+                        this
+                    })
+                };
+
+                // Setting the default parser explicitly takes the owned path
+                let borrowed = configured();
+                let owned = configured().with(|this| {
+                    this.parser(match options & 4 != 0 {
+                        true => test_no_case_parser,
+                        false => Text::DEFAULT_PARSER,
+                    })
+                });
+
+                for &input in &inputs {
+                    let text = borrowed.parse(input);
+                    let ctx = (marker, options, input);
+
+                    if let Ok(ref text) = text {
+                        let replaced = options & 8 != 0 && text == "default";
+                        assert!(replaced || matches!(text, Cow::Borrowed(_)), "{:?}", ctx);
+                    }
+
+                    assert_eq!(text, owned.parse(input), "{:?}", ctx);
+                    assert_eq!(
+                        borrowed.parse_str(input),
+                        owned.parse_str(input),
+                        "{:?}",
+                        ctx
+                    );
+                }
+            }
+        }
+    }
+
+    fn test_no_case_parser<'a>(input: &'a str, marker: &str) -> nom::IResult<&'a str, String> {
+        nom::tag_no_case(marker)(input).map(|(text, _)| ("", String::from(text)))
+    }
+
+    fn test_custom_parser<'a>(input: &'a str, marker: &str) -> nom::IResult<&'a str, String> {
         use ::nom::error::{make_error, ErrorKind};
         if input.is_empty() {
             Err(::nom::Err::Error(make_error(input, ErrorKind::NonEmpty)))
@@ -0,0 +1,244 @@
+use super::{
+    failed_at,
+    nom::{self, Finish},
+    EKind, InputError, InputType, Named, NomError, Parser, ParserName, Weight,
+};
+
+use std::fmt;
+
+/// Function signature of the parser Url calls for processing input
+pub type UrlParser = for<'a, 'b> fn(&'a str, &'b str) -> nom::IResult<&'a str, String>;
+
+/// Construct for treating the given input as a URL, which is fetched when the input is accessed.
+/// By default, this parser accepts any input beginning with `http://` or `https://`.
+///
+/// Fetching requires the `http` feature, without it accessing a URL input will always fail.
+#[derive(Clone, Default)]
+pub struct Url {
+    marker: Option<String>,
+    parser: Option<UrlParser>,
+    weight: Option<u8>,
+    label: Option<String>,
+}
+
+impl Url {
+    /// The default weighting for [Url], which sits between [File][super::File] and
+    /// [Stdin][super::Stdin]
+    pub const DEFAULT_WEIGHT: u8 = 135;
+    /// Default marker for [Url]. Note that this is empty, as the URL's scheme already marks it
+    pub const DEFAULT_MARKER: &str = "";
+    /// Default parser implementation for [Url]
+    pub const DEFAULT_PARSER: UrlParser = default_url_parser;
+
+    /// Instantiate a new Url parser with sensible defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convenience function for modifying the semantics of
+    /// this parser
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use grab::parsers::Url;
+    ///
+    /// // Require URLs to be written as url:https://...
+    /// let url = Url::new().with(|this| this.marker("url:"));
+    /// ```
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnMut(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        let mut actions = f;
+
+        actions(&mut this);
+
+        this
+    }
+
+    /// Modify the marker string for triggering this Url parser.
+    /// This marker is passed to the parser function as the second &str
+    /// argument.
+    pub fn marker(&mut self, marker: impl AsRef<str>) -> &mut Self {
+        self.marker = Some(marker.as_ref().to_string());
+
+        self
+    }
+
+    /// Replace the parser for this Url with a different one. Expects a
+    /// _function_ (not closure) with the following arguments + return:
+    ///
+    /// fn my_parser<'a>(input: &'a str, marker: &str) -> crate::nom::IResult<&'a str, String>
+    /// {
+    ///     /* ... */
+    /// }
+    pub fn parser(&mut self, parser: UrlParser) -> &mut Self {
+        self.parser = Some(parser);
+
+        self
+    }
+
+    /// Set this parser's weight. Lower numbers will be ran before greater.
+    pub fn weight(&mut self, weight: u8) -> &mut Self {
+        self.weight = Some(weight);
+
+        self
+    }
+
+    /// Attach a human readable label to this parser, which will be included in any
+    /// [InputError] it produces.
+    pub fn label(&mut self, label: impl AsRef<str>) -> &mut Self {
+        self.label = Some(label.as_ref().to_string());
+
+        self
+    }
+
+    fn get_weight(&self) -> u8 {
+        self.weight.unwrap_or(Self::DEFAULT_WEIGHT)
+    }
+
+    fn get_marker(&self) -> &str {
+        self.marker.as_deref().unwrap_or(Self::DEFAULT_MARKER)
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<String, NomError<&'a str>> {
+        let marker = self.get_marker();
+
+        let (_, url) = self
+            .parser
+            .map(|p| p(input, marker))
+            .unwrap_or_else(|| Self::DEFAULT_PARSER(input, marker))
+            .finish()?;
+
+        Ok(url)
+    }
+
+    fn new_error(&self, p_error: NomError<&str>) -> InputError {
+        let mut error = InputError::new(EKind::URL);
+
+        if let Some(ref label) = self.label {
+            error.add_label(label);
+        }
+
+        error.add_context(failed_at(&p_error));
+
+        error
+    }
+}
+
+impl Parser for Url {
+    fn parse_str(&self, s: &str) -> Result<InputType, InputError> {
+        self.parse(s)
+            .map(InputType::Url)
+            .map_err(|e| self.new_error(e))
+    }
+}
+
+impl Weight for Url {
+    fn weight(&self) -> u8 {
+        self.get_weight()
+    }
+}
+
+impl Named for Url {
+    fn name(&self) -> ParserName {
+        ParserName::Url
+    }
+
+    fn marker(&self) -> &str {
+        self.get_marker()
+    }
+}
+
+impl fmt::Debug for Url {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Url")
+            .field("marker", &self.get_marker())
+            .field(
+                "parser",
+                &self
+                    .parser
+                    .map_or("Default UrlParser", |_| "Custom UrlParser"),
+            )
+            .finish()
+    }
+}
+
+/// Default parser for URLs. It expects input starting with the 'marker', followed by a URL with
+/// an `http` or `https` scheme, returning the URL.
+pub fn default_url_parser<'a>(input: &'a str, marker: &str) -> nom::IResult<&'a str, String> {
+    let (url, _) = nom::context("URL", nom::tag(marker))(input)?;
+    let scheme = nom::alt((nom::tag("http://"), nom::tag("https://")));
+
+    nom::context("URL", scheme)(url).map(|_| ("", String::from(url)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BAD_INPUT: &str = "invalid url input";
+
+    #[test]
+    fn defaults_success() {
+        for input in &["http://example.com/a", "https://example.com/b?c=d"] {
+            let parser = Url::new();
+
+            let result = parser.parse_str(input);
+
+            assert_eq!(result, Ok(InputType::Url(input.to_string())))
+        }
+    }
+
+    #[test]
+    fn defaults_failure() {
+        for input in &[BAD_INPUT, "ftp://example.com", "@http://example.com"] {
+            let parser = Url::new();
+
+            let result = parser.parse_str(input);
+
+            assert_eq!(result, Err(EKind::URL.into()))
+        }
+    }
+
+    #[test]
+    fn c_marker_success() {
+        let input = "url:https://example.com";
+        let output = String::from("https://example.com");
+
+        let parser = Url::new().with(|this| this.marker("url:"));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Ok(InputType::Url(output)))
+    }
+
+    #[test]
+    fn c_marker_failure() {
+        let input = "https://example.com";
+
+        let parser = Url::new().with(|this| this.marker("url:"));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Err(EKind::URL.into()))
+    }
+
+    #[test]
+    fn c_parser_success() {
+        let input = "example.com";
+        let output = String::from("http://example.com");
+
+        let parser = Url::new().with(|this| this.parser(test_custom_parser));
+
+        let result = parser.parse_str(input);
+
+        assert_eq!(result, Ok(InputType::Url(output)))
+    }
+
+    fn test_custom_parser<'a>(input: &'a str, _: &str) -> nom::IResult<&'a str, String> {
+        Ok(("", format!("http://{}", input)))
+    }
+}
@@ -0,0 +1,191 @@
+//! [serde] support for [Input], which is (de)serialized as the argument it was parsed from, and
+//! [Input::to_json] for describing how an input was resolved.
+
+use std::fmt;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Config, Input, InputKind};
+
+impl Input {
+    /// Describe how this input was resolved as a JSON object, for `--explain` or `--dry-run`
+    /// style output. The object always has a `kind`, e.g `"file"` or `"stdin"`, and a `raw`
+    /// field holding the [spec][Input::spec] it was parsed from, or its [Display][fmt::Display]
+    /// form for inputs which weren't parsed, and files additionally have a `path`.
+    ///
+    /// ```
+    /// use grab::Input;
+    ///
+    /// let input = Input::with_defaults("@notes.txt").unwrap();
+    ///
+    /// assert_eq!(
+    ///     input.to_json(),
+    ///     r#"{"kind":"file","path":"notes.txt","raw":"@notes.txt"}"#
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut object = serde_json::Map::new();
+
+        object.insert("kind".into(), kind_name(self.kind()).into());
+
+        if let Some(path) = self.path() {
+            object.insert("path".into(), path.to_string_lossy().into());
+        }
+
+        let raw = match self.spec() {
+            Some(spec) => spec.to_string(),
+            None => self.to_string(),
+        };
+        object.insert("raw".into(), raw.into());
+
+        serde_json::Value::Object(object).to_string()
+    }
+}
+
+fn kind_name(kind: InputKind) -> &'static str {
+    match kind {
+        InputKind::Stdin => "stdin",
+        InputKind::File => "file",
+        InputKind::Files => "files",
+        InputKind::Text => "text",
+        InputKind::Url => "url",
+        InputKind::Env => "env",
+        InputKind::Base64 => "base64",
+        InputKind::Bytes => "bytes",
+        InputKind::Reader => "reader",
+        #[cfg(feature = "tcp")]
+        InputKind::Tcp => "tcp",
+        #[cfg(feature = "clipboard")]
+        InputKind::Clipboard => "clipboard",
+    }
+}
+
+/// Inputs are serialized as the [spec][Input::spec] they were parsed from, or for inputs which
+/// weren't parsed, their [Display][fmt::Display] form.
+impl Serialize for Input {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.spec() {
+            Some(spec) => serializer.serialize_str(spec),
+            None => serializer.collect_str(self),
+        }
+    }
+}
+
+/// Inputs are deserialized from a string, which is parsed with the [default][Config::default]
+/// config. Deserialize a string and parse it yourself to use a custom config.
+impl<'de> Deserialize<'de> for Input {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(InputVisitor)
+    }
+}
+
+struct InputVisitor;
+
+impl de::Visitor<'_> for InputVisitor {
+    type Value = Input;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a string describing an input, like `-` or `@file`")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Input, E>
+    where
+        E: de::Error,
+    {
+        Config::default().parse(v).map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_kinds() {
+        let cases = [
+            (r#""-""#, InputKind::Stdin),
+            (r#""@config.toml""#, InputKind::File),
+            (r#""some text""#, InputKind::Text),
+        ];
+
+        for &(json, kind) in cases.iter() {
+            let input: Input = serde_json::from_str(json).unwrap();
+
+            assert_eq!(input.kind(), kind, "{}", json)
+        }
+    }
+
+    #[test]
+    fn deserialize_not_a_string() {
+        assert!(serde_json::from_str::<Input>("42").is_err())
+    }
+
+    #[test]
+    fn serialize_round_trip() {
+        for json in [r#""-""#, r#""@./config.toml""#, r#""some text""#].iter() {
+            let input: Input = serde_json::from_str(json).unwrap();
+
+            assert_eq!(&serde_json::to_string(&input).unwrap(), json);
+        }
+
+        // Inputs which weren't parsed use their Display form
+        let input = Input::from_text("some text");
+        assert_eq!(serde_json::to_string(&input).unwrap(), r#""some text""#)
+    }
+
+    #[test]
+    fn to_json_kinds() {
+        let cfg = crate::Builder::new()
+            .with(|this| this.text().stdin().file().url().env().base64())
+            .build();
+
+        let cases = [
+            ("-", r#"{"kind":"stdin","raw":"-"}"#),
+            ("@a.txt", r#"{"kind":"file","path":"a.txt","raw":"@a.txt"}"#),
+            (
+                "@./a.txt",
+                r#"{"kind":"file","path":"./a.txt","raw":"@./a.txt"}"#,
+            ),
+            ("some text", r#"{"kind":"text","raw":"some text"}"#),
+            (
+                "http://example.com",
+                r#"{"kind":"url","raw":"http://example.com"}"#,
+            ),
+            ("env:HOME", r#"{"kind":"env","raw":"env:HOME"}"#),
+            ("base64:aGk=", r#"{"kind":"base64","raw":"base64:aGk="}"#),
+        ];
+
+        for &(spec, json) in cases.iter() {
+            assert_eq!(cfg.parse(spec).unwrap().to_json(), json, "{}", spec)
+        }
+
+        assert_eq!(
+            Input::from_bytes(&b"hi"[..]).to_json(),
+            r#"{"kind":"bytes","raw":"base64:aGk="}"#
+        );
+        assert_eq!(
+            Input::from_reader(std::io::empty()).to_json(),
+            r#"{"kind":"reader","raw":"<reader>"}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_nested() {
+        #[derive(Deserialize)]
+        struct Settings {
+            inputs: Vec<Input>,
+        }
+
+        let settings: Settings =
+            serde_json::from_str(r#"{"inputs": ["-", "@a.txt", "text"]}"#).unwrap();
+        let kinds: Vec<_> = settings.inputs.iter().map(Input::kind).collect();
+
+        assert_eq!(kinds, [InputKind::Stdin, InputKind::File, InputKind::Text])
+    }
+}
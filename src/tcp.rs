@@ -0,0 +1,71 @@
+//! Connecting to the server behind a [Tcp][crate::parsers::Tcp] input
+
+use std::{
+    io,
+    net::{TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+/// How long to wait for a connection to be established, and for each read
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Connect to the given `host:port`, trying each address it resolves to in turn. Reads from the
+/// returned stream fail if the server sends nothing for [TIMEOUT].
+pub(crate) fn connect(addr: &str) -> io::Result<TcpStream> {
+    let mut last_err = None;
+
+    for addr in addr.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&addr, TIMEOUT) {
+            Ok(stream) => {
+                stream.set_read_timeout(Some(TIMEOUT))?;
+
+                return Ok(stream);
+            }
+            Err(e) => last_err = Some(e),
+        }
+    }
+
+    Err(last_err.unwrap_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "address did not resolve to any addresses",
+        )
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::TcpListener;
+
+    #[test]
+    fn connect_success() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+
+        let stream = connect(&addr).unwrap();
+
+        assert_eq!(stream.peer_addr().unwrap().to_string(), addr);
+        assert_eq!(stream.read_timeout().unwrap(), Some(TIMEOUT))
+    }
+
+    #[test]
+    fn connect_refused() {
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .to_string();
+
+        // Nothing listens on the port any more
+        let err = connect(&addr).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused)
+    }
+
+    #[test]
+    fn connect_invalid_address() {
+        assert!(connect("not an address").is_err())
+    }
+}
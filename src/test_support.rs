@@ -0,0 +1,117 @@
+//! Helpers shared by the unit tests of several modules
+
+use std::{
+    cell::Cell,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{
+    input::{Read, StdinSource},
+    parsers::SharedReader,
+};
+
+/// The path of the given file in `tests/fixtures`
+pub(crate) fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+/// A new, empty directory in the system's temp dir, which is removed along with everything in it
+/// when dropped, so nothing is left behind even if the test panics
+pub(crate) struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Create a directory for the named test, unique to this process and call
+    pub fn new(name: &str) -> Self {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        let dir = std::env::temp_dir().join(format!(
+            "grab-{}-{}-{}",
+            name,
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&dir).unwrap();
+
+        Self(dir)
+    }
+
+    /// The path of the given file in this directory, which isn't created
+    pub fn join(&self, name: &str) -> PathBuf {
+        self.0.join(name)
+    }
+
+    /// Write the given file into this directory, returning its path
+    pub fn write(&self, name: &str, contents: impl AsRef<[u8]>) -> PathBuf {
+        let path = self.join(name);
+        fs::write(&path, contents).unwrap();
+
+        path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Fixed data standing in for the process's stdin. The data is shared between every access, so
+/// like the real stdin it can only be consumed once.
+pub(crate) struct FakeStdin {
+    data: SharedReader,
+    accessed: Cell<bool>,
+}
+
+impl FakeStdin {
+    pub fn new(data: impl Into<Vec<u8>>) -> Self {
+        Self::with_error(data.into(), None)
+    }
+
+    /// Like [new](FakeStdin::new), but once the data is exhausted, reads fail with the given
+    /// error rather than returning EOF
+    pub fn failing(data: impl Into<Vec<u8>>, kind: io::ErrorKind) -> Self {
+        Self::with_error(data.into(), Some(kind))
+    }
+
+    fn with_error(data: Vec<u8>, error: Option<io::ErrorKind>) -> Self {
+        Self {
+            data: SharedReader::new(Failing {
+                data: io::Cursor::new(data),
+                error,
+            }),
+            accessed: Cell::new(false),
+        }
+    }
+}
+
+impl StdinSource for FakeStdin {
+    fn open(&self) -> Read {
+        Read::Reader(self.data.clone())
+    }
+
+    fn mark_accessed(&self) -> bool {
+        self.accessed.replace(true)
+    }
+
+    fn would_block(&self) -> bool {
+        false
+    }
+}
+
+struct Failing {
+    data: io::Cursor<Vec<u8>>,
+    error: Option<io::ErrorKind>,
+}
+
+impl io::Read for Failing {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match (io::Read::read(&mut self.data, buf)?, self.error) {
+            (0, Some(kind)) if !buf.is_empty() => Err(kind.into()),
+            (n, _) => Ok(n),
+        }
+    }
+}
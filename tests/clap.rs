@@ -0,0 +1,26 @@
+#![cfg(feature = "clap")]
+
+use clap::{Arg, Command};
+use grab::{Config, Input, InputKind};
+
+fn command() -> Command {
+    Command::new("cli").arg(
+        Arg::new("input")
+            .num_args(1..)
+            .value_parser(grab::clap::input_value_parser(Config::default())),
+    )
+}
+
+#[test]
+fn input_value_parser() {
+    let file = concat!("@", env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/lorem.txt");
+
+    let m = command().get_matches_from(["cli", "-", file, "some text"]);
+
+    let inputs: Vec<&Input> = m.get_many::<Input>("input").unwrap().collect();
+    let kinds: Vec<InputKind> = inputs.iter().map(|i| i.kind()).collect();
+
+    assert_eq!(kinds, [InputKind::Stdin, InputKind::File, InputKind::Text]);
+    assert!(!inputs[1].read_to_string().unwrap().is_empty());
+    assert_eq!(inputs[2].read_to_string().unwrap(), "some text")
+}